@@ -0,0 +1,399 @@
+//! # Automatic Differentiation Module
+//!
+//! This module provides a reverse-mode automatic differentiation engine over [`Matrix`]. Instead
+//! of deriving the gradient of every layer by hand, a layer is written as a forward pass over
+//! [`Tensor`]s and the gradients are computed by calling [`Tensor::backward`] on the loss.
+//!
+//! ## Key Concepts
+//!
+//! **Tape**: Every operation on a tensor appends a node to a [`Tape`], remembering which tensors
+//! it was computed from. The tape is a record of the whole computation, in the order it ran.
+//!
+//! **Reverse Mode**: `backward()` walks the tape from the loss back to the inputs. Each node
+//! receives the gradient of the loss with respect to its value and passes it on to its inputs
+//! using the chain rule, so one backward pass yields the gradient of every tensor at once.
+//! Gradients from several uses of the same tensor are summed.
+//!
+//! ## Available Operations
+//!
+//! - **Linear algebra**: `a * b` (matrix product), `a + b`, `a - b`, [`Tensor::hadamard`]
+//!   (element-wise product), [`Tensor::scale`], [`Tensor::add_row`] (broadcast a bias row),
+//!   [`Tensor::transpose`]
+//! - **Element-wise**: [`Tensor::sigmoid`], [`Tensor::tanh`], [`Tensor::relu`], [`Tensor::exp`],
+//!   [`Tensor::ln`], [`Tensor::square`]
+//! - **Reductions**: [`Tensor::sum`] and [`Tensor::mean`] (to a `1 × 1` tensor),
+//!   [`Tensor::column_sums`] (to a `1 × cols` row)
+//!
+//! ## Example Usage
+//!
+//! ```rust
+//! use aspirina_core::autograd::Tape;
+//! use aspirina_core::matrix::Matrix;
+//!
+//! let tape = Tape::new();
+//! let x = tape.variable(Matrix::new(vec![vec![1.0, 2.0]]));
+//! let w = tape.variable(Matrix::new(vec![vec![0.5], vec![-1.0]]));
+//!
+//! // loss = (x·w)² = (0.5 - 2)² = 2.25
+//! let loss = (&x * &w).square().sum();
+//! loss.backward();
+//!
+//! assert_eq!(loss.value().data, vec![vec![2.25]]);
+//! // d loss / d w = 2·(x·w)·xᵀ
+//! assert_eq!(w.gradient().unwrap().data, vec![vec![-3.0], vec![-6.0]]);
+//! ```
+
+use crate::calc::Calc;
+use crate::matrix::Matrix;
+use std::cell::RefCell;
+use std::ops::{Add, Mul, Sub};
+use std::rc::Rc;
+
+/// How a node was computed, referring to its inputs by tape position.
+#[derive(Debug, Clone)]
+enum Operation {
+    Variable,
+    MatMul(usize, usize),
+    Add(usize, usize),
+    Sub(usize, usize),
+    Hadamard(usize, usize),
+    Scale(usize, f64),
+    AddRow(usize, usize),
+    Transpose(usize),
+    Sigmoid(usize),
+    Tanh(usize),
+    Relu(usize),
+    Exp(usize),
+    Ln(usize),
+    Square(usize),
+    Sum(usize),
+    Mean(usize),
+    ColumnSums(usize),
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    value: Matrix,
+    operation: Operation,
+    gradient: Option<Matrix>,
+}
+
+/// The record of every tensor operation, shared by all tensors created from it.
+///
+/// Cloning a tape gives another handle to the same record.
+#[derive(Debug, Clone, Default)]
+pub struct Tape {
+    nodes: Rc<RefCell<Vec<Node>>>,
+}
+
+/// A matrix value on a [`Tape`] that knows how it was computed.
+///
+/// Tensors are cheap handles; cloning one refers to the same node.
+#[derive(Debug, Clone)]
+pub struct Tensor {
+    tape: Tape,
+    index: usize,
+}
+
+impl Tape {
+    /// Creates an empty tape.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `value` as an input tensor, such as a weight matrix or a batch of data.
+    pub fn variable(&self, value: Matrix) -> Tensor {
+        self.push(value, Operation::Variable)
+    }
+
+    /// Number of recorded nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.borrow().len()
+    }
+
+    /// Whether nothing has been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.borrow().is_empty()
+    }
+
+    fn push(&self, value: Matrix, operation: Operation) -> Tensor {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.push(Node {
+            value,
+            operation,
+            gradient: None,
+        });
+
+        Tensor {
+            tape: self.clone(),
+            index: nodes.len() - 1,
+        }
+    }
+
+    fn value(&self, index: usize) -> Matrix {
+        self.nodes.borrow()[index].value.clone()
+    }
+}
+
+impl Tensor {
+    /// A copy of the tensor's value.
+    pub fn value(&self) -> Matrix {
+        self.tape.value(self.index)
+    }
+
+    /// The gradient of the last [`backward`](Self::backward) call's tensor with respect to
+    /// this one, or `None` if this tensor did not contribute to it.
+    pub fn gradient(&self) -> Option<Matrix> {
+        self.tape.nodes.borrow()[self.index].gradient.clone()
+    }
+
+    /// Computes the gradient of this `1 × 1` tensor with respect to every tensor recorded
+    /// before it. Gradients of an earlier `backward()` call are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tensor is not `1 × 1`.
+    pub fn backward(&self) {
+        let value = self.value();
+        if value.data.len() != 1 || value.data[0].len() != 1 {
+            panic!("Backward needs a 1×1 tensor");
+        }
+
+        let mut nodes = self.tape.nodes.borrow_mut();
+        for node in nodes.iter_mut() {
+            node.gradient = None;
+        }
+        nodes[self.index].gradient = Some(Matrix::new(vec![vec![1.0]]));
+
+        for index in (0..=self.index).rev() {
+            let Some(gradient) = nodes[index].gradient.clone() else {
+                continue;
+            };
+
+            for (input, input_gradient) in input_gradients(&nodes, index, &gradient) {
+                let accumulated = match nodes[input].gradient.take() {
+                    Some(existing) => existing + input_gradient,
+                    None => input_gradient,
+                };
+                nodes[input].gradient = Some(accumulated);
+            }
+        }
+    }
+
+    /// Element-wise product, like [`Matrix::naive_multiply`].
+    pub fn hadamard(&self, other: &Tensor) -> Tensor {
+        let value = Matrix::naive_multiply(self.value(), other.value());
+        self.binary(other, value, Operation::Hadamard(self.index, other.index))
+    }
+
+    /// Multiplies every element by `factor`.
+    pub fn scale(&self, factor: f64) -> Tensor {
+        let value = self.value().scale(factor);
+        self.unary(value, Operation::Scale(self.index, factor))
+    }
+
+    /// Adds the `1 × cols` tensor `row` to every row, e.g. a bias.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is not a single row as wide as this tensor.
+    pub fn add_row(&self, row: &Tensor) -> Tensor {
+        let value = self.value();
+        let row_value = row.value();
+        if row_value.data.len() != 1 || row_value.data[0].len() != value.data[0].len() {
+            panic!("Incompatible dimensions");
+        }
+
+        let value = Matrix::new(
+            value
+                .data
+                .iter()
+                .map(|r| {
+                    r.iter()
+                        .zip(row_value.data[0].iter())
+                        .map(|(x, b)| x + b)
+                        .collect()
+                })
+                .collect(),
+        );
+        self.binary(row, value, Operation::AddRow(self.index, row.index))
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Tensor {
+        let value = self.value().transpose();
+        self.unary(value, Operation::Transpose(self.index))
+    }
+
+    /// Element-wise sigmoid.
+    pub fn sigmoid(&self) -> Tensor {
+        let value = map(&self.value(), |x| Calc::sigmoid(&x));
+        self.unary(value, Operation::Sigmoid(self.index))
+    }
+
+    /// Element-wise hyperbolic tangent.
+    pub fn tanh(&self) -> Tensor {
+        let value = map(&self.value(), |x| Calc::tanh(&x));
+        self.unary(value, Operation::Tanh(self.index))
+    }
+
+    /// Element-wise `max(x, 0)`.
+    pub fn relu(&self) -> Tensor {
+        let value = map(&self.value(), |x| x.max(0.0));
+        self.unary(value, Operation::Relu(self.index))
+    }
+
+    /// Element-wise `eˣ`.
+    pub fn exp(&self) -> Tensor {
+        let value = map(&self.value(), f64::exp);
+        self.unary(value, Operation::Exp(self.index))
+    }
+
+    /// Element-wise natural logarithm.
+    pub fn ln(&self) -> Tensor {
+        let value = map(&self.value(), f64::ln);
+        self.unary(value, Operation::Ln(self.index))
+    }
+
+    /// Element-wise `x²`.
+    pub fn square(&self) -> Tensor {
+        let value = map(&self.value(), |x| x * x);
+        self.unary(value, Operation::Square(self.index))
+    }
+
+    /// Sum of all elements, as a `1 × 1` tensor.
+    pub fn sum(&self) -> Tensor {
+        let value = self.value().data.iter().flatten().sum::<f64>();
+        self.unary(Matrix::new(vec![vec![value]]), Operation::Sum(self.index))
+    }
+
+    /// Mean of all elements, as a `1 × 1` tensor.
+    pub fn mean(&self) -> Tensor {
+        let value = self.value();
+        let count = value.data.len() * value.data[0].len();
+        let mean = value.data.iter().flatten().sum::<f64>() / count as f64;
+        self.unary(Matrix::new(vec![vec![mean]]), Operation::Mean(self.index))
+    }
+
+    /// Sums every column into a `1 × cols` row.
+    pub fn column_sums(&self) -> Tensor {
+        let value = column_sums(&self.value());
+        self.unary(value, Operation::ColumnSums(self.index))
+    }
+
+    fn unary(&self, value: Matrix, operation: Operation) -> Tensor {
+        self.tape.push(value, operation)
+    }
+
+    fn binary(&self, other: &Tensor, value: Matrix, operation: Operation) -> Tensor {
+        if !Rc::ptr_eq(&self.tape.nodes, &other.tape.nodes) {
+            panic!("Tensors belong to different tapes");
+        }
+
+        self.tape.push(value, operation)
+    }
+}
+
+impl Add for &Tensor {
+    type Output = Tensor;
+
+    fn add(self, other: &Tensor) -> Tensor {
+        let value = self.value() + other.value();
+        self.binary(other, value, Operation::Add(self.index, other.index))
+    }
+}
+
+impl Sub for &Tensor {
+    type Output = Tensor;
+
+    fn sub(self, other: &Tensor) -> Tensor {
+        let value = self.value() - other.value();
+        self.binary(other, value, Operation::Sub(self.index, other.index))
+    }
+}
+
+impl Mul for &Tensor {
+    type Output = Tensor;
+
+    /// Matrix product, like `Matrix * Matrix`.
+    fn mul(self, other: &Tensor) -> Tensor {
+        let value = self.value() * other.value();
+        self.binary(other, value, Operation::MatMul(self.index, other.index))
+    }
+}
+
+/// The gradients a node passes to each of its inputs, given its own `gradient`.
+fn input_gradients(nodes: &[Node], index: usize, gradient: &Matrix) -> Vec<(usize, Matrix)> {
+    let value = &nodes[index].value;
+    let input = |i: usize| nodes[i].value.clone();
+
+    match nodes[index].operation {
+        Operation::Variable => vec![],
+        Operation::MatMul(a, b) => vec![
+            (a, gradient.clone() * input(b).transpose()),
+            (b, input(a).transpose() * gradient.clone()),
+        ],
+        Operation::Add(a, b) => vec![(a, gradient.clone()), (b, gradient.clone())],
+        Operation::Sub(a, b) => vec![(a, gradient.clone()), (b, gradient.scale(-1.0))],
+        Operation::Hadamard(a, b) => vec![
+            (a, Matrix::naive_multiply(gradient.clone(), input(b))),
+            (b, Matrix::naive_multiply(gradient.clone(), input(a))),
+        ],
+        Operation::Scale(a, factor) => vec![(a, gradient.scale(factor))],
+        Operation::AddRow(a, row) => vec![(a, gradient.clone()), (row, column_sums(gradient))],
+        Operation::Transpose(a) => vec![(a, gradient.transpose())],
+        Operation::Sigmoid(a) => vec![(
+            a,
+            zip(gradient, value, |g, y| g * Calc::sigmoid_derivative(&y)),
+        )],
+        Operation::Tanh(a) => vec![(a, zip(gradient, value, |g, y| g * (1.0 - y * y)))],
+        Operation::Relu(a) => vec![(
+            a,
+            zip(gradient, &input(a), |g, x| if x > 0.0 { g } else { 0.0 }),
+        )],
+        Operation::Exp(a) => vec![(a, zip(gradient, value, |g, y| g * y))],
+        Operation::Ln(a) => vec![(a, zip(gradient, &input(a), |g, x| g / x))],
+        Operation::Square(a) => vec![(a, zip(gradient, &input(a), |g, x| 2.0 * g * x))],
+        Operation::Sum(a) => vec![(a, map(&input(a), |_| gradient.data[0][0]))],
+        Operation::Mean(a) => {
+            let value = input(a);
+            let count = (value.data.len() * value.data[0].len()) as f64;
+            vec![(a, map(&value, |_| gradient.data[0][0] / count))]
+        }
+        Operation::ColumnSums(a) => {
+            let rows = nodes[a].value.data.len();
+            vec![(a, Matrix::new(vec![gradient.data[0].clone(); rows]))]
+        }
+    }
+}
+
+fn map(matrix: &Matrix, f: impl Fn(f64) -> f64) -> Matrix {
+    Matrix::new(
+        matrix
+            .data
+            .iter()
+            .map(|row| row.iter().map(|x| f(*x)).collect())
+            .collect(),
+    )
+}
+
+fn zip(a: &Matrix, b: &Matrix, f: impl Fn(f64, f64) -> f64) -> Matrix {
+    Matrix::new(
+        a.data
+            .iter()
+            .zip(b.data.iter())
+            .map(|(a, b)| a.iter().zip(b.iter()).map(|(a, b)| f(*a, *b)).collect())
+            .collect(),
+    )
+}
+
+fn column_sums(matrix: &Matrix) -> Matrix {
+    let mut sums = vec![0.0; matrix.data[0].len()];
+    for row in &matrix.data {
+        for (sum, x) in sums.iter_mut().zip(row.iter()) {
+            *sum += x;
+        }
+    }
+
+    Matrix::new(vec![sums])
+}
@@ -1,2 +1,4 @@
+pub mod autograd;
 pub mod calc;
 pub mod matrix;
+pub mod random;
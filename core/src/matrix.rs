@@ -89,10 +89,10 @@ impl Matrix {
 
         let mut result = vec![vec![0.0; m2_cols_len]; m1_rows_len];
 
-        for (i, m1_row) in m1.data.iter().enumerate() {
-            for j in 0..m2_cols_len {
+        for (result_row, m1_row) in result.iter_mut().zip(m1.data.iter()) {
+            for (j, cell) in result_row.iter_mut().enumerate() {
                 for (k, &m1_val) in m1_row.iter().enumerate() {
-                    result[i][j] += m1_val * m2.data[k][j];
+                    *cell += m1_val * m2.data[k][j];
                 }
             }
         }
@@ -100,6 +100,16 @@ impl Matrix {
         Self { data: result }
     }
 
+    pub fn scale(&self, factor: f64) -> Self {
+        let result = self
+            .data
+            .iter()
+            .map(|row| row.iter().map(|value| value * factor).collect())
+            .collect();
+
+        Self { data: result }
+    }
+
     pub fn derivative(&self) -> Self {
         let result = self
             .data
@@ -0,0 +1,193 @@
+use aspirina_core::autograd::{Tape, Tensor};
+use aspirina_core::matrix::Matrix;
+use aspirina_core::random::Rng;
+
+fn random_matrix(rows: usize, cols: usize, rng: &mut Rng) -> Matrix {
+    Matrix::new(
+        (0..rows)
+            .map(|_| (0..cols).map(|_| rng.range(0.1, 1.0)).collect())
+            .collect(),
+    )
+}
+
+/// Compares the tape gradients of `inputs` with central differences of `f`.
+fn check_gradients(inputs: &[Matrix], f: impl Fn(&[Tensor]) -> Tensor) {
+    let tape = Tape::new();
+    let tensors: Vec<Tensor> = inputs.iter().map(|m| tape.variable(m.clone())).collect();
+    f(&tensors).backward();
+
+    let evaluate = |values: &[Matrix]| {
+        let tape = Tape::new();
+        let tensors: Vec<Tensor> = values.iter().map(|m| tape.variable(m.clone())).collect();
+        f(&tensors).value().data[0][0]
+    };
+
+    let epsilon = 1e-6;
+    for (index, tensor) in tensors.iter().enumerate() {
+        let gradient = tensor.gradient().unwrap();
+        for i in 0..inputs[index].data.len() {
+            for j in 0..inputs[index].data[0].len() {
+                let mut plus = inputs.to_vec();
+                plus[index].data[i][j] += epsilon;
+                let mut minus = inputs.to_vec();
+                minus[index].data[i][j] -= epsilon;
+                let numerical = (evaluate(&plus) - evaluate(&minus)) / (2.0 * epsilon);
+
+                assert!(
+                    (numerical - gradient.data[i][j]).abs() < 1e-6,
+                    "input {} [{}][{}]: numerical {} analytical {}",
+                    index,
+                    i,
+                    j,
+                    numerical,
+                    gradient.data[i][j]
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn matmul_add_and_sub_gradients() {
+    let mut rng = Rng::new(1);
+    let inputs = [
+        random_matrix(3, 2, &mut rng),
+        random_matrix(2, 4, &mut rng),
+        random_matrix(3, 4, &mut rng),
+    ];
+
+    check_gradients(&inputs, |t| {
+        let product = &t[0] * &t[1];
+        (&(&product + &t[2]) - &t[2].square()).square().sum()
+    });
+}
+
+#[test]
+fn elementwise_and_activation_gradients() {
+    let mut rng = Rng::new(2);
+    let inputs = [random_matrix(2, 3, &mut rng), random_matrix(2, 3, &mut rng)];
+
+    check_gradients(&inputs, |t| {
+        let a = t[0].sigmoid().hadamard(&t[1].tanh());
+        let b = t[0].exp().hadamard(&t[1].ln()).scale(0.5);
+        (&a + &b).relu().mean()
+    });
+}
+
+#[test]
+fn broadcast_transpose_and_reduction_gradients() {
+    let mut rng = Rng::new(3);
+    let inputs = [random_matrix(4, 3, &mut rng), random_matrix(1, 3, &mut rng)];
+
+    check_gradients(&inputs, |t| {
+        let shifted = t[0].add_row(&t[1]);
+        (&shifted.transpose() * &shifted)
+            .column_sums()
+            .square()
+            .sum()
+    });
+}
+
+#[test]
+fn reused_tensor_accumulates_gradient() {
+    let tape = Tape::new();
+    let x = tape.variable(Matrix::new(vec![vec![3.0]]));
+
+    // y = x·x + x  =>  dy/dx = 2x + 1
+    let y = &(&x * &x) + &x;
+    y.backward();
+
+    assert_eq!(x.gradient().unwrap().data, vec![vec![7.0]]);
+}
+
+#[test]
+fn backward_discards_previous_gradients() {
+    let tape = Tape::new();
+    let x = tape.variable(Matrix::new(vec![vec![2.0]]));
+    let y = x.square().sum();
+
+    y.backward();
+    y.backward();
+
+    assert_eq!(x.gradient().unwrap().data, vec![vec![4.0]]);
+}
+
+#[test]
+fn unrelated_tensor_has_no_gradient() {
+    let tape = Tape::new();
+    let x = tape.variable(Matrix::new(vec![vec![1.0]]));
+    let unused = tape.variable(Matrix::new(vec![vec![1.0]]));
+
+    x.square().sum().backward();
+
+    assert!(unused.gradient().is_none());
+    assert_eq!(tape.len(), 4);
+}
+
+#[test]
+fn layers_defined_by_forward_pass_learn_xor() {
+    let inputs = Matrix::new(vec![
+        vec![0.0, 0.0],
+        vec![0.0, 1.0],
+        vec![1.0, 0.0],
+        vec![1.0, 1.0],
+    ]);
+    let targets = Matrix::new(vec![vec![0.0], vec![1.0], vec![1.0], vec![0.0]]);
+
+    let mut rng = Rng::new(42);
+    let mut parameters = vec![
+        Matrix::new(
+            (0..2)
+                .map(|_| (0..4).map(|_| rng.range(-1.0, 1.0)).collect())
+                .collect(),
+        ),
+        Matrix::new(vec![vec![0.0; 4]]),
+        Matrix::new((0..4).map(|_| vec![rng.range(-1.0, 1.0)]).collect()),
+        Matrix::new(vec![vec![0.0]]),
+    ];
+
+    let mut loss = f64::INFINITY;
+    let mut output = Matrix::new(vec![]);
+    for _ in 0..2000 {
+        let tape = Tape::new();
+        let p: Vec<Tensor> = parameters
+            .iter()
+            .map(|m| tape.variable(m.clone()))
+            .collect();
+        let x = tape.variable(inputs.clone());
+        let y = tape.variable(targets.clone());
+
+        let hidden = (&x * &p[0]).add_row(&p[1]).tanh();
+        let prediction = (&hidden * &p[2]).add_row(&p[3]).sigmoid();
+        let error = (&prediction - &y).square().mean();
+        error.backward();
+
+        loss = error.value().data[0][0];
+        output = prediction.value();
+        parameters = parameters
+            .iter()
+            .zip(p.iter())
+            .map(|(value, tensor)| value.clone() - tensor.gradient().unwrap().scale(2.0))
+            .collect();
+    }
+
+    assert!(loss < 0.01, "loss {}", loss);
+    for (row, target) in output.data.iter().zip(targets.data.iter()) {
+        assert!((row[0] - target[0]).abs() < 0.2);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Backward needs a 1×1 tensor")]
+fn backward_requires_scalar() {
+    let tape = Tape::new();
+    tape.variable(Matrix::new(vec![vec![1.0, 2.0]])).backward();
+}
+
+#[test]
+#[should_panic(expected = "Tensors belong to different tapes")]
+fn tensors_from_different_tapes_cannot_mix() {
+    let a = Tape::new().variable(Matrix::new(vec![vec![1.0]]));
+    let b = Tape::new().variable(Matrix::new(vec![vec![1.0]]));
+    let _ = &a + &b;
+}
@@ -106,3 +106,12 @@ fn derivative_applies_sigmoid_derivative() {
     assert_eq!(result.data[1][1], 0.1875);
     assert!((result.data[1][2] - 0.09).abs() < 1e-10);
 }
+
+#[test]
+fn scale_multiplies_every_element() {
+    let matrix = Matrix::new(vec![vec![1.0, -2.0], vec![0.5, 4.0]]);
+
+    let result = matrix.scale(-2.0);
+
+    assert_eq!(result.data, vec![vec![-2.0, 4.0], vec![-1.0, -8.0],]);
+}
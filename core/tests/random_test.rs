@@ -0,0 +1,78 @@
+use aspirina_core::random::Rng;
+
+#[test]
+fn same_seed_produces_same_sequence() {
+    let mut a = Rng::new(7);
+    let mut b = Rng::new(7);
+
+    for _ in 0..100 {
+        assert_eq!(a.next_u64(), b.next_u64());
+    }
+}
+
+#[test]
+fn different_seeds_produce_different_sequences() {
+    let mut a = Rng::new(1);
+    let mut b = Rng::new(2);
+
+    assert_ne!(a.next_u64(), b.next_u64());
+}
+
+#[test]
+fn next_f64_stays_in_unit_interval() {
+    let mut rng = Rng::new(123);
+
+    for _ in 0..10_000 {
+        let value = rng.next_f64();
+        assert!((0.0..1.0).contains(&value));
+    }
+}
+
+#[test]
+fn range_respects_bounds_and_is_roughly_uniform() {
+    let mut rng = Rng::new(99);
+    let samples: Vec<f64> = (0..10_000).map(|_| rng.range(-2.0, 2.0)).collect();
+
+    assert!(samples.iter().all(|value| (-2.0..2.0).contains(value)));
+
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    assert!(mean.abs() < 0.1);
+}
+
+#[test]
+fn below_stays_under_bound() {
+    let mut rng = Rng::new(5);
+
+    for _ in 0..1_000 {
+        assert!(rng.below(7) < 7);
+    }
+}
+
+#[test]
+fn shuffle_is_a_seeded_permutation() {
+    let mut a: Vec<usize> = (0..20).collect();
+    let mut b = a.clone();
+
+    Rng::new(11).shuffle(&mut a);
+    Rng::new(11).shuffle(&mut b);
+
+    assert_eq!(a, b);
+    assert_ne!(a, (0..20).collect::<Vec<_>>());
+
+    let mut sorted = a.clone();
+    sorted.sort();
+    assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+}
+
+#[test]
+fn normal_has_requested_mean_and_spread() {
+    let mut rng = Rng::new(9);
+    let samples: Vec<f64> = (0..10_000).map(|_| rng.normal(1.0, 0.5)).collect();
+
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+
+    assert!(samples.iter().all(|x| x.is_finite()));
+    assert!((mean - 1.0).abs() < 0.02);
+    assert!((variance.sqrt() - 0.5).abs() < 0.02);
+}
@@ -0,0 +1,268 @@
+//! Training Callbacks
+//!
+//! Callbacks let code observe and steer a [`Trainer`](crate::trainer::Trainer) run without
+//! rewriting the training loop. The trainer calls every callback at the start and end of each
+//! epoch and after each batch; a callback can stop training by returning
+//! [`CallbackAction::Stop`] from [`Callback::on_epoch_end`].
+//!
+//! # Built-in Callbacks
+//!
+//! - [`ProgressLogger`]: prints the epoch number and loss to the console
+//! - [`CsvLogger`]: appends one row of metrics per epoch to a CSV file
+//! - [`BestModelCheckpoint`]: remembers the parameters of the best epoch so far
+//! - [`StopOnNaN`]: stops training as soon as the loss stops being a finite number
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::callbacks::{BestModelCheckpoint, StopOnNaN};
+//! # use aspirina_gates::trainer::Trainer;
+//! # use aspirina_gates::neural_network::NeuralNetwork;
+//! # use aspirina_gates::layer::Layer;
+//! # use aspirina_core::matrix::Matrix;
+//! let network = NeuralNetwork::new(vec![Layer::new(Matrix::new(vec![vec![0.1, 0.2]]))]);
+//! let input = Matrix::new(vec![vec![0.0, 1.0], vec![1.0, 1.0]]);
+//! let targets = Matrix::new(vec![vec![1.0, 1.0]]);
+//!
+//! let mut checkpoint = BestModelCheckpoint::new();
+//! let mut stop_on_nan = StopOnNaN::new();
+//! let history = Trainer::new(50).fit_with_callbacks(
+//!     &network,
+//!     input,
+//!     targets,
+//!     &mut [&mut checkpoint, &mut stop_on_nan],
+//! );
+//!
+//! assert_eq!(history.epochs.len(), 50);
+//! assert_eq!(checkpoint.best_epoch(), Some(49));
+//! ```
+
+use crate::neural_network::{NeuralNetwork, TrainingStep};
+use crate::trainer::EpochMetrics;
+use aspirina_core::matrix::Matrix;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// What the trainer should do after a callback has seen an epoch's metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallbackAction {
+    Continue,
+    Stop,
+}
+
+/// Hooks invoked by the trainer during a training run.
+///
+/// Every method has a no-op default, so a callback only implements the events it needs.
+pub trait Callback {
+    /// Called before the first batch of `epoch` (zero-based).
+    fn on_epoch_start(&mut self, _epoch: usize, _network: &NeuralNetwork) {}
+
+    /// Called after each training step within an epoch.
+    fn on_batch_end(&mut self, _epoch: usize, _batch: usize, _step: &TrainingStep) {}
+
+    /// Called once the epoch's metrics are known.
+    fn on_epoch_end(
+        &mut self,
+        _metrics: &EpochMetrics,
+        _network: &NeuralNetwork,
+    ) -> CallbackAction {
+        CallbackAction::Continue
+    }
+}
+
+/// Prints progress to the console every `every` epochs and on the final epoch.
+///
+/// Output looks like `Epoch: 2001/10000 - loss: 0.012345`.
+#[derive(Debug, Clone)]
+pub struct ProgressLogger {
+    every: usize,
+}
+
+impl ProgressLogger {
+    /// Creates a logger that prints every `every` epochs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `every` is zero.
+    pub fn new(every: usize) -> Self {
+        if every == 0 {
+            panic!("Logging interval must be greater than zero");
+        }
+
+        Self { every }
+    }
+}
+
+impl Callback for ProgressLogger {
+    fn on_epoch_end(&mut self, metrics: &EpochMetrics, _network: &NeuralNetwork) -> CallbackAction {
+        if metrics.epoch.is_multiple_of(self.every) || metrics.epoch + 1 == metrics.epochs {
+            println!(
+                "Epoch: {}/{} - loss: {:.6}",
+                metrics.epoch + 1,
+                metrics.epochs,
+                metrics.loss
+            );
+        }
+
+        CallbackAction::Continue
+    }
+}
+
+/// Writes one CSV row of metrics per epoch.
+///
+/// The file starts with the header
+/// `epoch,loss,accuracy,gradient_norm,learning_rate,validation_loss`; the validation loss
+/// column is empty when no validation data is configured. If a write fails, training is
+/// stopped and the error is kept in [`error`](Self::error).
+#[derive(Debug)]
+pub struct CsvLogger {
+    file: File,
+    error: Option<io::Error>,
+}
+
+impl CsvLogger {
+    /// Creates (or truncates) the CSV file at `path` and writes the header.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut file = File::create(path)?;
+        writeln!(
+            file,
+            "epoch,loss,accuracy,gradient_norm,learning_rate,validation_loss"
+        )?;
+
+        Ok(Self { file, error: None })
+    }
+
+    /// The write error that stopped training, if any.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+}
+
+impl Callback for CsvLogger {
+    fn on_epoch_end(&mut self, metrics: &EpochMetrics, _network: &NeuralNetwork) -> CallbackAction {
+        let validation_loss = metrics
+            .validation_loss
+            .map(|loss| loss.to_string())
+            .unwrap_or_default();
+
+        let result = writeln!(
+            self.file,
+            "{},{},{},{},{},{}",
+            metrics.epoch,
+            metrics.loss,
+            metrics.accuracy,
+            metrics.gradient_norm,
+            metrics.learning_rate,
+            validation_loss
+        );
+
+        match result {
+            Ok(()) => CallbackAction::Continue,
+            Err(error) => {
+                self.error = Some(error);
+                CallbackAction::Stop
+            }
+        }
+    }
+}
+
+/// Keeps a copy of the parameters from the epoch with the lowest monitored loss.
+///
+/// The validation loss is monitored when available, otherwise the training loss.
+/// Call [`restore`](Self::restore) after training to roll the network back to that epoch.
+#[derive(Debug, Clone, Default)]
+pub struct BestModelCheckpoint {
+    best_loss: Option<f64>,
+    best_epoch: Option<usize>,
+    parameters: Option<Vec<Vec<Matrix>>>,
+}
+
+impl BestModelCheckpoint {
+    /// Creates a checkpoint that has not seen any epoch yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The lowest monitored loss seen so far.
+    pub fn best_loss(&self) -> Option<f64> {
+        self.best_loss
+    }
+
+    /// The epoch (zero-based) that produced the best loss.
+    pub fn best_epoch(&self) -> Option<usize> {
+        self.best_epoch
+    }
+
+    /// The parameters saved at the best epoch, in the layout of [`NeuralNetwork::parameters`].
+    pub fn parameters(&self) -> Option<&Vec<Vec<Matrix>>> {
+        self.parameters.as_ref()
+    }
+
+    /// Loads the best parameters back into `network`. Returns `false` if nothing was saved.
+    pub fn restore(&self, network: &NeuralNetwork) -> bool {
+        match &self.parameters {
+            Some(parameters) => {
+                network.set_parameters(parameters.clone());
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Callback for BestModelCheckpoint {
+    fn on_epoch_end(&mut self, metrics: &EpochMetrics, network: &NeuralNetwork) -> CallbackAction {
+        let loss = metrics.validation_loss.unwrap_or(metrics.loss);
+        let improved = match self.best_loss {
+            Some(best) => loss < best,
+            None => loss.is_finite(),
+        };
+
+        if improved {
+            self.best_loss = Some(loss);
+            self.best_epoch = Some(metrics.epoch);
+            self.parameters = Some(network.parameters());
+        }
+
+        CallbackAction::Continue
+    }
+}
+
+/// Stops training as soon as the loss becomes NaN or infinite.
+#[derive(Debug, Clone, Default)]
+pub struct StopOnNaN {
+    stopped_at: Option<usize>,
+}
+
+impl StopOnNaN {
+    /// Creates the callback.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The epoch (zero-based) at which a non-finite loss was detected.
+    pub fn stopped_at(&self) -> Option<usize> {
+        self.stopped_at
+    }
+}
+
+impl Callback for StopOnNaN {
+    fn on_batch_end(&mut self, epoch: usize, _batch: usize, step: &TrainingStep) {
+        if !step.loss.is_finite() && self.stopped_at.is_none() {
+            self.stopped_at = Some(epoch);
+        }
+    }
+
+    fn on_epoch_end(&mut self, metrics: &EpochMetrics, _network: &NeuralNetwork) -> CallbackAction {
+        if !metrics.loss.is_finite() && self.stopped_at.is_none() {
+            self.stopped_at = Some(metrics.epoch);
+        }
+
+        if self.stopped_at.is_some() {
+            CallbackAction::Stop
+        } else {
+            CallbackAction::Continue
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use super::compiled::{Gate, Mode};
 use super::full_adder::FullAdder;
-use super::gates::{GateType, LogicGate};
+use super::gates::GateType;
+use super::registry::GateRegistry;
 
 /// 4-bit ALU operations
 #[derive(Debug, Clone, PartialEq)]
@@ -28,47 +30,60 @@ pub struct ALU {
     adder2: FullAdder,
     adder3: FullAdder, // MSB
     // Logic gates for bitwise operations
-    and_gates: [LogicGate; 4],
-    or_gates: [LogicGate; 4],
-    xor_gates: [LogicGate; 4],
+    and_gates: [Gate; 4],
+    or_gates: [Gate; 4],
+    xor_gates: [Gate; 4],
+    // 4-input NOR over the result bits, when built from wide gates
+    zero_gate: Option<Gate>,
 }
 
 impl ALU {
     /// Create a new 4-bit ALU
     pub fn new() -> Self {
-        let adder0 = FullAdder::new();
-        let adder1 = FullAdder::new();
-        let adder2 = FullAdder::new();
-        let adder3 = FullAdder::new();
-
-        // Create logic gates for each bit position
-        let and_gates = [
-            LogicGate::new(GateType::AND),
-            LogicGate::new(GateType::AND),
-            LogicGate::new(GateType::AND),
-            LogicGate::new(GateType::AND),
-        ];
+        Self::with_registry(&GateRegistry::new())
+    }
 
-        let or_gates = [
-            LogicGate::new(GateType::OR),
-            LogicGate::new(GateType::OR),
-            LogicGate::new(GateType::OR),
-            LogicGate::new(GateType::OR),
-        ];
+    /// Create a 4-bit ALU sharing the registry's trained gates
+    pub fn with_registry(registry: &GateRegistry) -> Self {
+        Self::build(
+            [
+                FullAdder::with_registry(registry),
+                FullAdder::with_registry(registry),
+                FullAdder::with_registry(registry),
+                FullAdder::with_registry(registry),
+            ],
+            None,
+            registry,
+        )
+    }
 
-        let xor_gates = [
-            LogicGate::new(GateType::XOR),
-            LogicGate::new(GateType::XOR),
-            LogicGate::new(GateType::XOR),
-            LogicGate::new(GateType::XOR),
-        ];
+    /// Create a 4-bit ALU from native wide gates: parity/majority full adders and a 4-input
+    /// NOR gate computing the zero flag
+    pub fn wide() -> Self {
+        Self::wide_with_registry(&GateRegistry::new())
+    }
 
-        // Train all logic gates
-        for i in 0..4 {
-            and_gates[i].train(10_000);
-            or_gates[i].train(10_000);
-            xor_gates[i].train(10_000);
-        }
+    /// Create a wide 4-bit ALU, sharing the registry's trained gates
+    pub fn wide_with_registry(registry: &GateRegistry) -> Self {
+        Self::build(
+            [
+                FullAdder::wide_with_registry(registry),
+                FullAdder::wide_with_registry(registry),
+                FullAdder::wide_with_registry(registry),
+                FullAdder::wide_with_registry(registry),
+            ],
+            Some(registry.get(GateType::NOR, 4).into()),
+            registry,
+        )
+    }
+
+    fn build(adders: [FullAdder; 4], zero_gate: Option<Gate>, registry: &GateRegistry) -> Self {
+        let [adder0, adder1, adder2, adder3] = adders;
+
+        // Each bit position shares the same trained logic gates
+        let and_gates = std::array::from_fn(|_| registry.get(GateType::AND, 2).into());
+        let or_gates = std::array::from_fn(|_| registry.get(GateType::OR, 2).into());
+        let xor_gates = std::array::from_fn(|_| registry.get(GateType::XOR, 2).into());
 
         ALU {
             adder0,
@@ -78,9 +93,40 @@ impl ALU {
             and_gates,
             or_gates,
             xor_gates,
+            zero_gate,
         }
     }
 
+    /// A copy of this ALU running on lookup tables compiled from its gates, giving the same
+    /// results without any network forward pass
+    pub fn compiled(&self) -> Result<Self, String> {
+        let compile = |gates: &[Gate; 4]| -> Result<[Gate; 4], String> {
+            let [g0, g1, g2, g3] = gates;
+            Ok([
+                g0.compiled()?,
+                g1.compiled()?,
+                g2.compiled()?,
+                g3.compiled()?,
+            ])
+        };
+
+        Ok(ALU {
+            adder0: self.adder0.compiled()?,
+            adder1: self.adder1.compiled()?,
+            adder2: self.adder2.compiled()?,
+            adder3: self.adder3.compiled()?,
+            and_gates: compile(&self.and_gates)?,
+            or_gates: compile(&self.or_gates)?,
+            xor_gates: compile(&self.xor_gates)?,
+            zero_gate: self.zero_gate.as_ref().map(Gate::compiled).transpose()?,
+        })
+    }
+
+    /// Whether the gates run as networks or as compiled tables
+    pub fn mode(&self) -> Mode {
+        self.and_gates[0].mode()
+    }
+
     /// Perform ALU operation on two 4-bit numbers
     pub fn compute(&self, a: u8, b: u8, operation: ALUOperation) -> ALUResult {
         // Ensure inputs are 4-bit
@@ -115,7 +161,7 @@ impl ALU {
         ALUResult {
             result: result_value,
             carry: result3.carry,
-            zero: result_value == 0,
+            zero: self.is_zero(result_bits),
         }
     }
 
@@ -136,17 +182,14 @@ impl ALU {
 
         let mut result_bits = [false; 4];
         for i in 0..4 {
-            let a_f = if a_bits[i] { 1.0 } else { 0.0 };
-            let b_f = if b_bits[i] { 1.0 } else { 0.0 };
-            let output = self.and_gates[i].compute(vec![a_f, b_f]);
-            result_bits[i] = output > 0.5;
+            result_bits[i] = self.and_gates[i].compute(&[a_bits[i], b_bits[i]]);
         }
 
         let result_value = self.bits_to_u8(result_bits);
         ALUResult {
             result: result_value,
             carry: false,
-            zero: result_value == 0,
+            zero: self.is_zero(result_bits),
         }
     }
 
@@ -157,17 +200,14 @@ impl ALU {
 
         let mut result_bits = [false; 4];
         for i in 0..4 {
-            let a_f = if a_bits[i] { 1.0 } else { 0.0 };
-            let b_f = if b_bits[i] { 1.0 } else { 0.0 };
-            let output = self.or_gates[i].compute(vec![a_f, b_f]);
-            result_bits[i] = output > 0.5;
+            result_bits[i] = self.or_gates[i].compute(&[a_bits[i], b_bits[i]]);
         }
 
         let result_value = self.bits_to_u8(result_bits);
         ALUResult {
             result: result_value,
             carry: false,
-            zero: result_value == 0,
+            zero: self.is_zero(result_bits),
         }
     }
 
@@ -178,17 +218,22 @@ impl ALU {
 
         let mut result_bits = [false; 4];
         for i in 0..4 {
-            let a_f = if a_bits[i] { 1.0 } else { 0.0 };
-            let b_f = if b_bits[i] { 1.0 } else { 0.0 };
-            let output = self.xor_gates[i].compute(vec![a_f, b_f]);
-            result_bits[i] = output > 0.5;
+            result_bits[i] = self.xor_gates[i].compute(&[a_bits[i], b_bits[i]]);
         }
 
         let result_value = self.bits_to_u8(result_bits);
         ALUResult {
             result: result_value,
             carry: false,
-            zero: result_value == 0,
+            zero: self.is_zero(result_bits),
+        }
+    }
+
+    /// Zero flag, from the NOR gate when there is one
+    fn is_zero(&self, bits: [bool; 4]) -> bool {
+        match &self.zero_gate {
+            Some(gate) => gate.compute(&bits),
+            None => bits.iter().all(|bit| !bit),
         }
     }
 
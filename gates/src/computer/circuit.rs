@@ -0,0 +1,622 @@
+//! Gate-Level Netlists
+//!
+//! `HalfAdder`, `FullAdder` and `ALU` are wired by hand in Rust. A [`Circuit`] describes the
+//! same kind of component as data instead: named input and output wires, and parts that are
+//! either gates of any [`GateType`] or other circuits, each connected to wires by name. The
+//! wires `true` and `false` always carry those constants.
+//!
+//! [`Circuit::build`] checks the netlist, orders the parts topologically (rejecting
+//! combinational cycles) and instantiates every gate from a [`GateRegistry`], giving a
+//! [`Component`] that evaluates the whole circuit in one pass.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::computer::circuit::Circuit;
+//! # use aspirina_gates::computer::gates::GateType;
+//! # use aspirina_gates::computer::registry::GateRegistry;
+//! let half_adder = Circuit::new("HalfAdder", &["a", "b"], &["sum", "carry"])
+//!     .with_gate(GateType::XOR, &["a", "b"], "sum")
+//!     .with_gate(GateType::AND, &["a", "b"], "carry");
+//!
+//! let full_adder = Circuit::new("FullAdder", &["a", "b", "c"], &["sum", "carry"])
+//!     .with_circuit(&half_adder, &["a", "b"], &["s1", "c1"])
+//!     .with_circuit(&half_adder, &["s1", "c"], &["sum", "c2"])
+//!     .with_gate(GateType::OR, &["c1", "c2"], "carry");
+//!
+//! let component = full_adder.build(&GateRegistry::new()).unwrap();
+//! assert_eq!(component.compute(&[true, true, true]), vec![true, true]);
+//! ```
+
+use super::compiled::{Gate, Mode};
+use super::gates::{GateType, MAX_INPUTS};
+use super::registry::GateRegistry;
+use super::truth_table::TruthTable;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// What a part of a circuit instantiates
+#[derive(Debug, Clone)]
+pub enum PartKind {
+    Gate(GateType),
+    Circuit(Rc<Circuit>),
+}
+
+/// A gate or sub-circuit connected to wires by name
+#[derive(Debug, Clone)]
+pub struct Part {
+    pub kind: PartKind,
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+}
+
+/// A netlist: input and output wires plus the parts connecting them
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    name: String,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    parts: Vec<Part>,
+}
+
+/// A built circuit, with trained gates and parts in evaluation order
+#[derive(Debug)]
+pub struct Component {
+    name: String,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    wires: usize,
+    input_wires: Vec<usize>,
+    output_wires: Vec<usize>,
+    instances: Vec<Instance>,
+}
+
+#[derive(Debug)]
+struct Instance {
+    cell: Cell,
+    inputs: Vec<usize>,
+    outputs: Vec<usize>,
+}
+
+#[derive(Debug)]
+enum Cell {
+    Gate(Gate),
+    Component(Box<Component>),
+}
+
+// Wire indices of the constants, before any named wire
+const FALSE: usize = 0;
+const TRUE: usize = 1;
+
+impl Circuit {
+    /// Creates a circuit with no parts yet
+    pub fn new(name: &str, inputs: &[&str], outputs: &[&str]) -> Self {
+        Circuit {
+            name: name.to_string(),
+            inputs: inputs.iter().map(|wire| wire.to_string()).collect(),
+            outputs: outputs.iter().map(|wire| wire.to_string()).collect(),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Adds a gate driving `output` from the `inputs` wires
+    pub fn with_gate(mut self, gate_type: GateType, inputs: &[&str], output: &str) -> Self {
+        self.parts.push(Part {
+            kind: PartKind::Gate(gate_type),
+            inputs: inputs.iter().map(|wire| wire.to_string()).collect(),
+            outputs: vec![output.to_string()],
+        });
+        self
+    }
+
+    /// Adds a copy of another circuit, connecting its inputs and outputs in order
+    pub fn with_circuit(self, circuit: &Circuit, inputs: &[&str], outputs: &[&str]) -> Self {
+        self.with_shared_circuit(Rc::new(circuit.clone()), inputs, outputs)
+    }
+
+    /// Adds a circuit shared with other parts, connecting its inputs and outputs in order
+    pub fn with_shared_circuit(
+        mut self,
+        circuit: Rc<Circuit>,
+        inputs: &[&str],
+        outputs: &[&str],
+    ) -> Self {
+        self.parts.push(Part {
+            kind: PartKind::Circuit(circuit),
+            inputs: inputs.iter().map(|wire| wire.to_string()).collect(),
+            outputs: outputs.iter().map(|wire| wire.to_string()).collect(),
+        });
+        self
+    }
+
+    /// The circuit name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Input wire names, in the order [`Component::compute`] takes them
+    pub fn inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    /// Output wire names, in the order [`Component::compute`] returns them
+    pub fn outputs(&self) -> &[String] {
+        &self.outputs
+    }
+
+    /// The parts, in the order they were added
+    pub fn parts(&self) -> &[Part] {
+        &self.parts
+    }
+
+    /// Number of gates, counting those inside sub-circuits
+    pub fn gate_count(&self) -> usize {
+        self.parts
+            .iter()
+            .map(|part| match &part.kind {
+                PartKind::Gate(_) => 1,
+                PartKind::Circuit(circuit) => circuit.gate_count(),
+            })
+            .sum()
+    }
+
+    /// Checks the netlist and returns the part indices in evaluation order
+    ///
+    /// Every wire must be driven exactly once, by a circuit input, a constant or a part
+    /// output, and every part must take as many wires as its gate or circuit expects. Parts
+    /// feeding each other in a loop are reported as a cycle.
+    pub fn order(&self) -> Result<Vec<usize>, String> {
+        let mut drivers: HashMap<&str, Option<usize>> = HashMap::new();
+        drivers.insert("false", None);
+        drivers.insert("true", None);
+        for wire in &self.inputs {
+            if drivers.insert(wire, None).is_some() {
+                return Err(format!(
+                    "{}: wire {} is driven more than once",
+                    self.name, wire
+                ));
+            }
+        }
+
+        for (index, part) in self.parts.iter().enumerate() {
+            let (inputs, outputs) = match &part.kind {
+                PartKind::Gate(gate_type) => {
+                    if !gate_type.supports(part.inputs.len()) {
+                        return Err(format!(
+                            "{}: {} gate cannot take {} input(s)",
+                            self.name,
+                            gate_type,
+                            part.inputs.len()
+                        ));
+                    }
+                    (part.inputs.len(), 1)
+                }
+                PartKind::Circuit(circuit) => (circuit.inputs.len(), circuit.outputs.len()),
+            };
+            if part.inputs.len() != inputs || part.outputs.len() != outputs {
+                return Err(format!(
+                    "{}: {} expects {} input(s) and {} output(s), got {} and {}",
+                    self.name,
+                    part.label(),
+                    inputs,
+                    outputs,
+                    part.inputs.len(),
+                    part.outputs.len()
+                ));
+            }
+
+            for wire in &part.outputs {
+                if drivers.insert(wire, Some(index)).is_some() {
+                    return Err(format!(
+                        "{}: wire {} is driven more than once",
+                        self.name, wire
+                    ));
+                }
+            }
+        }
+
+        let used = self
+            .parts
+            .iter()
+            .flat_map(|part| &part.inputs)
+            .chain(&self.outputs);
+        for wire in used {
+            if !drivers.contains_key(wire.as_str()) {
+                return Err(format!("{}: wire {} is never driven", self.name, wire));
+            }
+        }
+
+        // Kahn's algorithm: a part is ready once every part driving its inputs is placed
+        let dependencies: Vec<Vec<usize>> = self
+            .parts
+            .iter()
+            .map(|part| {
+                part.inputs
+                    .iter()
+                    .filter_map(|wire| drivers[wire.as_str()])
+                    .collect()
+            })
+            .collect();
+        let mut placed = vec![false; self.parts.len()];
+        let mut order = Vec::with_capacity(self.parts.len());
+
+        while order.len() < self.parts.len() {
+            let ready: Vec<usize> = (0..self.parts.len())
+                .filter(|index| !placed[*index])
+                .filter(|index| dependencies[*index].iter().all(|d| placed[*d]))
+                .collect();
+            if ready.is_empty() {
+                let mut wires: Vec<&str> = (0..self.parts.len())
+                    .filter(|index| !placed[*index])
+                    .flat_map(|index| self.parts[index].outputs.iter().map(String::as_str))
+                    .collect();
+                wires.sort_unstable();
+                return Err(format!(
+                    "{}: combinational cycle through wires {}",
+                    self.name,
+                    wires.join(", ")
+                ));
+            }
+
+            for index in ready {
+                placed[index] = true;
+                order.push(index);
+            }
+        }
+
+        Ok(order)
+    }
+
+    /// Checks the netlist and instantiates every gate from the registry
+    pub fn build(&self, registry: &GateRegistry) -> Result<Component, String> {
+        let order = self.order()?;
+
+        let mut wires: HashMap<&str, usize> = HashMap::new();
+        wires.insert("false", FALSE);
+        wires.insert("true", TRUE);
+        let input_wires: Vec<usize> = self
+            .inputs
+            .iter()
+            .map(|wire| wire_index(&mut wires, wire))
+            .collect();
+
+        let mut instances = Vec::with_capacity(order.len());
+        for part_index in order {
+            let part = &self.parts[part_index];
+            let cell = match &part.kind {
+                PartKind::Gate(gate_type) => {
+                    Cell::Gate(registry.get(gate_type.clone(), part.inputs.len()).into())
+                }
+                PartKind::Circuit(circuit) => Cell::Component(Box::new(circuit.build(registry)?)),
+            };
+            instances.push(Instance {
+                cell,
+                inputs: part
+                    .inputs
+                    .iter()
+                    .map(|wire| wire_index(&mut wires, wire))
+                    .collect(),
+                outputs: part
+                    .outputs
+                    .iter()
+                    .map(|wire| wire_index(&mut wires, wire))
+                    .collect(),
+            });
+        }
+
+        let output_wires = self
+            .outputs
+            .iter()
+            .map(|wire| wire_index(&mut wires, wire))
+            .collect();
+
+        Ok(Component {
+            name: self.name.clone(),
+            inputs: self.inputs.clone(),
+            outputs: self.outputs.clone(),
+            wires: wires.len(),
+            input_wires,
+            output_wires,
+            instances,
+        })
+    }
+
+    /// Sum = A XOR B, Carry = A AND B
+    pub fn half_adder() -> Self {
+        Circuit::new("HalfAdder", &["a", "b"], &["sum", "carry"])
+            .with_gate(GateType::XOR, &["a", "b"], "sum")
+            .with_gate(GateType::AND, &["a", "b"], "carry")
+    }
+
+    /// Two half adders and an OR gate, as in [`FullAdder::new`](super::full_adder::FullAdder::new)
+    pub fn full_adder() -> Self {
+        let half_adder = Rc::new(Circuit::half_adder());
+
+        Circuit::new("FullAdder", &["a", "b", "cin"], &["sum", "carry"])
+            .with_shared_circuit(Rc::clone(&half_adder), &["a", "b"], &["s1", "c1"])
+            .with_shared_circuit(half_adder, &["s1", "cin"], &["sum", "c2"])
+            .with_gate(GateType::OR, &["c1", "c2"], "carry")
+    }
+
+    /// A 3-input parity and majority gate, as in [`FullAdder::wide`](super::full_adder::FullAdder::wide)
+    pub fn wide_full_adder() -> Self {
+        Circuit::new("WideFullAdder", &["a", "b", "cin"], &["sum", "carry"])
+            .with_gate(GateType::PARITY, &["a", "b", "cin"], "sum")
+            .with_gate(GateType::MAJORITY, &["a", "b", "cin"], "carry")
+    }
+
+    /// Chains full adders into an adder of `bits` bits
+    ///
+    /// Inputs are `a0..`, `b0..` and `cin`, outputs `s0..` and `carry`, least significant
+    /// bit first.
+    pub fn ripple_adder(full_adder: &Circuit, bits: usize) -> Self {
+        let full_adder = Rc::new(full_adder.clone());
+        let inputs: Vec<String> = (0..bits)
+            .map(|bit| format!("a{}", bit))
+            .chain((0..bits).map(|bit| format!("b{}", bit)))
+            .chain(["cin".to_string()])
+            .collect();
+        let outputs: Vec<String> = (0..bits)
+            .map(|bit| format!("s{}", bit))
+            .chain(["carry".to_string()])
+            .collect();
+
+        let mut circuit =
+            Circuit::new(&format!("Adder{}", bits), &names(&inputs), &names(&outputs));
+        for bit in 0..bits {
+            let carry_in = if bit == 0 {
+                "cin".to_string()
+            } else {
+                format!("c{}", bit)
+            };
+            let carry_out = if bit + 1 == bits {
+                "carry".to_string()
+            } else {
+                format!("c{}", bit + 1)
+            };
+            circuit = circuit.with_shared_circuit(
+                Rc::clone(&full_adder),
+                &[&format!("a{}", bit), &format!("b{}", bit), &carry_in],
+                &[&format!("s{}", bit), &carry_out],
+            );
+        }
+        circuit
+    }
+
+    /// The 4-bit ALU as a netlist, giving the same results as [`ALU`](super::alu::ALU)
+    ///
+    /// Inputs are `a0..a3` and `b0..b3`, least significant bit first, followed by one
+    /// select line per operation: `add`, `sub`, `and`, `or` and `xor`. Exactly one select
+    /// line should be high. Outputs are `r0..r3`, `carry` and `zero`.
+    ///
+    /// Subtraction follows the ALU step by step: B is inverted and incremented by one adder,
+    /// then added to A by another.
+    pub fn alu(full_adder: &Circuit) -> Self {
+        const OPERATIONS: [&str; 5] = ["add", "sub", "and", "or", "xor"];
+        let adder = Rc::new(Circuit::ripple_adder(full_adder, 4));
+        let bit_names = |prefix: &str| -> Vec<String> {
+            (0..4).map(|bit| format!("{}{}", prefix, bit)).collect()
+        };
+        let wires = |prefix: &str, extra: &[&str]| -> Vec<String> {
+            bit_names(prefix)
+                .into_iter()
+                .chain(extra.iter().map(|wire| wire.to_string()))
+                .collect()
+        };
+
+        let inputs: Vec<String> = wires("a", &[])
+            .into_iter()
+            .chain(wires("b", &OPERATIONS))
+            .collect();
+        let outputs = wires("r", &["carry", "zero"]);
+        let mut circuit = Circuit::new("ALU", &names(&inputs), &names(&outputs));
+
+        // A + B
+        let mut adder_inputs: Vec<String> = wires("a", &[]);
+        adder_inputs.extend(wires("b", &["false"]));
+        circuit = circuit.with_shared_circuit(
+            Rc::clone(&adder),
+            &names(&adder_inputs),
+            &names(&wires("add_r", &["add_carry"])),
+        );
+
+        // ~B + 1, then A + (~B + 1)
+        for bit in 0..4 {
+            circuit = circuit.with_gate(
+                GateType::NOT,
+                &[&format!("b{}", bit)],
+                &format!("not_b{}", bit),
+            );
+        }
+        let negate_inputs = wires("not_b", &["true", "false", "false", "false", "false"]);
+        circuit = circuit.with_shared_circuit(
+            Rc::clone(&adder),
+            &names(&negate_inputs),
+            &names(&wires("neg_b", &["neg_carry"])),
+        );
+        let mut subtract_inputs = wires("a", &[]);
+        subtract_inputs.extend(wires("neg_b", &["false"]));
+        circuit = circuit.with_shared_circuit(
+            adder,
+            &names(&subtract_inputs),
+            &names(&wires("sub_r", &["sub_carry"])),
+        );
+
+        // Bitwise operations
+        for (operation, gate_type) in [
+            ("and", GateType::AND),
+            ("or", GateType::OR),
+            ("xor", GateType::XOR),
+        ] {
+            for bit in 0..4 {
+                circuit = circuit.with_gate(
+                    gate_type.clone(),
+                    &[&format!("a{}", bit), &format!("b{}", bit)],
+                    &format!("{}_r{}", operation, bit),
+                );
+            }
+        }
+
+        // Each result bit is the OR of every operation's bit, gated by its select line
+        for bit in 0..4 {
+            let mut selected = Vec::new();
+            for operation in OPERATIONS {
+                let wire = format!("{}_sel{}", operation, bit);
+                circuit = circuit.with_gate(
+                    GateType::AND,
+                    &[operation, &format!("{}_r{}", operation, bit)],
+                    &wire,
+                );
+                selected.push(wire);
+            }
+            circuit = circuit.with_gate(GateType::OR, &names(&selected), &format!("r{}", bit));
+        }
+        circuit = circuit
+            .with_gate(GateType::AND, &["add", "add_carry"], "add_sel_carry")
+            .with_gate(GateType::AND, &["sub", "sub_carry"], "sub_sel_carry")
+            .with_gate(GateType::OR, &["add_sel_carry", "sub_sel_carry"], "carry")
+            .with_gate(GateType::NOR, &["r0", "r1", "r2", "r3"], "zero");
+
+        circuit
+    }
+}
+
+impl Component {
+    /// The circuit name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Input wire names, in the order [`Component::compute`] takes them
+    pub fn inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    /// Output wire names, in the order [`Component::compute`] returns them
+    pub fn outputs(&self) -> &[String] {
+        &self.outputs
+    }
+
+    /// Evaluates every part once, in topological order
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs does not match the circuit.
+    pub fn compute(&self, inputs: &[bool]) -> Vec<bool> {
+        if inputs.len() != self.inputs.len() {
+            panic!(
+                "{} expects {} input(s), got {}",
+                self.name,
+                self.inputs.len(),
+                inputs.len()
+            );
+        }
+
+        let mut values = vec![false; self.wires];
+        values[TRUE] = true;
+        for (wire, value) in self.input_wires.iter().zip(inputs) {
+            values[*wire] = *value;
+        }
+
+        for instance in &self.instances {
+            let inputs: Vec<bool> = instance.inputs.iter().map(|wire| values[*wire]).collect();
+            match &instance.cell {
+                Cell::Gate(gate) => values[instance.outputs[0]] = gate.compute(&inputs),
+                Cell::Component(component) => {
+                    for (wire, value) in instance.outputs.iter().zip(component.compute(&inputs)) {
+                        values[*wire] = value;
+                    }
+                }
+            }
+        }
+
+        self.output_wires.iter().map(|wire| values[*wire]).collect()
+    }
+
+    /// One truth table per output, over every combination of inputs
+    ///
+    /// Fails for components without inputs or with more than [`MAX_INPUTS`], such as the
+    /// 13-input ALU, whose tables would not fit a [`TruthTable`].
+    pub fn truth_tables(&self) -> Result<Vec<TruthTable>, String> {
+        let inputs = self.inputs.len();
+        if !(1..=MAX_INPUTS).contains(&inputs) {
+            return Err(format!(
+                "{}: truth tables need between 1 and {} inputs, found {}",
+                self.name, MAX_INPUTS, inputs
+            ));
+        }
+
+        let rows: Vec<Vec<bool>> = (0..1usize << inputs)
+            .map(|row| {
+                let bits: Vec<bool> = (0..inputs)
+                    .map(|bit| (row >> (inputs - 1 - bit)) & 1 == 1)
+                    .collect();
+                self.compute(&bits)
+            })
+            .collect();
+
+        Ok((0..self.outputs.len())
+            .map(|output| TruthTable::new(inputs, rows.iter().map(|row| row[output]).collect()))
+            .collect())
+    }
+
+    /// A copy of this component running on lookup tables compiled from its gates
+    pub fn compiled(&self) -> Result<Self, String> {
+        let instances = self
+            .instances
+            .iter()
+            .map(|instance| {
+                let cell = match &instance.cell {
+                    Cell::Gate(gate) => Cell::Gate(gate.compiled()?),
+                    Cell::Component(component) => Cell::Component(Box::new(component.compiled()?)),
+                };
+                Ok(Instance {
+                    cell,
+                    inputs: instance.inputs.clone(),
+                    outputs: instance.outputs.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Component {
+            name: self.name.clone(),
+            inputs: self.inputs.clone(),
+            outputs: self.outputs.clone(),
+            wires: self.wires,
+            input_wires: self.input_wires.clone(),
+            output_wires: self.output_wires.clone(),
+            instances,
+        })
+    }
+
+    /// Whether the gates run as networks or as compiled tables
+    pub fn mode(&self) -> Mode {
+        self.instances
+            .iter()
+            .map(|instance| match &instance.cell {
+                Cell::Gate(gate) => gate.mode(),
+                Cell::Component(component) => component.mode(),
+            })
+            .next()
+            .unwrap_or(Mode::Compiled)
+    }
+}
+
+impl Part {
+    fn label(&self) -> String {
+        match &self.kind {
+            PartKind::Gate(gate_type) => format!("{} gate", gate_type),
+            PartKind::Circuit(circuit) => circuit.name.clone(),
+        }
+    }
+}
+
+/// The wire's index, numbering wires in order of first use
+fn wire_index<'a>(wires: &mut HashMap<&'a str, usize>, wire: &'a str) -> usize {
+    let next = wires.len();
+    *wires.entry(wire).or_insert(next)
+}
+
+fn names(wires: &[String]) -> Vec<&str> {
+    wires.iter().map(String::as_str).collect()
+}
@@ -0,0 +1,162 @@
+//! Compiled Gates
+//!
+//! A trained [`LogicGate`] runs matrix multiplies and sigmoids on every call, even though
+//! once verified it only ever answers with its truth table. Compiling collapses the network
+//! into an exact form that gives the same answers without floating point:
+//!
+//! - **Lookup table:** [`LogicGate::compile`] reads the network's answer for every row into a
+//!   [`TruthTable`], failing if any row is on the wrong side of 0.5
+//! - **Threshold circuit:** [`LogicGate::to_threshold_circuit`] replaces every sigmoid by
+//!   steps over integer weights, giving a circuit that needs only integer additions
+//!
+//! `HalfAdder`, `FullAdder` and `ALU` can be switched to [`Mode::Compiled`] with their
+//! `compiled()` methods, which replace every gate by its lookup table.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::computer::compiled::Mode;
+//! # use aspirina_gates::computer::half_adder::HalfAdder;
+//! let neural = HalfAdder::new();
+//! let compiled = neural.compiled().unwrap();
+//!
+//! assert_eq!(compiled.mode(), Mode::Compiled);
+//! assert_eq!(compiled.compute(true, true), neural.compute(true, true));
+//! ```
+
+use super::gates::LogicGate;
+use super::truth_table::TruthTable;
+use std::fmt;
+use std::rc::Rc;
+
+/// How a component evaluates its gates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Forward passes through the trained networks
+    Neural,
+    /// Lookups in tables compiled from the trained networks
+    Compiled,
+}
+
+/// A neuron with integer weights that fires when `weights · inputs + bias > 0`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThresholdUnit {
+    pub weights: Vec<i64>,
+    pub bias: i64,
+}
+
+impl ThresholdUnit {
+    /// The weighted sum of the high inputs plus the bias
+    pub fn activation(&self, inputs: &[bool]) -> i64 {
+        let sum: i64 = self
+            .weights
+            .iter()
+            .zip(inputs)
+            .filter(|(_, input)| **input)
+            .map(|(weight, _)| weight)
+            .sum();
+        sum + self.bias
+    }
+
+    /// Whether the unit fires for these inputs
+    pub fn fires(&self, inputs: &[bool]) -> bool {
+        self.activation(inputs) > 0
+    }
+}
+
+impl fmt::Display for ThresholdUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let weights: Vec<String> = self.weights.iter().map(i64::to_string).collect();
+        write!(f, "[{}] {:+} > 0", weights.join(" "), self.bias)
+    }
+}
+
+/// Layers of integer threshold units, the last one holding the single output unit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThresholdCircuit {
+    pub layers: Vec<Vec<ThresholdUnit>>,
+}
+
+impl ThresholdCircuit {
+    /// Number of inputs of the first layer
+    pub fn inputs(&self) -> usize {
+        self.layers[0][0].weights.len()
+    }
+
+    /// The circuit's output for one combination of inputs
+    pub fn evaluate(&self, inputs: &[bool]) -> bool {
+        let output = self.layers.iter().fold(inputs.to_vec(), |signals, layer| {
+            layer.iter().map(|unit| unit.fires(&signals)).collect()
+        });
+        output[0]
+    }
+
+    /// The circuit's output for every row
+    pub fn truth_table(&self) -> TruthTable {
+        let inputs = self.inputs();
+        let outputs = (0..1usize << inputs)
+            .map(|row| {
+                let bits: Vec<bool> = (0..inputs)
+                    .map(|bit| (row >> (inputs - 1 - bit)) & 1 == 1)
+                    .collect();
+                self.evaluate(&bits)
+            })
+            .collect();
+
+        TruthTable::new(inputs, outputs)
+    }
+}
+
+impl fmt::Display for ThresholdCircuit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, layer) in self.layers.iter().enumerate() {
+            writeln!(f, "Layer {}:", index + 1)?;
+            for unit in layer {
+                writeln!(f, "  {}", unit)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A gate inside a component: the shared trained network or its compiled table
+#[derive(Debug, Clone)]
+pub(crate) enum Gate {
+    Neural(Rc<LogicGate>),
+    Compiled(Rc<TruthTable>),
+}
+
+impl Gate {
+    pub(crate) fn compute(&self, inputs: &[bool]) -> bool {
+        match self {
+            Gate::Neural(gate) => {
+                let inputs = inputs
+                    .iter()
+                    .map(|bit| if *bit { 1.0 } else { 0.0 })
+                    .collect();
+                gate.compute(inputs) > 0.5
+            }
+            Gate::Compiled(table) => table.evaluate(inputs),
+        }
+    }
+
+    pub(crate) fn compiled(&self) -> Result<Gate, String> {
+        match self {
+            Gate::Neural(gate) => Ok(Gate::Compiled(Rc::new(gate.compile()?))),
+            Gate::Compiled(table) => Ok(Gate::Compiled(Rc::clone(table))),
+        }
+    }
+
+    pub(crate) fn mode(&self) -> Mode {
+        match self {
+            Gate::Neural(_) => Mode::Neural,
+            Gate::Compiled(_) => Mode::Compiled,
+        }
+    }
+}
+
+impl From<Rc<LogicGate>> for Gate {
+    fn from(gate: Rc<LogicGate>) -> Self {
+        Gate::Neural(gate)
+    }
+}
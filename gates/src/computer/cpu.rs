@@ -1,6 +1,8 @@
 use super::alu::{ALUOperation, ALU};
 use super::memory::Memory;
 use super::registers::CPURegisters;
+use super::registry::GateRegistry;
+use super::routing::Decoder;
 
 /// 4-bit CPU Instructions
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -23,6 +25,26 @@ pub enum Instruction {
     HALT,      // 0xF: Stop execution
 }
 
+/// Instruction selected by each decoder line, given the operand byte
+const INSTRUCTIONS: [fn(u8) -> Instruction; 16] = [
+    |_| Instruction::NOP,
+    Instruction::LOAD,
+    Instruction::STORE,
+    Instruction::ADD,
+    Instruction::SUB,
+    Instruction::AND,
+    Instruction::OR,
+    Instruction::XOR,
+    Instruction::JUMP,
+    Instruction::JZ,
+    Instruction::LDI,
+    |_| Instruction::INC,
+    |_| Instruction::DEC,
+    Instruction::CMP,
+    Instruction::JNZ,
+    |_| Instruction::HALT,
+];
+
 /// Simple 4-bit CPU implementation
 pub struct SimpleCPU {
     pub registers: CPURegisters,
@@ -30,20 +52,47 @@ pub struct SimpleCPU {
     pub alu: ALU,
     pub halted: bool,
     pub cycle_count: usize,
+    // Opcode decoding through neural one-hot lines, when routed
+    decoder: Option<Decoder>,
 }
 
 impl SimpleCPU {
     /// Create a new CPU instance
     pub fn new() -> Self {
+        Self::with_registry(&GateRegistry::new())
+    }
+
+    /// Create a CPU whose ALU shares the registry's trained gates
+    pub fn with_registry(registry: &GateRegistry) -> Self {
         SimpleCPU {
             registers: CPURegisters::new(),
             memory: Memory::new(),
-            alu: ALU::new(),
+            alu: ALU::with_registry(registry),
             halted: false,
             cycle_count: 0,
+            decoder: None,
         }
     }
 
+    /// Replace the register set, e.g. with [`CPURegisters::neural`]
+    pub fn with_registers(mut self, registers: CPURegisters) -> Self {
+        self.registers = registers;
+        self
+    }
+
+    /// Route memory addressing and opcode decoding through neural multiplexers and decoders
+    /// built from the registry's gates
+    pub fn with_routing(mut self, registry: &GateRegistry) -> Self {
+        self.memory = std::mem::take(&mut self.memory).with_routing(registry);
+        self.decoder = Some(Decoder::with_registry(registry));
+        self
+    }
+
+    /// Whether memory and opcodes are routed through neural components
+    pub fn is_routed(&self) -> bool {
+        self.decoder.is_some()
+    }
+
     /// Reset CPU to initial state
     pub fn reset(&mut self) {
         self.registers.reset();
@@ -65,6 +114,23 @@ impl SimpleCPU {
         instruction
     }
 
+    /// Decode an opcode through the decoder's one-hot lines
+    ///
+    /// The raised line selects the instruction from [`INSTRUCTIONS`]. If no line is raised
+    /// the instruction is a NOP; if several are, the lowest wins.
+    fn decode_lines(&self, decoder: &Decoder, opcode: u8) -> Instruction {
+        let select = std::array::from_fn(|bit| opcode & (1 << bit) != 0);
+        let pc = self.registers.program_counter.read();
+        let operand = self.memory.read((pc + 1) & 0x0F);
+
+        decoder
+            .compute(select)
+            .iter()
+            .zip(INSTRUCTIONS)
+            .find(|(line, _)| **line)
+            .map_or(Instruction::NOP, |(_, instruction)| instruction(operand))
+    }
+
     /// Decode instruction byte into Instruction enum
     /// Since memory is 4-bit, we need a two-byte instruction format:
     /// First byte: opcode, Second byte: operand (if needed)
@@ -279,7 +345,10 @@ impl SimpleCPU {
         }
 
         let instruction_byte = self.fetch();
-        let instruction = self.decode(instruction_byte);
+        let instruction = match &self.decoder {
+            Some(decoder) => self.decode_lines(decoder, instruction_byte),
+            None => self.decode(instruction_byte),
+        };
         self.execute(instruction);
         self.cycle_count += 1;
     }
@@ -1,13 +1,28 @@
-use super::gates::{GateType, LogicGate};
+use super::compiled::{Gate, Mode};
+use super::gates::GateType;
 use super::half_adder::HalfAdder;
+use super::registry::GateRegistry;
 
 /// A full adder built from two half adders and an OR gate
 /// Full Adder = HalfAdder1(A, B) + HalfAdder2(Sum1, Cin) + OR(Carry1, Carry2)
+///
+/// [`FullAdder::wide`] builds it from two native 3-input gates instead:
+/// Sum = PARITY(A, B, Cin), Carry = MAJORITY(A, B, Cin)
 #[derive(Debug)]
 pub struct FullAdder {
-    half_adder1: HalfAdder, // A + B
-    half_adder2: HalfAdder, // (A XOR B) + Cin
-    or_gate: LogicGate,     // Carry1 OR Carry2
+    circuit: Circuit,
+}
+
+#[derive(Debug)]
+enum Circuit {
+    HalfAdders {
+        half_adders: Box<[HalfAdder; 2]>, // A + B, then (A XOR B) + Cin
+        or_gate: Gate,                    // Carry1 OR Carry2
+    },
+    Wide {
+        parity_gate: Gate,   // odd number of inputs high
+        majority_gate: Gate, // at least two inputs high
+    },
 }
 
 /// Result of full adder computation
@@ -20,36 +35,102 @@ pub struct FullAdderResult {
 impl FullAdder {
     /// Create a new full adder using two half adders
     pub fn new() -> Self {
-        let half_adder1 = HalfAdder::new();
-        let half_adder2 = HalfAdder::new();
-        let or_gate = LogicGate::new(GateType::OR);
-
-        // Train the OR gate
-        or_gate.train(10_000);
+        Self::with_registry(&GateRegistry::new())
+    }
 
+    /// Create a full adder from two half adders, sharing the registry's trained gates
+    pub fn with_registry(registry: &GateRegistry) -> Self {
         FullAdder {
-            half_adder1,
-            half_adder2,
-            or_gate,
+            circuit: Circuit::HalfAdders {
+                half_adders: Box::new([
+                    HalfAdder::with_registry(registry),
+                    HalfAdder::with_registry(registry),
+                ]),
+                or_gate: registry.get(GateType::OR, 2).into(),
+            },
         }
     }
 
-    /// Compute full adder output for three binary inputs (A, B, Carry_in)
-    pub fn compute(&self, a: bool, b: bool, carry_in: bool) -> FullAdderResult {
-        // First half adder: A + B
-        let result1 = self.half_adder1.compute(a, b);
+    /// Create a full adder from a 3-input parity gate and a 3-input majority gate
+    pub fn wide() -> Self {
+        Self::wide_with_registry(&GateRegistry::new())
+    }
+
+    /// Create a wide full adder, sharing the registry's trained gates
+    pub fn wide_with_registry(registry: &GateRegistry) -> Self {
+        FullAdder {
+            circuit: Circuit::Wide {
+                parity_gate: registry.get(GateType::PARITY, 3).into(),
+                majority_gate: registry.get(GateType::MAJORITY, 3).into(),
+            },
+        }
+    }
 
-        // Second half adder: (A XOR B) + Cin
-        let result2 = self.half_adder2.compute(result1.sum, carry_in);
+    /// A copy of this full adder running on lookup tables compiled from its gates
+    pub fn compiled(&self) -> Result<Self, String> {
+        let circuit = match &self.circuit {
+            Circuit::HalfAdders {
+                half_adders,
+                or_gate,
+            } => {
+                let [half_adder1, half_adder2] = half_adders.as_ref();
+                Circuit::HalfAdders {
+                    half_adders: Box::new([half_adder1.compiled()?, half_adder2.compiled()?]),
+                    or_gate: or_gate.compiled()?,
+                }
+            }
+            Circuit::Wide {
+                parity_gate,
+                majority_gate,
+            } => Circuit::Wide {
+                parity_gate: parity_gate.compiled()?,
+                majority_gate: majority_gate.compiled()?,
+            },
+        };
+
+        Ok(FullAdder { circuit })
+    }
 
-        // Final carry: Carry1 OR Carry2
-        let carry1_f = if result1.carry { 1.0 } else { 0.0 };
-        let carry2_f = if result2.carry { 1.0 } else { 0.0 };
-        let final_carry_output = self.or_gate.compute(vec![carry1_f, carry2_f]);
+    /// Whether the gates run as networks or as compiled tables
+    pub fn mode(&self) -> Mode {
+        match &self.circuit {
+            Circuit::HalfAdders { or_gate, .. } => or_gate.mode(),
+            Circuit::Wide { parity_gate, .. } => parity_gate.mode(),
+        }
+    }
 
-        FullAdderResult {
-            sum: result2.sum,
-            carry: final_carry_output > 0.5,
+    /// Compute full adder output for three binary inputs (A, B, Carry_in)
+    pub fn compute(&self, a: bool, b: bool, carry_in: bool) -> FullAdderResult {
+        match &self.circuit {
+            Circuit::HalfAdders {
+                half_adders,
+                or_gate,
+            } => {
+                let [half_adder1, half_adder2] = half_adders.as_ref();
+
+                // First half adder: A + B
+                let result1 = half_adder1.compute(a, b);
+
+                // Second half adder: (A XOR B) + Cin
+                let result2 = half_adder2.compute(result1.sum, carry_in);
+
+                // Final carry: Carry1 OR Carry2
+                FullAdderResult {
+                    sum: result2.sum,
+                    carry: or_gate.compute(&[result1.carry, result2.carry]),
+                }
+            }
+            Circuit::Wide {
+                parity_gate,
+                majority_gate,
+            } => {
+                let inputs = [a, b, carry_in];
+
+                FullAdderResult {
+                    sum: parity_gate.compute(&inputs),
+                    carry: majority_gate.compute(&inputs),
+                }
+            }
         }
     }
 
@@ -1,48 +1,358 @@
+use super::compiled::{ThresholdCircuit, ThresholdUnit};
+use super::truth_table::TruthTable;
 use crate::layer::Layer;
+use crate::metrics;
 use crate::neural_network::NeuralNetwork;
 use aspirina_core::matrix::Matrix;
+use aspirina_core::random::Rng;
+use std::fmt;
+
+/// Widest gate supported; an 8-input gate trains on 256 rows
+pub const MAX_INPUTS: usize = 8;
 
 /// Represents all available logic gates
-#[derive(Debug, Clone)]
+///
+/// Two-input gates generalize to `n` inputs following the IEEE 91 symbols: XOR is high when
+/// exactly one input is high, while PARITY is high when an odd number of inputs are high.
+/// For two inputs both are the same function.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum GateType {
     AND,
     OR,
     XOR,
+    NOT,
+    NAND,
+    NOR,
+    XNOR,
+    /// Single-input identity gate, useful as a signal repeater
+    BUFFER,
+    /// High when more than half of the inputs are high
+    MAJORITY,
+    /// High when an odd number of inputs are high
+    PARITY,
+    /// High when at least this many inputs are high
+    THRESHOLD(usize),
+    /// Any boolean function, given by its truth table
+    CUSTOM(TruthTable),
+}
+
+impl GateType {
+    /// Every fixed gate type: the seven boolean operations, the buffer, majority and parity
+    pub const ALL: [GateType; 10] = [
+        GateType::AND,
+        GateType::OR,
+        GateType::XOR,
+        GateType::NOT,
+        GateType::NAND,
+        GateType::NOR,
+        GateType::XNOR,
+        GateType::BUFFER,
+        GateType::MAJORITY,
+        GateType::PARITY,
+    ];
+
+    /// Usual number of inputs: 1 for NOT and BUFFER, 3 for MAJORITY and PARITY, 2 otherwise
+    pub fn default_inputs(&self) -> usize {
+        match self {
+            GateType::NOT | GateType::BUFFER => 1,
+            GateType::MAJORITY | GateType::PARITY => 3,
+            GateType::THRESHOLD(threshold) => (*threshold).max(2),
+            GateType::CUSTOM(table) => table.inputs(),
+            _ => 2,
+        }
+    }
+
+    /// Whether the gate can take `inputs` inputs
+    ///
+    /// NOT and BUFFER take exactly one, every other gate between 2 and [`MAX_INPUTS`], and a
+    /// threshold must be between 1 and the number of inputs. A custom gate takes exactly the
+    /// inputs of its truth table.
+    pub fn supports(&self, inputs: usize) -> bool {
+        match self {
+            GateType::NOT | GateType::BUFFER => inputs == 1,
+            GateType::CUSTOM(table) => inputs == table.inputs(),
+            GateType::THRESHOLD(threshold) => {
+                (2..=MAX_INPUTS).contains(&inputs) && (1..=inputs).contains(threshold)
+            }
+            _ => (2..=MAX_INPUTS).contains(&inputs),
+        }
+    }
+
+    /// The gate's boolean function
+    pub fn evaluate(&self, inputs: &[bool]) -> bool {
+        let high = inputs.iter().filter(|input| **input).count();
+        match self {
+            GateType::AND => high == inputs.len(),
+            GateType::OR => high > 0,
+            GateType::XOR => high == 1,
+            GateType::NOT => !inputs[0],
+            GateType::NAND => high < inputs.len(),
+            GateType::NOR => high == 0,
+            GateType::XNOR => high != 1,
+            GateType::BUFFER => inputs[0],
+            GateType::MAJORITY => 2 * high > inputs.len(),
+            GateType::PARITY => high % 2 == 1,
+            GateType::THRESHOLD(threshold) => high >= *threshold,
+            GateType::CUSTOM(table) => table.evaluate(inputs),
+        }
+    }
+
+    /// Parses a gate name as printed by `Display`, e.g. `XOR`, `THRESHOLD(2)` or
+    /// `CUSTOM(0110)`
+    pub fn parse(name: &str) -> Result<Self, String> {
+        let gate_type = match name {
+            "AND" => GateType::AND,
+            "OR" => GateType::OR,
+            "XOR" => GateType::XOR,
+            "NOT" => GateType::NOT,
+            "NAND" => GateType::NAND,
+            "NOR" => GateType::NOR,
+            "XNOR" => GateType::XNOR,
+            "BUFFER" => GateType::BUFFER,
+            "MAJORITY" => GateType::MAJORITY,
+            "PARITY" => GateType::PARITY,
+            _ => {
+                if let Some(bits) = name
+                    .strip_prefix("CUSTOM(")
+                    .and_then(|rest| rest.strip_suffix(')'))
+                {
+                    return TruthTable::from_output_bits(bits).map(GateType::CUSTOM);
+                }
+                name.strip_prefix("THRESHOLD(")
+                    .and_then(|rest| rest.strip_suffix(')'))
+                    .and_then(|threshold| threshold.parse().ok())
+                    .map(GateType::THRESHOLD)
+                    .ok_or_else(|| format!("Unknown gate type {:?}", name))?
+            }
+        };
+
+        Ok(gate_type)
+    }
+
+    /// All `2^inputs` rows in binary counting order, paired with the expected output
+    pub fn truth_table(&self, inputs: usize) -> Vec<(Vec<f64>, f64)> {
+        TruthTable::of(self, inputs).rows()
+    }
 }
 
-/// A neural logic gate that can perform any of the 7 basic boolean operations
+impl fmt::Display for GateType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GateType::CUSTOM(table) => write!(f, "CUSTOM({})", table.output_bits()),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+/// A neural logic gate that can perform any of the 7 basic boolean operations, plus
+/// majority, parity and threshold, over any number of inputs up to [`MAX_INPUTS`]
+///
+/// Cloning a gate copies its trained weights, which is far cheaper than training again.
 #[derive(Debug)]
 pub struct LogicGate {
     gate_type: GateType,
+    inputs: usize,
     network: NeuralNetwork,
+    // Wide networks take a constant 1 as an extra last input, acting as a bias
+    bias: bool,
+}
+
+/// One truth table row as computed by a gate
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowReport {
+    pub inputs: Vec<f64>,
+    pub expected: f64,
+    pub output: f64,
+    /// Distance from the 0.5 threshold, negative when the output is on the wrong side
+    pub margin: f64,
+}
+
+/// Result of checking a gate against its full truth table
+#[derive(Debug, Clone, PartialEq)]
+pub struct GateReport {
+    pub gate_type: GateType,
+    pub rows: Vec<RowReport>,
+    /// The smallest row margin
+    pub margin: f64,
+    /// Whether every row is on the correct side of 0.5
+    pub passed: bool,
+}
+
+/// Accuracy of a gate when its inputs carry Gaussian noise
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoiseResult {
+    pub std_dev: f64,
+    /// Fraction of noisy samples classified correctly
+    pub accuracy: f64,
+}
+
+impl GateReport {
+    /// Whether every row clears the threshold by more than `margin`
+    pub fn meets(&self, margin: f64) -> bool {
+        self.margin > margin
+    }
+}
+
+impl fmt::Display for GateReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in &self.rows {
+            let inputs: Vec<String> = row.inputs.iter().map(|v| format!("{}", v)).collect();
+            writeln!(
+                f,
+                "{}({}): {:.4} (expected: {:.1}, margin {:+.4}) {}",
+                self.gate_type,
+                inputs.join(", "),
+                row.output,
+                row.expected,
+                row.margin,
+                if row.margin > 0.0 { "✓" } else { "✗" }
+            )?;
+        }
+        write!(
+            f,
+            "Margin: {:.4} {}",
+            self.margin,
+            if self.passed { "PASS" } else { "FAIL" }
+        )
+    }
 }
 
 impl LogicGate {
-    /// Creates a new logic gate of the specified type
-    pub fn new(gate_type: GateType) -> Self {
-        let network = match gate_type {
-            GateType::AND => create_and_network(),
-            GateType::OR => create_or_network(),
-            GateType::XOR => create_xor_network(),
+    /// Creates a new logic gate of the specified type with `inputs` inputs
+    ///
+    /// The 1- and 2-input basic gates start from hand-picked weights; wider gates start from
+    /// "at least k inputs high" detectors, and custom gates from one detector per row of
+    /// their truth table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gate type does not support that many inputs.
+    pub fn new(gate_type: GateType, inputs: usize) -> Self {
+        if !gate_type.supports(inputs) {
+            panic!("{:?} gate cannot take {} input(s)", gate_type, inputs);
+        }
+
+        let bias = !matches!(
+            (&gate_type, inputs),
+            (GateType::NOT | GateType::BUFFER, _)
+                | (
+                    GateType::AND
+                        | GateType::OR
+                        | GateType::XOR
+                        | GateType::PARITY
+                        | GateType::NAND
+                        | GateType::NOR
+                        | GateType::XNOR,
+                    2
+                )
+        );
+        let network = match (&gate_type, inputs) {
+            (GateType::AND, 2) => create_and_network(),
+            (GateType::OR, 2) => create_or_network(),
+            (GateType::XOR, 2) | (GateType::PARITY, 2) => create_xor_network(),
+            (GateType::NOT, _) => create_not_network(),
+            (GateType::NAND, 2) => create_nand_network(),
+            (GateType::NOR, 2) => create_nor_network(),
+            (GateType::XNOR, 2) => create_xnor_network(),
+            (GateType::BUFFER, _) => create_buffer_network(),
+            (GateType::CUSTOM(table), _) => create_table_network(table),
+            _ => create_wide_network(inputs),
         };
 
-        LogicGate { gate_type, network }
+        LogicGate {
+            gate_type,
+            inputs,
+            network,
+            bias,
+        }
     }
 
-    /// Train the gate with appropriate training data
+    /// Creates a gate whose starting weights are the default ones plus uniform noise in
+    /// `±0.5` drawn from `seed`, so a failed training can be retried from elsewhere
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gate type does not support that many inputs.
+    pub fn with_seed(gate_type: GateType, inputs: usize, seed: u64) -> Self {
+        let gate = LogicGate::new(gate_type, inputs);
+        let mut rng = Rng::new(seed);
+        let parameters = gate
+            .parameters()
+            .into_iter()
+            .map(|layer| {
+                layer
+                    .into_iter()
+                    .map(|matrix| {
+                        Matrix::new(
+                            matrix
+                                .data
+                                .iter()
+                                .map(|row| row.iter().map(|w| w + rng.range(-0.5, 0.5)).collect())
+                                .collect(),
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+        gate.set_parameters(parameters);
+        gate
+    }
+
+    /// Trains gates until one clears every row by more than `margin`
+    ///
+    /// The first attempt starts from the default weights, every retry from
+    /// [`LogicGate::with_seed`] with the attempt number as seed.
+    pub fn train_verified(
+        gate_type: GateType,
+        inputs: usize,
+        epochs: usize,
+        margin: f64,
+        attempts: usize,
+    ) -> Result<LogicGate, String> {
+        let mut best = f64::NEG_INFINITY;
+
+        for attempt in 0..attempts {
+            let gate = if attempt == 0 {
+                LogicGate::new(gate_type.clone(), inputs)
+            } else {
+                LogicGate::with_seed(gate_type.clone(), inputs, attempt as u64)
+            };
+            gate.train(epochs);
+
+            let report = gate.verify();
+            if report.meets(margin) {
+                return Ok(gate);
+            }
+            best = best.max(report.margin);
+        }
+
+        Err(format!(
+            "{} gate with {} input(s) failed verification after {} attempt(s) of {} epochs: \
+             best margin {:.4}, required {:.4}",
+            gate_type, inputs, attempts, epochs, best, margin
+        ))
+    }
+
+    /// Synthesizes a gate for any boolean function
+    ///
+    /// The hidden layer gets one detector for each row on the rarer side of the table, plus
+    /// a constant neuron, which is always enough to represent the function. The gate is
+    /// trained with [`LogicGate::train_verified`] for 10,000 epochs and up to three attempts.
+    pub fn from_truth_table(table: TruthTable) -> Result<LogicGate, String> {
+        let inputs = table.inputs();
+        LogicGate::train_verified(GateType::CUSTOM(table), inputs, 10_000, 0.0, 3)
+    }
+
+    /// Train the gate on its full truth table
     pub fn train(&self, epochs: usize) {
-        let input = Matrix::new(vec![
-            vec![0.0, 0.0],
-            vec![0.0, 1.0],
-            vec![1.0, 0.0],
-            vec![1.0, 1.0],
-        ]);
-
-        let targets = match self.gate_type {
-            GateType::AND => Matrix::new(vec![vec![0.0, 0.0, 0.0, 1.0]]),
-            GateType::OR => Matrix::new(vec![vec![0.0, 1.0, 1.0, 1.0]]),
-            GateType::XOR => Matrix::new(vec![vec![0.0, 1.0, 1.0, 0.0]]),
-        };
+        let table = self.truth_table();
+        let input = Matrix::new(
+            table
+                .iter()
+                .map(|(inputs, _)| self.network_input(inputs.clone()))
+                .collect(),
+        );
+        let targets = Matrix::new(vec![table.iter().map(|(_, output)| *output).collect()]);
 
         for _ in 0..epochs {
             self.network.train(input.clone(), targets.clone());
@@ -50,16 +360,261 @@ impl LogicGate {
     }
 
     /// Compute the gate output for given inputs
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs does not match the gate.
     pub fn compute(&self, inputs: Vec<f64>) -> f64 {
-        let input_matrix = Matrix::new(vec![inputs]);
+        if inputs.len() != self.inputs {
+            panic!(
+                "{:?} gate expects {} input(s), got {}",
+                self.gate_type,
+                self.inputs,
+                inputs.len()
+            );
+        }
+
+        let input_matrix = Matrix::new(vec![self.network_input(inputs)]);
         let result = self.network.predict(input_matrix);
         result.data[0][0]
     }
 
+    /// Computes every row of the truth table and how far each output is from 0.5
+    pub fn verify(&self) -> GateReport {
+        let rows: Vec<RowReport> = self
+            .truth_table()
+            .into_iter()
+            .map(|(inputs, expected)| {
+                let output = self.compute(inputs.clone());
+                let margin = if expected > 0.5 {
+                    output - 0.5
+                } else {
+                    0.5 - output
+                };
+                RowReport {
+                    inputs,
+                    expected,
+                    output,
+                    margin,
+                }
+            })
+            .collect();
+        let margin = rows
+            .iter()
+            .map(|row| row.margin)
+            .fold(f64::INFINITY, f64::min);
+
+        GateReport {
+            gate_type: self.gate_type.clone(),
+            rows,
+            margin,
+            passed: margin > 0.0,
+        }
+    }
+
+    /// Whether every row of the truth table lands on the correct side of 0.5
+    pub fn is_exact(&self) -> bool {
+        self.verify().passed
+    }
+
+    /// Accuracy under Gaussian input noise, for each standard deviation in `std_devs`
+    ///
+    /// Every truth table row is presented `trials` times with independent noise
+    /// `N(0, std_dev²)` added to each input. Every level restarts from `seed`, so the levels
+    /// see the same noise pattern, only scaled.
+    pub fn noise_sweep(&self, std_devs: &[f64], trials: usize, seed: u64) -> Vec<NoiseResult> {
+        let table = self.truth_table();
+
+        std_devs
+            .iter()
+            .map(|&std_dev| {
+                let mut rng = Rng::new(seed);
+                let mut correct = 0;
+                for _ in 0..trials {
+                    for (inputs, expected) in &table {
+                        let noisy = inputs
+                            .iter()
+                            .map(|x| x + rng.normal(0.0, std_dev))
+                            .collect();
+                        if (self.compute(noisy) > 0.5) == (*expected > 0.5) {
+                            correct += 1;
+                        }
+                    }
+                }
+
+                NoiseResult {
+                    std_dev,
+                    accuracy: correct as f64 / (trials * table.len()) as f64,
+                }
+            })
+            .collect()
+    }
+
+    /// Collapses the network into an exact lookup table of its answers
+    ///
+    /// Fails if any row lands on the wrong side of 0.5, as the table would then differ from
+    /// the gate's truth table.
+    pub fn compile(&self) -> Result<TruthTable, String> {
+        let outputs = self
+            .truth_table()
+            .into_iter()
+            .map(|(inputs, _)| self.compute(inputs) > 0.5)
+            .collect();
+        let table = TruthTable::new(self.inputs, outputs);
+
+        if table != TruthTable::of(&self.gate_type, self.inputs) {
+            return Err(format!(
+                "{} gate does not reproduce its truth table",
+                self.gate_type
+            ));
+        }
+        Ok(table)
+    }
+
+    /// Converts the network into integer threshold units
+    ///
+    /// Hidden weights are multiplied by a scale of 1, 2, 4, ... up to 1024 and rounded, the
+    /// constant bias input, if any, becoming each neuron's bias. A sigmoid's output depends
+    /// on which of its distinct pre-activation levels a row reaches, so each hidden neuron
+    /// becomes one step unit per level above the lowest, and the output is linear in those
+    /// steps. The output unit is then fit with the perceptron rule, which only adds and
+    /// subtracts whole step outputs. The smallest scale that reproduces the truth table wins.
+    pub fn to_threshold_circuit(&self) -> Result<ThresholdCircuit, String> {
+        let expected = self.compile()?;
+        let hidden = &self.parameters()[0][0];
+        let rows: Vec<Vec<bool>> = self
+            .truth_table()
+            .iter()
+            .map(|(inputs, _)| inputs.iter().map(|x| *x == 1.0).collect())
+            .collect();
+
+        for scale in (0..=10).map(|power| (1i64 << power) as f64) {
+            let mut units = Vec::new();
+            for neuron in &hidden.data {
+                let mut weights: Vec<i64> =
+                    neuron.iter().map(|w| (w * scale).round() as i64).collect();
+                let bias = if self.bias { weights.pop().unwrap() } else { 0 };
+                let unit = ThresholdUnit { weights, bias };
+
+                let mut levels: Vec<i64> = rows.iter().map(|row| unit.activation(row)).collect();
+                levels.sort_unstable();
+                levels.dedup();
+                for level in &levels[..levels.len() - 1] {
+                    let step = ThresholdUnit {
+                        weights: unit.weights.clone(),
+                        bias: unit.bias - level,
+                    };
+                    if !units.contains(&step) {
+                        units.push(step);
+                    }
+                }
+            }
+
+            let features: Vec<Vec<bool>> = rows
+                .iter()
+                .map(|row| units.iter().map(|unit| unit.fires(row)).collect())
+                .collect();
+            let mut output = ThresholdUnit {
+                weights: vec![0; units.len()],
+                bias: 0,
+            };
+            for _ in 0..1000 {
+                let mut errors = 0;
+                for (feature, target) in features.iter().zip(expected.outputs()) {
+                    if output.fires(feature) != *target {
+                        let step = if *target { 1 } else { -1 };
+                        for (weight, active) in output.weights.iter_mut().zip(feature) {
+                            if *active {
+                                *weight += step;
+                            }
+                        }
+                        output.bias += step;
+                        errors += 1;
+                    }
+                }
+                if errors == 0 {
+                    break;
+                }
+            }
+
+            let circuit = ThresholdCircuit {
+                layers: vec![units, vec![output]],
+            };
+            if circuit.truth_table() == expected {
+                return Ok(circuit);
+            }
+        }
+
+        Err(format!(
+            "{} gate has no exact threshold circuit up to scale 1024",
+            self.gate_type
+        ))
+    }
+
+    /// Copies of the network weights, in the layout of [`NeuralNetwork::parameters`]
+    pub fn parameters(&self) -> Vec<Vec<Matrix>> {
+        self.network.parameters()
+    }
+
+    /// Replaces the network weights, e.g. with ones saved from a trained gate
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shapes do not match the gate's network.
+    pub fn set_parameters(&self, parameters: Vec<Vec<Matrix>>) {
+        if parameter_shapes(&parameters) != parameter_shapes(&self.network.parameters()) {
+            panic!(
+                "Parameters do not match the {} gate's network",
+                self.gate_type
+            );
+        }
+
+        self.network.set_parameters(parameters);
+    }
+
     /// Get the gate type
     pub fn gate_type(&self) -> &GateType {
         &self.gate_type
     }
+
+    /// Number of inputs
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    /// The gate's expected truth table, `2^inputs` rows
+    pub fn truth_table(&self) -> Vec<(Vec<f64>, f64)> {
+        self.gate_type.truth_table(self.inputs)
+    }
+
+    fn network_input(&self, mut inputs: Vec<f64>) -> Vec<f64> {
+        if self.bias {
+            inputs.push(1.0);
+        }
+        inputs
+    }
+}
+
+impl Clone for LogicGate {
+    // Deep copy: cloning the network itself would share its layers
+    fn clone(&self) -> Self {
+        let gate = LogicGate::new(self.gate_type.clone(), self.inputs);
+        gate.set_parameters(self.parameters());
+        gate
+    }
+}
+
+/// Rows and columns of every parameter matrix, layer by layer
+pub(crate) fn parameter_shapes(parameters: &[Vec<Matrix>]) -> Vec<Vec<(usize, usize)>> {
+    parameters
+        .iter()
+        .map(|layer| {
+            layer
+                .iter()
+                .map(|m| (m.data.len(), m.data.first().map_or(0, Vec::len)))
+                .collect()
+        })
+        .collect()
 }
 
 // Network creation functions for each gate type
@@ -100,58 +655,143 @@ fn create_xor_network() -> NeuralNetwork {
     NeuralNetwork::new(layers)
 }
 
+fn create_not_network() -> NeuralNetwork {
+    let layers = vec![
+        Layer::new(Matrix::new(vec![
+            vec![-1.5], // Strong negative weight to invert
+            vec![0.8],  // Helper neuron
+        ])),
+        Layer::new(Matrix::new(vec![vec![1.2, -0.6]])),
+    ];
+    NeuralNetwork::new(layers)
+}
+
+fn create_nand_network() -> NeuralNetwork {
+    let layers = vec![
+        Layer::new(Matrix::new(vec![
+            vec![-0.8, -0.8], // Negative weights for NAND logic
+            vec![0.5, 0.5],   // Positive bias neuron
+            vec![-0.3, 0.3],  // Mixed weights for complexity
+        ])),
+        Layer::new(Matrix::new(vec![vec![-1.2, 1.5, 0.8]])),
+    ];
+    NeuralNetwork::new(layers)
+}
+
+fn create_nor_network() -> NeuralNetwork {
+    let layers = vec![
+        Layer::new(Matrix::new(vec![
+            vec![-0.8, -0.8], // Negative weights to invert OR logic
+            vec![-0.5, -0.5], // Additional negative weights
+            vec![0.3, 0.3],   // Helper positive weights
+        ])),
+        Layer::new(Matrix::new(vec![vec![1.0, 1.2, -0.4]])),
+    ];
+    NeuralNetwork::new(layers)
+}
+
+fn create_xnor_network() -> NeuralNetwork {
+    let layers = vec![
+        Layer::new(Matrix::new(vec![
+            vec![-0.5, -0.5],
+            vec![0.3, 0.3],
+            vec![0.8, -0.8],
+            vec![-0.6, 0.6],
+        ])),
+        Layer::new(Matrix::new(vec![vec![-0.9, 0.7, -1.2, 0.4]])), // Inverted from XOR weights
+    ];
+    NeuralNetwork::new(layers)
+}
+
+fn create_buffer_network() -> NeuralNetwork {
+    let layers = vec![
+        Layer::new(Matrix::new(vec![
+            vec![1.5],  // Strong positive weight passes the signal through
+            vec![-0.8], // Helper neuron
+        ])),
+        Layer::new(Matrix::new(vec![vec![1.2, -0.6]])),
+    ];
+    NeuralNetwork::new(layers)
+}
+
+fn create_wide_network(inputs: usize) -> NeuralNetwork {
+    // Every wide gate is symmetric: its output only depends on how many inputs are high.
+    // Hidden neuron k starts as a "count >= k" detector, using the constant last input as its
+    // bias, so the output layer only has to learn which counts are high. Gradients are summed
+    // over the batch, so the learning rate shrinks with the 2^n rows of the table.
+    let sharpness = 6.0;
+    let detectors = (0..=inputs)
+        .map(|k| {
+            let mut weights = vec![sharpness; inputs];
+            weights.push(-sharpness * (k as f64 - 0.5));
+            weights
+        })
+        .collect();
+    let layers = vec![
+        Layer::new(Matrix::new(detectors)),
+        Layer::new(Matrix::new(vec![vec![0.0; inputs + 1]])),
+    ];
+    NeuralNetwork::new(layers).with_learning_rate(4.0 / (1usize << inputs) as f64)
+}
+
+fn create_table_network(table: &TruthTable) -> NeuralNetwork {
+    // Hidden neurons start as exact row detectors: +w for every high bit of the row, -w for
+    // every low bit, with the bias set so only that row clears zero. Detecting the rows of the
+    // rarer output keeps the layer at most 2^(n-1) + 1 wide; the last neuron only sees the
+    // bias and stays on, letting the output layer set the default.
+    let inputs = table.inputs();
+    let rarer = table.outputs().iter().filter(|output| **output).count() * 2 <= 1 << inputs;
+    let sharpness = 6.0;
+    let mut detectors: Vec<Vec<f64>> = table
+        .rows()
+        .iter()
+        .filter(|(_, output)| (*output == 1.0) == rarer)
+        .map(|(bits, _)| {
+            let high = bits.iter().sum::<f64>();
+            let mut weights: Vec<f64> = bits
+                .iter()
+                .map(|bit| if *bit == 1.0 { sharpness } else { -sharpness })
+                .collect();
+            weights.push(-sharpness * (high - 0.5));
+            weights
+        })
+        .collect();
+    let mut constant = vec![0.0; inputs];
+    constant.push(sharpness);
+    detectors.push(constant);
+
+    let hidden = detectors.len();
+    let layers = vec![
+        Layer::new(Matrix::new(detectors)),
+        Layer::new(Matrix::new(vec![vec![0.0; hidden]])),
+    ];
+    NeuralNetwork::new(layers).with_learning_rate(4.0 / (1usize << inputs) as f64)
+}
+
 /// Convenience function to train and test all gates
 pub fn test_all_gates() {
-    let gates = [GateType::AND, GateType::OR, GateType::XOR];
-
-    for gate_type in gates.iter() {
-        println!("=== Testing {:?} Gate ===", gate_type);
-        let gate = LogicGate::new(gate_type.clone());
+    for gate_type in GateType::ALL.iter() {
+        println!("=== Testing {} Gate ===", gate_type);
+        let gate = LogicGate::new(gate_type.clone(), gate_type.default_inputs());
 
         // Train the gate
         gate.train(10_000);
 
         // Test the gate
-        let test_cases = get_test_cases(gate_type);
+        let report = gate.verify();
+        println!("{}", report);
 
-        for (inputs, description, expected) in test_cases.iter() {
-            let output = gate.compute(inputs.clone());
-            let success = if *expected > 0.5 {
-                output > 0.5
-            } else {
-                output < 0.5
-            };
-            println!(
-                "{}: {:.4} (expected: {:.1}) {}",
-                description,
-                output,
-                expected,
-                if success { "✓" } else { "✗" }
-            );
-        }
-        println!();
-    }
-}
+        let outputs = report.rows.iter().map(|row| vec![row.output]).collect();
+        let targets = report.rows.iter().map(|row| vec![row.expected]).collect();
+        let accuracy = metrics::accuracy(&Matrix::new(outputs), &Matrix::new(targets), 0.5);
+        println!("Accuracy: {:.0}%", accuracy * 100.0);
 
-fn get_test_cases(gate_type: &GateType) -> Vec<(Vec<f64>, &'static str, f64)> {
-    match gate_type {
-        GateType::AND => vec![
-            (vec![0.0, 0.0], "0 AND 0", 0.0),
-            (vec![0.0, 1.0], "0 AND 1", 0.0),
-            (vec![1.0, 0.0], "1 AND 0", 0.0),
-            (vec![1.0, 1.0], "1 AND 1", 1.0),
-        ],
-        GateType::OR => vec![
-            (vec![0.0, 0.0], "0 OR 0", 0.0),
-            (vec![0.0, 1.0], "0 OR 1", 1.0),
-            (vec![1.0, 0.0], "1 OR 0", 1.0),
-            (vec![1.0, 1.0], "1 OR 1", 1.0),
-        ],
-        GateType::XOR => vec![
-            (vec![0.0, 0.0], "0 XOR 0", 0.0),
-            (vec![0.0, 1.0], "0 XOR 1", 1.0),
-            (vec![1.0, 0.0], "1 XOR 0", 1.0),
-            (vec![1.0, 1.0], "1 XOR 1", 0.0),
-        ],
+        let sweep: Vec<String> = gate
+            .noise_sweep(&[0.1, 0.2, 0.3], 20, 42)
+            .iter()
+            .map(|result| format!("σ={}: {:.0}%", result.std_dev, result.accuracy * 100.0))
+            .collect();
+        println!("Noise robustness: {}", sweep.join(", "));
+        println!();
     }
 }
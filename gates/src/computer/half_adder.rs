@@ -1,11 +1,13 @@
-use super::gates::{GateType, LogicGate};
+use super::compiled::{Gate, Mode};
+use super::gates::GateType;
+use super::registry::GateRegistry;
 
 /// A half adder that computes sum and carry for two bits
 /// Sum = A XOR B, Carry = A AND B
 #[derive(Debug)]
 pub struct HalfAdder {
-    xor_gate: LogicGate,
-    and_gate: LogicGate,
+    xor_gate: Gate,
+    and_gate: Gate,
 }
 
 /// Result of half adder computation
@@ -18,27 +20,35 @@ pub struct HalfAdderResult {
 impl HalfAdder {
     /// Create a new half adder with trained gates
     pub fn new() -> Self {
-        let xor_gate = LogicGate::new(GateType::XOR);
-        let and_gate = LogicGate::new(GateType::AND);
+        Self::with_registry(&GateRegistry::new())
+    }
 
-        // Train both gates
-        xor_gate.train(10_000);
-        and_gate.train(10_000);
+    /// Create a half adder sharing the registry's trained gates
+    pub fn with_registry(registry: &GateRegistry) -> Self {
+        HalfAdder {
+            xor_gate: registry.get(GateType::XOR, 2).into(),
+            and_gate: registry.get(GateType::AND, 2).into(),
+        }
+    }
 
-        HalfAdder { xor_gate, and_gate }
+    /// A copy of this half adder running on lookup tables compiled from its gates
+    pub fn compiled(&self) -> Result<Self, String> {
+        Ok(HalfAdder {
+            xor_gate: self.xor_gate.compiled()?,
+            and_gate: self.and_gate.compiled()?,
+        })
+    }
+
+    /// Whether the gates run as networks or as compiled tables
+    pub fn mode(&self) -> Mode {
+        self.xor_gate.mode()
     }
 
     /// Compute half adder output for two binary inputs
     pub fn compute(&self, a: bool, b: bool) -> HalfAdderResult {
-        let a_f = if a { 1.0 } else { 0.0 };
-        let b_f = if b { 1.0 } else { 0.0 };
-
-        let sum_output = self.xor_gate.compute(vec![a_f, b_f]);
-        let carry_output = self.and_gate.compute(vec![a_f, b_f]);
-
         HalfAdderResult {
-            sum: sum_output > 0.5,
-            carry: carry_output > 0.5,
+            sum: self.xor_gate.compute(&[a, b]),
+            carry: self.and_gate.compute(&[a, b]),
         }
     }
 
@@ -0,0 +1,601 @@
+//! Hardware Description Language
+//!
+//! Circuits can be described in text, in the style of the nand2tetris HDL, instead of being
+//! wired in Rust. Every chip lists its input and output pins, then its parts: built-in gates
+//! or other chips, with each pin connected to a wire by name.
+//!
+//! ```text
+//! // Adds three bits
+//! CHIP FullAdder {
+//!     IN a, b, c;
+//!     OUT sum, carry;
+//!
+//!     PARTS:
+//!     HalfAdder(a=a, b=b, sum=s1, carry=c1);
+//!     HalfAdder(a=s1, b=c, sum=sum, carry=c2);
+//!     Or(a=c1, b=c2, out=carry);
+//! }
+//! ```
+//!
+//! The built-in gates are `And`, `Or`, `Xor`, `Nand`, `Nor`, `Xnor`, `Majority` and
+//! `Parity`, taking inputs `a`, `b`, `c`, ... in order, plus `Not` and `Buffer` taking
+//! `in`; all of them drive `out`. Chips may use chips defined anywhere in the same library,
+//! and the wires `true` and `false` carry constants.
+//!
+//! Elaborating a chip turns it into a [`Circuit`] netlist, and [`Library::build`]
+//! instantiates its neural [`LogicGate`](super::gates::LogicGate)s from a [`GateRegistry`].
+//! Every error reports the line and column it was found at.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::computer::hdl::Library;
+//! # use aspirina_gates::computer::registry::GateRegistry;
+//! let library = Library::parse(
+//!     "CHIP HalfAdder { IN a, b; OUT sum, carry; PARTS:
+//!          Xor(a=a, b=b, out=sum);
+//!          And(a=a, b=b, out=carry); }",
+//! )
+//! .unwrap();
+//!
+//! let half_adder = library.build("HalfAdder", &GateRegistry::new()).unwrap();
+//! assert_eq!(half_adder.compute(&[true, true]), vec![false, true]);
+//! ```
+
+use super::circuit::{Circuit, Component};
+use super::gates::GateType;
+use super::registry::GateRegistry;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Chips parsed from HDL sources, plus any circuits added directly
+#[derive(Debug)]
+pub struct Library {
+    circuits: HashMap<String, Rc<Circuit>>,
+}
+
+/// Line and column of a token, both starting at 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Symbol(char),
+    End,
+}
+
+#[derive(Debug)]
+struct Chip {
+    name: String,
+    position: Position,
+    inputs: Vec<(String, Position)>,
+    outputs: Vec<(String, Position)>,
+    parts: Vec<PartSource>,
+}
+
+#[derive(Debug)]
+struct PartSource {
+    name: String,
+    position: Position,
+    connections: Vec<(String, Position, String)>,
+}
+
+impl Library {
+    /// Creates an empty library
+    pub fn new() -> Self {
+        Library {
+            circuits: HashMap::new(),
+        }
+    }
+
+    /// Makes a circuit available to chips under its name, e.g. [`Circuit::half_adder`]
+    pub fn with_circuit(mut self, circuit: Circuit) -> Self {
+        self.circuits
+            .insert(circuit.name().to_string(), Rc::new(circuit));
+        self
+    }
+
+    /// Parses and elaborates every chip in an HDL source
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut library = Library::new();
+        library.add_source(text)?;
+        Ok(library)
+    }
+
+    /// Reads, parses and elaborates the HDL file at `path`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|error| format!("Cannot read {}: {}", path.display(), error))?;
+
+        Self::parse(&text)
+    }
+
+    /// Parses and elaborates more chips; they may use the chips already in the library
+    ///
+    /// Either every chip in `text` is added or, on error, none of them are.
+    pub fn add_source(&mut self, text: &str) -> Result<(), String> {
+        let chips = Parser::new(text)?.chips()?;
+
+        let mut sources: HashMap<&str, &Chip> = HashMap::new();
+        for chip in &chips {
+            if self.circuits.contains_key(&chip.name) || sources.insert(&chip.name, chip).is_some()
+            {
+                return Err(error(
+                    chip.position,
+                    &format!("Chip {} is already defined", chip.name),
+                ));
+            }
+        }
+
+        let mut elaborated = HashMap::new();
+        for chip in &chips {
+            self.elaborate(chip, &sources, &mut elaborated, &mut Vec::new())?;
+        }
+        self.circuits.extend(elaborated);
+        Ok(())
+    }
+
+    /// The elaborated circuit of a chip
+    pub fn circuit(&self, name: &str) -> Option<Rc<Circuit>> {
+        self.circuits.get(name).cloned()
+    }
+
+    /// Chip names, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.circuits.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Instantiates a chip with trained gates from the registry
+    pub fn build(&self, name: &str, registry: &GateRegistry) -> Result<Component, String> {
+        self.circuit(name)
+            .ok_or_else(|| format!("Unknown chip {:?}", name))?
+            .build(registry)
+    }
+
+    /// Turns a chip into a circuit, first elaborating the chips it uses
+    ///
+    /// Circuits go into `elaborated` rather than the library, so a failing source leaves the
+    /// library untouched.
+    fn elaborate(
+        &self,
+        chip: &Chip,
+        sources: &HashMap<&str, &Chip>,
+        elaborated: &mut HashMap<String, Rc<Circuit>>,
+        stack: &mut Vec<String>,
+    ) -> Result<Rc<Circuit>, String> {
+        if let Some(circuit) = elaborated.get(&chip.name) {
+            return Ok(Rc::clone(circuit));
+        }
+        if stack.contains(&chip.name) {
+            return Err(error(
+                chip.position,
+                &format!("Chip {} contains itself", chip.name),
+            ));
+        }
+        stack.push(chip.name.clone());
+
+        let inputs: Vec<String> = chip.inputs.iter().map(|(pin, _)| pin.clone()).collect();
+        let outputs: Vec<String> = chip.outputs.iter().map(|(pin, _)| pin.clone()).collect();
+        let mut circuit = Circuit::new(&chip.name, &names(&inputs), &names(&outputs));
+
+        // Wires are checked here rather than left to Circuit::order, so errors point at the
+        // connection that causes them
+        let mut drivers: HashMap<&str, Option<usize>> = HashMap::new();
+        drivers.insert("false", None);
+        drivers.insert("true", None);
+        for (wire, position) in &chip.inputs {
+            if drivers.insert(wire, None).is_some() {
+                return Err(driven_twice(chip, wire, *position));
+            }
+        }
+        let mut uses: Vec<(usize, &str, Position)> = Vec::new();
+
+        for (index, part) in chip.parts.iter().enumerate() {
+            let mut pins: HashMap<&str, &str> = HashMap::new();
+            for (pin, position, wire) in &part.connections {
+                if pins.insert(pin, wire).is_some() {
+                    return Err(error(
+                        *position,
+                        &format!("Pin {} is connected more than once", pin),
+                    ));
+                }
+            }
+
+            let used = match sources.get(part.name.as_str()) {
+                Some(source) => Some(self.elaborate(source, sources, elaborated, stack)?),
+                None => self.circuit(&part.name),
+            };
+            let output_pins;
+            (circuit, output_pins) = match used {
+                Some(used) => {
+                    let inputs = connect(part, &pins, used.inputs(), used.outputs(), None)?;
+                    let outputs = used
+                        .outputs()
+                        .iter()
+                        .map(|pin| match pins.get(pin.as_str()) {
+                            Some(wire) => wire.to_string(),
+                            // Unconnected outputs get a wire no chip can name
+                            None => format!("{}.{}.{}", chip.name, index, pin),
+                        })
+                        .collect::<Vec<_>>();
+                    let output_pins = used.outputs().to_vec();
+                    (
+                        circuit.with_shared_circuit(used, &names(&inputs), &names(&outputs)),
+                        output_pins,
+                    )
+                }
+                None => {
+                    let gate_type = gate_type(&part.name).ok_or_else(|| {
+                        error(
+                            part.position,
+                            &format!("Unknown chip or gate {:?}", part.name),
+                        )
+                    })?;
+                    let input_pins: Vec<String> = match gate_type {
+                        GateType::NOT | GateType::BUFFER => vec!["in".to_string()],
+                        _ => (b'a'..)
+                            .map(|pin| (pin as char).to_string())
+                            .take_while(|pin| pins.contains_key(pin.as_str()))
+                            .collect(),
+                    };
+                    if !gate_type.supports(input_pins.len()) {
+                        return Err(error(
+                            part.position,
+                            &format!(
+                                "{} gate cannot take {} input(s)",
+                                gate_type,
+                                input_pins.len()
+                            ),
+                        ));
+                    }
+
+                    let out = vec!["out".to_string()];
+                    let inputs = connect(part, &pins, &input_pins, &out, Some("out"))?;
+                    (
+                        circuit.with_gate(gate_type, &names(&inputs), pins["out"]),
+                        out,
+                    )
+                }
+            };
+
+            for (pin, position, wire) in &part.connections {
+                if !output_pins.contains(pin) {
+                    uses.push((index, wire, *position));
+                } else if drivers.insert(wire, Some(index)).is_some() {
+                    return Err(driven_twice(chip, wire, *position));
+                }
+            }
+        }
+
+        let used = uses.iter().map(|(_, wire, position)| (*wire, *position));
+        let outputs = chip
+            .outputs
+            .iter()
+            .map(|(wire, position)| (wire.as_str(), *position));
+        for (wire, position) in used.chain(outputs) {
+            if !drivers.contains_key(wire) {
+                return Err(error(
+                    position,
+                    &format!("{}: wire {} is never driven", chip.name, wire),
+                ));
+            }
+        }
+
+        // The only error left is a combinational cycle, reported at its first part
+        if let Err(message) = circuit.order() {
+            let mut dependencies = vec![Vec::new(); chip.parts.len()];
+            for (index, wire, _) in &uses {
+                dependencies[*index].extend(drivers[wire]);
+            }
+            let position = first_in_cycle(&dependencies)
+                .map_or(chip.position, |index| chip.parts[index].position);
+            return Err(error(position, &message));
+        }
+
+        stack.pop();
+        let circuit = Rc::new(circuit);
+        elaborated.insert(chip.name.clone(), Rc::clone(&circuit));
+        Ok(circuit)
+    }
+}
+
+impl Default for Library {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The wires connected to each input pin, checking every pin is known and every input and
+/// `required` output is connected
+fn connect(
+    part: &PartSource,
+    pins: &HashMap<&str, &str>,
+    inputs: &[String],
+    outputs: &[String],
+    required: Option<&str>,
+) -> Result<Vec<String>, String> {
+    for (pin, position, _) in &part.connections {
+        if !inputs.contains(pin) && !outputs.contains(pin) {
+            return Err(error(
+                *position,
+                &format!("{} has no pin {}", part.name, pin),
+            ));
+        }
+    }
+
+    let missing = inputs
+        .iter()
+        .map(String::as_str)
+        .chain(required)
+        .find(|pin| !pins.contains_key(pin));
+    if let Some(pin) = missing {
+        return Err(error(
+            part.position,
+            &format!("{} is missing pin {}", part.name, pin),
+        ));
+    }
+
+    Ok(inputs
+        .iter()
+        .map(|pin| pins[pin.as_str()].to_string())
+        .collect())
+}
+
+fn driven_twice(chip: &Chip, wire: &str, position: Position) -> String {
+    error(
+        position,
+        &format!("{}: wire {} is driven more than once", chip.name, wire),
+    )
+}
+
+/// The first part that depends on its own output, given the parts driving each part's inputs
+fn first_in_cycle(dependencies: &[Vec<usize>]) -> Option<usize> {
+    (0..dependencies.len()).find(|&start| {
+        let mut visited = vec![false; dependencies.len()];
+        let mut pending = dependencies[start].clone();
+        while let Some(index) = pending.pop() {
+            if index == start {
+                return true;
+            }
+            if !visited[index] {
+                visited[index] = true;
+                pending.extend(&dependencies[index]);
+            }
+        }
+        false
+    })
+}
+
+/// The built-in gate with this name, e.g. `Xor` or `Majority`
+fn gate_type(name: &str) -> Option<GateType> {
+    let upper = name.to_uppercase();
+    GateType::ALL
+        .into_iter()
+        .find(|gate_type| gate_type.to_string() == upper)
+}
+
+fn error(position: Position, message: &str) -> String {
+    format!(
+        "Line {}, column {}: {}",
+        position.line, position.column, message
+    )
+}
+
+fn names(wires: &[String]) -> Vec<&str> {
+    wires.iter().map(String::as_str).collect()
+}
+
+/// Recursive descent over the tokens of an HDL source
+struct Parser {
+    tokens: Vec<(Token, Position)>,
+    position: usize,
+}
+
+impl Parser {
+    fn new(text: &str) -> Result<Self, String> {
+        Ok(Parser {
+            tokens: tokenize(text)?,
+            position: 0,
+        })
+    }
+
+    fn chips(&mut self) -> Result<Vec<Chip>, String> {
+        let mut chips = Vec::new();
+        while self.peek() != &Token::End {
+            chips.push(self.chip()?);
+        }
+        Ok(chips)
+    }
+
+    fn chip(&mut self) -> Result<Chip, String> {
+        let position = self.keyword("CHIP")?;
+        let name = self.identifier()?.0;
+        self.symbol('{')?;
+
+        self.keyword("IN")?;
+        let inputs = self.pins()?;
+        self.keyword("OUT")?;
+        let outputs = self.pins()?;
+        self.keyword("PARTS")?;
+        self.symbol(':')?;
+
+        let mut parts = Vec::new();
+        while self.peek() != &Token::Symbol('}') {
+            parts.push(self.part()?);
+        }
+        self.symbol('}')?;
+
+        Ok(Chip {
+            name,
+            position,
+            inputs,
+            outputs,
+            parts,
+        })
+    }
+
+    fn pins(&mut self) -> Result<Vec<(String, Position)>, String> {
+        let mut pins = vec![self.identifier()?];
+        while self.accept(',') {
+            pins.push(self.identifier()?);
+        }
+        self.symbol(';')?;
+        Ok(pins)
+    }
+
+    fn part(&mut self) -> Result<PartSource, String> {
+        let (name, position) = self.identifier()?;
+        self.symbol('(')?;
+
+        let mut connections = Vec::new();
+        loop {
+            let (pin, pin_position) = self.identifier()?;
+            self.symbol('=')?;
+            let wire = self.identifier()?.0;
+            connections.push((pin, pin_position, wire));
+            if !self.accept(',') {
+                break;
+            }
+        }
+        self.symbol(')')?;
+        self.symbol(';')?;
+
+        Ok(PartSource {
+            name,
+            position,
+            connections,
+        })
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<Position, String> {
+        let (word, position) = self.identifier()?;
+        if word != keyword {
+            return Err(error(
+                position,
+                &format!("Expected {}, found {:?}", keyword, word),
+            ));
+        }
+        Ok(position)
+    }
+
+    fn identifier(&mut self) -> Result<(String, Position), String> {
+        let (token, position) = self.next();
+        match token {
+            Token::Identifier(word) => Ok((word, position)),
+            token => Err(error(
+                position,
+                &format!("Expected a name, found {}", describe(&token)),
+            )),
+        }
+    }
+
+    fn symbol(&mut self, symbol: char) -> Result<(), String> {
+        let (token, position) = self.next();
+        if token != Token::Symbol(symbol) {
+            return Err(error(
+                position,
+                &format!("Expected '{}', found {}", symbol, describe(&token)),
+            ));
+        }
+        Ok(())
+    }
+
+    fn accept(&mut self, symbol: char) -> bool {
+        if self.peek() == &Token::Symbol(symbol) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].0
+    }
+
+    fn next(&mut self) -> (Token, Position) {
+        let token = self.tokens[self.position].clone();
+        if token.0 != Token::End {
+            self.position += 1;
+        }
+        token
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Identifier(word) => format!("{:?}", word),
+        Token::Symbol(symbol) => format!("'{}'", symbol),
+        Token::End => "end of file".to_string(),
+    }
+}
+
+/// Splits the source into names and symbols, skipping whitespace and `//` and `/* */`
+/// comments. The token list always ends with [`Token::End`].
+fn tokenize(text: &str) -> Result<Vec<(Token, Position)>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut position = Position { line: 1, column: 1 };
+    let mut i = 0;
+
+    let advance = |i: &mut usize, position: &mut Position| {
+        if chars[*i] == '\n' {
+            position.line += 1;
+            position.column = 1;
+        } else {
+            position.column += 1;
+        }
+        *i += 1;
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = position;
+
+        if c.is_whitespace() {
+            advance(&mut i, &mut position);
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                advance(&mut i, &mut position);
+            }
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            advance(&mut i, &mut position);
+            advance(&mut i, &mut position);
+            loop {
+                if i + 1 >= chars.len() {
+                    return Err(error(start, "Unterminated comment"));
+                }
+                if chars[i] == '*' && chars[i + 1] == '/' {
+                    advance(&mut i, &mut position);
+                    advance(&mut i, &mut position);
+                    break;
+                }
+                advance(&mut i, &mut position);
+            }
+        } else if c.is_alphanumeric() || c == '_' {
+            let mut word = String::new();
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                word.push(chars[i]);
+                advance(&mut i, &mut position);
+            }
+            tokens.push((Token::Identifier(word), start));
+        } else if "{}();,=:".contains(c) {
+            tokens.push((Token::Symbol(c), start));
+            advance(&mut i, &mut position);
+        } else {
+            return Err(error(start, &format!("Unexpected character {:?}", c)));
+        }
+    }
+
+    tokens.push((Token::End, position));
+    Ok(tokens)
+}
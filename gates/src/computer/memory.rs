@@ -1,3 +1,6 @@
+use super::registry::GateRegistry;
+use super::routing::{Demux, Mux16};
+
 /// 4-bit Memory Cell that stores a single 4-bit value
 #[derive(Debug, Clone, Copy)]
 pub struct MemoryCell {
@@ -38,6 +41,8 @@ impl Default for MemoryCell {
 #[derive(Debug)]
 pub struct Memory {
     cells: [MemoryCell; 16],
+    // Gate-level addressing: one read multiplexer per data bit, one write-enable demultiplexer
+    routing: Option<([Mux16; 4], Demux)>,
 }
 
 impl Memory {
@@ -45,19 +50,56 @@ impl Memory {
     pub fn new() -> Self {
         Memory {
             cells: [MemoryCell::new(); 16],
+            routing: None,
         }
     }
 
+    /// Address cells through neural multiplexers and a demultiplexer instead of array indexing
+    pub fn with_routing(mut self, registry: &GateRegistry) -> Self {
+        self.routing = Some((
+            std::array::from_fn(|_| Mux16::with_registry(registry)),
+            Demux::with_registry(registry),
+        ));
+        self
+    }
+
+    /// Whether reads and writes are routed through neural components
+    pub fn is_routed(&self) -> bool {
+        self.routing.is_some()
+    }
+
     /// Read from memory at given address (4-bit address: 0-15)
     pub fn read(&self, address: u8) -> u8 {
-        let addr = (address & 0x0F) as usize; // Ensure 4-bit address
-        self.cells[addr].read()
+        let Some((muxes, _)) = &self.routing else {
+            let addr = (address & 0x0F) as usize; // Ensure 4-bit address
+            return self.cells[addr].read();
+        };
+
+        let select = bits(address);
+        (0..4)
+            .filter(|&bit| {
+                let column = std::array::from_fn(|cell| self.cells[cell].read() & (1 << bit) != 0);
+                muxes[bit].compute(&column, select)
+            })
+            .map(|bit| 1 << bit)
+            .sum()
     }
 
     /// Write to memory at given address
     pub fn write(&mut self, address: u8, value: u8) {
-        let addr = (address & 0x0F) as usize; // Ensure 4-bit address
-        self.cells[addr].write(value);
+        let Some((_, demux)) = &self.routing else {
+            let addr = (address & 0x0F) as usize; // Ensure 4-bit address
+            self.cells[addr].write(value);
+            return;
+        };
+
+        // Only the cell whose write-enable line is high stores the value
+        let enabled = demux.compute(true, bits(address));
+        for (cell, enabled) in self.cells.iter_mut().zip(enabled) {
+            if enabled {
+                cell.write(value);
+            }
+        }
     }
 
     /// Load program data into memory starting at address 0
@@ -117,6 +159,11 @@ impl Memory {
     }
 }
 
+/// The low 4 bits of a value, LSB first
+fn bits(value: u8) -> [bool; 4] {
+    std::array::from_fn(|bit| value & (1 << bit) != 0)
+}
+
 impl Default for Memory {
     fn default() -> Self {
         Self::new()
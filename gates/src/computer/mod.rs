@@ -1,9 +1,16 @@
 pub mod alu;
 pub mod assembler;
+pub mod circuit;
+pub mod compiled;
 pub mod cpu;
 pub mod full_adder;
 pub mod gates;
 pub mod half_adder;
+pub mod hdl;
 pub mod interpreter;
 pub mod memory;
 pub mod registers;
+pub mod registry;
+pub mod routing;
+pub mod sequential;
+pub mod truth_table;
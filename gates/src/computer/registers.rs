@@ -1,3 +1,6 @@
+use super::registry::GateRegistry;
+use super::sequential::NeuralRegister;
+
 /// 4-bit Register for CPU
 #[derive(Debug, Clone, Copy)]
 pub struct Register4Bit {
@@ -58,17 +61,108 @@ impl Default for Register4Bit {
     }
 }
 
+/// How a register stores its bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisterMode {
+    /// A plain `u8` field
+    Plain,
+    /// Clocked neural D flip-flops
+    Neural,
+}
+
+/// A CPU register backed by either storage mode
+#[derive(Debug, Clone)]
+pub enum Register {
+    Plain(Register4Bit),
+    Neural(Box<NeuralRegister>),
+}
+
+impl Register {
+    /// Create a register initialized to 0 in the given mode
+    pub fn with_mode(mode: RegisterMode, registry: &GateRegistry) -> Self {
+        match mode {
+            RegisterMode::Plain => Register::Plain(Register4Bit::new()),
+            RegisterMode::Neural => NeuralRegister::with_registry(registry).into(),
+        }
+    }
+
+    /// The storage mode of this register
+    pub fn mode(&self) -> RegisterMode {
+        match self {
+            Register::Plain(_) => RegisterMode::Plain,
+            Register::Neural(_) => RegisterMode::Neural,
+        }
+    }
+
+    /// Read the register value
+    pub fn read(&self) -> u8 {
+        match self {
+            Register::Plain(register) => register.read(),
+            Register::Neural(register) => register.read(),
+        }
+    }
+
+    /// Write a new value to the register (4-bit masked)
+    pub fn write(&mut self, value: u8) {
+        match self {
+            Register::Plain(register) => register.write(value),
+            Register::Neural(register) => register.write(value),
+        }
+    }
+
+    /// Increment the register (with wrap-around at 16)
+    pub fn increment(&mut self) {
+        match self {
+            Register::Plain(register) => register.increment(),
+            Register::Neural(register) => register.increment(),
+        }
+    }
+
+    /// Decrement the register (with wrap-around at 0)
+    pub fn decrement(&mut self) {
+        match self {
+            Register::Plain(register) => register.decrement(),
+            Register::Neural(register) => register.decrement(),
+        }
+    }
+
+    /// Clear the register (set to 0)
+    pub fn clear(&mut self) {
+        match self {
+            Register::Plain(register) => register.clear(),
+            Register::Neural(register) => register.clear(),
+        }
+    }
+
+    /// Check if register is zero
+    pub fn is_zero(&self) -> bool {
+        self.read() == 0
+    }
+}
+
+impl From<Register4Bit> for Register {
+    fn from(register: Register4Bit) -> Self {
+        Register::Plain(register)
+    }
+}
+
+impl From<NeuralRegister> for Register {
+    fn from(register: NeuralRegister) -> Self {
+        Register::Neural(Box::new(register))
+    }
+}
+
 /// CPU Register set for 4-bit computer
 #[derive(Debug)]
 pub struct CPURegisters {
     /// Accumulator - main working register for arithmetic/logic operations
-    pub accumulator: Register4Bit,
+    pub accumulator: Register,
 
     /// Program Counter - points to next instruction to execute
-    pub program_counter: Register4Bit,
+    pub program_counter: Register,
 
     /// Instruction Register - holds current instruction being executed
-    pub instruction_register: Register4Bit,
+    pub instruction_register: Register,
 
     /// Status flags
     pub zero_flag: bool, // Set when last operation resulted in zero
@@ -79,14 +173,35 @@ impl CPURegisters {
     /// Create new register set with all registers initialized to 0
     pub fn new() -> Self {
         CPURegisters {
-            accumulator: Register4Bit::new(),
-            program_counter: Register4Bit::new(),
-            instruction_register: Register4Bit::new(),
+            accumulator: Register4Bit::new().into(),
+            program_counter: Register4Bit::new().into(),
+            instruction_register: Register4Bit::new().into(),
+            zero_flag: false,
+            carry_flag: false,
+        }
+    }
+
+    /// Create a register set whose registers are neural flip-flops built from the registry's gates
+    pub fn neural(registry: &GateRegistry) -> Self {
+        Self::with_mode(RegisterMode::Neural, registry)
+    }
+
+    /// Create a register set with every register in the given mode
+    pub fn with_mode(mode: RegisterMode, registry: &GateRegistry) -> Self {
+        CPURegisters {
+            accumulator: Register::with_mode(mode, registry),
+            program_counter: Register::with_mode(mode, registry),
+            instruction_register: Register::with_mode(mode, registry),
             zero_flag: false,
             carry_flag: false,
         }
     }
 
+    /// The storage mode of the accumulator, program counter and instruction register
+    pub fn mode(&self) -> RegisterMode {
+        self.accumulator.mode()
+    }
+
     /// Reset all registers and flags to initial state
     pub fn reset(&mut self) {
         self.accumulator.clear();
@@ -0,0 +1,294 @@
+//! Trained Gate Registry
+//!
+//! Every component of the neural computer used to train its own gates, so building an ALU
+//! trained dozens of identical networks. A [`GateRegistry`] trains each gate type (and input
+//! count) once, checks it against its truth table and hands out shared references to it.
+//!
+//! The trained weights can be saved to a text file and loaded back, so later runs skip
+//! training entirely:
+//!
+//! ```text
+//! # Trained logic gates
+//! gate AND 2
+//! layer
+//! matrix
+//! row 0.8 0.8
+//! ...
+//! ```
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::computer::gates::GateType;
+//! # use aspirina_gates::computer::half_adder::HalfAdder;
+//! # use aspirina_gates::computer::registry::GateRegistry;
+//! # use std::rc::Rc;
+//! let registry = GateRegistry::new();
+//!
+//! let first = HalfAdder::with_registry(&registry);
+//! let second = HalfAdder::with_registry(&registry);
+//! assert_eq!(registry.len(), 2); // XOR and AND, trained once for both adders
+//!
+//! let xor = registry.get(GateType::XOR, 2);
+//! assert!(Rc::ptr_eq(&xor, &registry.get(GateType::XOR, 2)));
+//!
+//! let restored = GateRegistry::parse(&registry.to_text()).unwrap();
+//! assert_eq!(restored.len(), 2);
+//! ```
+
+use super::gates::{parameter_shapes, GateType, LogicGate};
+use aspirina_core::matrix::Matrix;
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Trains each gate once and shares it
+#[derive(Debug)]
+pub struct GateRegistry {
+    epochs: usize,
+    margin: f64,
+    attempts: usize,
+    gates: RefCell<Vec<(GateType, usize, Rc<LogicGate>)>>,
+}
+
+impl GateRegistry {
+    /// Creates an empty registry that trains gates for 10,000 epochs, retrying up to three
+    /// times until every truth table row is on the correct side of 0.5
+    pub fn new() -> Self {
+        GateRegistry {
+            epochs: 10_000,
+            margin: 0.0,
+            attempts: 3,
+            gates: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Sets the number of training epochs for gates not trained yet
+    pub fn with_epochs(mut self, epochs: usize) -> Self {
+        self.epochs = epochs;
+        self
+    }
+
+    /// Sets the distance from 0.5 every row must exceed for a gate to be accepted
+    pub fn with_margin(mut self, margin: f64) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Sets how many seeds to try before giving up on a gate
+    pub fn with_attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// The shared, trained gate of this type and input count
+    ///
+    /// The first request trains and verifies the gate with [`LogicGate::train_verified`];
+    /// later ones return the same gate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gate type does not support `inputs` inputs, or if no attempt reaches
+    /// the margin.
+    pub fn get(&self, gate_type: GateType, inputs: usize) -> Rc<LogicGate> {
+        if let Some(gate) = self.find(&gate_type, inputs) {
+            return gate;
+        }
+
+        let gate = LogicGate::train_verified(
+            gate_type.clone(),
+            inputs,
+            self.epochs,
+            self.margin,
+            self.attempts,
+        )
+        .unwrap_or_else(|error| panic!("{}", error));
+
+        let gate = Rc::new(gate);
+        self.gates
+            .borrow_mut()
+            .push((gate_type, inputs, Rc::clone(&gate)));
+        gate
+    }
+
+    /// An independent copy of the trained gate, e.g. to train it further
+    pub fn gate(&self, gate_type: GateType, inputs: usize) -> LogicGate {
+        self.get(gate_type, inputs).as_ref().clone()
+    }
+
+    /// Whether the gate has been trained or loaded already
+    pub fn contains(&self, gate_type: &GateType, inputs: usize) -> bool {
+        self.find(gate_type, inputs).is_some()
+    }
+
+    /// Number of trained gates
+    pub fn len(&self) -> usize {
+        self.gates.borrow().len()
+    }
+
+    /// Whether no gate has been trained or loaded yet
+    pub fn is_empty(&self) -> bool {
+        self.gates.borrow().is_empty()
+    }
+
+    /// The registry file contents: every gate with its weights
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# Trained logic gates\n");
+
+        for (gate_type, inputs, gate) in self.gates.borrow().iter() {
+            text.push_str(&format!("gate {} {}\n", gate_type, inputs));
+            for layer in gate.parameters() {
+                text.push_str("layer\n");
+                for matrix in layer {
+                    text.push_str("matrix\n");
+                    for row in &matrix.data {
+                        let values: Vec<String> = row.iter().map(f64::to_string).collect();
+                        text.push_str(&format!("row {}\n", values.join(" ")));
+                    }
+                }
+            }
+        }
+
+        text
+    }
+
+    /// Parses registry file contents, checking every gate against its truth table
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let registry = GateRegistry::new();
+        let mut current: Option<(usize, GateType, usize, Vec<Vec<Matrix>>)> = None;
+
+        for (line_idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |message: &str| format!("Line {}: {}", line_idx + 1, message);
+            let mut parts = line.split_whitespace();
+            match parts.next() {
+                Some("gate") => {
+                    if let Some(gate) = current.take() {
+                        registry.insert(gate)?;
+                    }
+
+                    let gate_type = parts
+                        .next()
+                        .ok_or_else(|| error("expected a gate type"))
+                        .and_then(|name| GateType::parse(name).map_err(|e| error(&e)))?;
+                    let inputs = parts
+                        .next()
+                        .and_then(|inputs| inputs.parse::<usize>().ok())
+                        .ok_or_else(|| error("expected an input count"))?;
+                    if !gate_type.supports(inputs) {
+                        return Err(error(&format!(
+                            "{} gate cannot take {} input(s)",
+                            gate_type, inputs
+                        )));
+                    }
+                    if registry.contains(&gate_type, inputs) {
+                        return Err(error("duplicate gate"));
+                    }
+
+                    current = Some((line_idx, gate_type, inputs, Vec::new()));
+                }
+                Some("layer") => match current.as_mut() {
+                    Some((_, _, _, layers)) => layers.push(Vec::new()),
+                    None => return Err(error("layer outside a gate")),
+                },
+                Some("matrix") => match current.as_mut().and_then(|gate| gate.3.last_mut()) {
+                    Some(layer) => layer.push(Matrix::new(Vec::new())),
+                    None => return Err(error("matrix outside a layer")),
+                },
+                Some("row") => {
+                    let row = parts
+                        .map(|value| value.parse::<f64>())
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|_| error("invalid number"))?;
+                    match current
+                        .as_mut()
+                        .and_then(|gate| gate.3.last_mut())
+                        .and_then(|layer| layer.last_mut())
+                    {
+                        Some(matrix) => matrix.data.push(row),
+                        None => return Err(error("row outside a matrix")),
+                    }
+                }
+                _ => return Err(error(&format!("unexpected {:?}", line))),
+            }
+        }
+
+        if let Some(gate) = current {
+            registry.insert(gate)?;
+        }
+
+        Ok(registry)
+    }
+
+    /// Reads and parses the registry file at `path`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|error| format!("Cannot read {}: {}", path.display(), error))?;
+
+        Self::parse(&text)
+    }
+
+    /// Loads the registry file at `path` if it exists, otherwise starts empty
+    pub fn load_or_new(path: impl AsRef<Path>) -> Result<Self, String> {
+        if path.as_ref().exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::new())
+        }
+    }
+
+    /// Writes the registry file to `path`
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        fs::write(path, self.to_text())
+            .map_err(|error| format!("Cannot write {}: {}", path.display(), error))
+    }
+
+    fn find(&self, gate_type: &GateType, inputs: usize) -> Option<Rc<LogicGate>> {
+        self.gates
+            .borrow()
+            .iter()
+            .find(|(t, n, _)| t == gate_type && *n == inputs)
+            .map(|(_, _, gate)| Rc::clone(gate))
+    }
+
+    fn insert(
+        &self,
+        (line_idx, gate_type, inputs, parameters): (usize, GateType, usize, Vec<Vec<Matrix>>),
+    ) -> Result<(), String> {
+        let gate = LogicGate::new(gate_type.clone(), inputs);
+        if parameter_shapes(&parameters) != parameter_shapes(&gate.parameters()) {
+            return Err(format!(
+                "Line {}: weights do not match the {} gate's network",
+                line_idx + 1,
+                gate_type
+            ));
+        }
+
+        gate.set_parameters(parameters);
+        if !gate.is_exact() {
+            return Err(format!(
+                "Line {}: {} gate does not reproduce its truth table",
+                line_idx + 1,
+                gate_type
+            ));
+        }
+
+        self.gates
+            .borrow_mut()
+            .push((gate_type, inputs, Rc::new(gate)));
+        Ok(())
+    }
+}
+
+impl Default for GateRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
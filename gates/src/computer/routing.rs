@@ -0,0 +1,313 @@
+//! Routing Components
+//!
+//! Selecting one of many signals, and sending one signal to one of many places, are the jobs
+//! of these components, all composed from 2-input neural AND/OR gates and NOT gates:
+//!
+//! - [`Mux2`], [`Mux4`], [`Mux16`]: pick one input by select bits
+//! - [`Decoder`]: 4 select bits to 16 one-hot lines
+//! - [`Demux`]: one input routed to one of 16 outputs
+//! - [`PriorityEncoder`]: the index of the highest active line of 16
+//!
+//! Select bits and indices are LSB first, matching the ALU. [`Memory`](super::memory::Memory)
+//! can address its cells through a [`Mux16`] and a [`Demux`], and the CPU can select
+//! instructions by the one-hot lines of a [`Decoder`].
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::computer::routing::{Decoder, PriorityEncoder};
+//! # use aspirina_gates::computer::registry::GateRegistry;
+//! let registry = GateRegistry::new();
+//! let decoder = Decoder::with_registry(&registry);
+//! let encoder = PriorityEncoder::with_registry(&registry);
+//!
+//! let lines = decoder.compute([true, false, true, false]); // 5
+//! assert!(lines[5]);
+//! assert_eq!(encoder.compute(&lines), Some([true, false, true, false]));
+//! ```
+
+use super::compiled::Gate;
+use super::gates::GateType;
+use super::registry::GateRegistry;
+
+/// 2-to-1 multiplexer: OR(AND(a, NOT s), AND(b, s))
+#[derive(Debug, Clone)]
+pub struct Mux2 {
+    not_gate: Gate,
+    and_gates: [Gate; 2],
+    or_gate: Gate,
+}
+
+/// 4-to-1 multiplexer: a tree of three 2-to-1 multiplexers
+#[derive(Debug, Clone)]
+pub struct Mux4 {
+    muxes: [Mux2; 3], // two on the low select bit, then one on the high bit
+}
+
+/// 16-to-1 multiplexer: a tree of five 4-to-1 multiplexers
+#[derive(Debug, Clone)]
+pub struct Mux16 {
+    muxes: [Mux4; 5], // four on the low select bits, then one on the high bits
+}
+
+/// 4-to-16 decoder
+///
+/// Each pair of select bits is predecoded into four lines, and every output ANDs one line
+/// from each pair, so only 2-input gates are needed.
+#[derive(Debug, Clone)]
+pub struct Decoder {
+    not_gates: [Gate; 4],
+    predecode_gates: [Gate; 8], // low pair, then high pair
+    and_gates: [Gate; 16],
+}
+
+/// 1-to-16 demultiplexer: a decoder whose lines are ANDed with the input
+#[derive(Debug, Clone)]
+pub struct Demux {
+    decoder: Decoder,
+    and_gates: [Gate; 16],
+}
+
+/// 4-to-2 priority encoder with a valid output, the building block of [`PriorityEncoder`]
+#[derive(Debug, Clone)]
+struct PriorityEncoder4 {
+    not_gate: Gate,
+    and_gate: Gate,
+    or_gates: [Gate; 4], // high bit, low bit, then two for valid
+}
+
+/// 16-to-4 priority encoder: the index of the highest active input
+///
+/// Four 4-to-2 encoders find the highest line within each group, a fifth finds the highest
+/// active group, and two 4-to-1 multiplexers pick that group's low bits.
+#[derive(Debug, Clone)]
+pub struct PriorityEncoder {
+    groups: [PriorityEncoder4; 4],
+    top: PriorityEncoder4,
+    muxes: [Mux4; 2],
+}
+
+impl Mux2 {
+    /// Create a multiplexer with trained gates
+    pub fn new() -> Self {
+        Self::with_registry(&GateRegistry::new())
+    }
+
+    /// Create a multiplexer sharing the registry's trained gates
+    pub fn with_registry(registry: &GateRegistry) -> Self {
+        Mux2 {
+            not_gate: registry.get(GateType::NOT, 1).into(),
+            and_gates: std::array::from_fn(|_| registry.get(GateType::AND, 2).into()),
+            or_gate: registry.get(GateType::OR, 2).into(),
+        }
+    }
+
+    /// `a` when `select` is low, `b` when it is high
+    pub fn compute(&self, a: bool, b: bool, select: bool) -> bool {
+        let keep_a = self.and_gates[0].compute(&[a, self.not_gate.compute(&[select])]);
+        let keep_b = self.and_gates[1].compute(&[b, select]);
+
+        self.or_gate.compute(&[keep_a, keep_b])
+    }
+}
+
+impl Mux4 {
+    /// Create a multiplexer with trained gates
+    pub fn new() -> Self {
+        Self::with_registry(&GateRegistry::new())
+    }
+
+    /// Create a multiplexer sharing the registry's trained gates
+    pub fn with_registry(registry: &GateRegistry) -> Self {
+        Mux4 {
+            muxes: std::array::from_fn(|_| Mux2::with_registry(registry)),
+        }
+    }
+
+    /// The input at index `select`
+    pub fn compute(&self, inputs: &[bool; 4], select: [bool; 2]) -> bool {
+        let low = self.muxes[0].compute(inputs[0], inputs[1], select[0]);
+        let high = self.muxes[1].compute(inputs[2], inputs[3], select[0]);
+
+        self.muxes[2].compute(low, high, select[1])
+    }
+}
+
+impl Mux16 {
+    /// Create a multiplexer with trained gates
+    pub fn new() -> Self {
+        Self::with_registry(&GateRegistry::new())
+    }
+
+    /// Create a multiplexer sharing the registry's trained gates
+    pub fn with_registry(registry: &GateRegistry) -> Self {
+        Mux16 {
+            muxes: std::array::from_fn(|_| Mux4::with_registry(registry)),
+        }
+    }
+
+    /// The input at index `select`
+    pub fn compute(&self, inputs: &[bool; 16], select: [bool; 4]) -> bool {
+        let low = [select[0], select[1]];
+        let groups: [bool; 4] = std::array::from_fn(|group| {
+            let inputs = std::array::from_fn(|bit| inputs[group * 4 + bit]);
+            self.muxes[group].compute(&inputs, low)
+        });
+
+        self.muxes[4].compute(&groups, [select[2], select[3]])
+    }
+}
+
+impl Decoder {
+    /// Create a decoder with trained gates
+    pub fn new() -> Self {
+        Self::with_registry(&GateRegistry::new())
+    }
+
+    /// Create a decoder sharing the registry's trained gates
+    pub fn with_registry(registry: &GateRegistry) -> Self {
+        Decoder {
+            not_gates: std::array::from_fn(|_| registry.get(GateType::NOT, 1).into()),
+            predecode_gates: std::array::from_fn(|_| registry.get(GateType::AND, 2).into()),
+            and_gates: std::array::from_fn(|_| registry.get(GateType::AND, 2).into()),
+        }
+    }
+
+    /// One-hot lines with only the line at index `select` high
+    pub fn compute(&self, select: [bool; 4]) -> [bool; 16] {
+        let inverted: [bool; 4] =
+            std::array::from_fn(|bit| self.not_gates[bit].compute(&[select[bit]]));
+        let literal = |bit: usize, high: bool| {
+            if high {
+                select[bit]
+            } else {
+                inverted[bit]
+            }
+        };
+
+        // Line k of a pair is high when the pair's bits spell k
+        let predecoded: [bool; 8] = std::array::from_fn(|index| {
+            let (pair, line) = (index / 4, index % 4);
+            self.predecode_gates[index].compute(&[
+                literal(pair * 2, line & 1 != 0),
+                literal(pair * 2 + 1, line & 2 != 0),
+            ])
+        });
+
+        std::array::from_fn(|line| {
+            self.and_gates[line].compute(&[predecoded[line % 4], predecoded[4 + line / 4]])
+        })
+    }
+}
+
+impl Demux {
+    /// Create a demultiplexer with trained gates
+    pub fn new() -> Self {
+        Self::with_registry(&GateRegistry::new())
+    }
+
+    /// Create a demultiplexer sharing the registry's trained gates
+    pub fn with_registry(registry: &GateRegistry) -> Self {
+        Demux {
+            decoder: Decoder::with_registry(registry),
+            and_gates: std::array::from_fn(|_| registry.get(GateType::AND, 2).into()),
+        }
+    }
+
+    /// `input` on the output at index `select`, low everywhere else
+    pub fn compute(&self, input: bool, select: [bool; 4]) -> [bool; 16] {
+        let lines = self.decoder.compute(select);
+
+        std::array::from_fn(|output| self.and_gates[output].compute(&[input, lines[output]]))
+    }
+}
+
+impl PriorityEncoder4 {
+    fn with_registry(registry: &GateRegistry) -> Self {
+        PriorityEncoder4 {
+            not_gate: registry.get(GateType::NOT, 1).into(),
+            and_gate: registry.get(GateType::AND, 2).into(),
+            or_gates: std::array::from_fn(|_| registry.get(GateType::OR, 2).into()),
+        }
+    }
+
+    /// The index bits of the highest active input, and whether any input is active
+    ///
+    /// high = d3 + d2, low = d3 + d1 * !d2, valid = (d0 + d1) + (d2 + d3)
+    fn compute(&self, inputs: &[bool; 4]) -> ([bool; 2], bool) {
+        let high = self.or_gates[0].compute(&[inputs[3], inputs[2]]);
+        let only_one = self
+            .and_gate
+            .compute(&[inputs[1], self.not_gate.compute(&[inputs[2]])]);
+        let low = self.or_gates[1].compute(&[inputs[3], only_one]);
+        let low_pair = self.or_gates[2].compute(&[inputs[0], inputs[1]]);
+        let valid = self.or_gates[3].compute(&[low_pair, high]);
+
+        ([low, high], valid)
+    }
+}
+
+impl PriorityEncoder {
+    /// Create an encoder with trained gates
+    pub fn new() -> Self {
+        Self::with_registry(&GateRegistry::new())
+    }
+
+    /// Create an encoder sharing the registry's trained gates
+    pub fn with_registry(registry: &GateRegistry) -> Self {
+        PriorityEncoder {
+            groups: std::array::from_fn(|_| PriorityEncoder4::with_registry(registry)),
+            top: PriorityEncoder4::with_registry(registry),
+            muxes: std::array::from_fn(|_| Mux4::with_registry(registry)),
+        }
+    }
+
+    /// The index bits of the highest active input, or `None` if no input is active
+    pub fn compute(&self, inputs: &[bool; 16]) -> Option<[bool; 4]> {
+        let groups: [([bool; 2], bool); 4] = std::array::from_fn(|group| {
+            self.groups[group].compute(&std::array::from_fn(|bit| inputs[group * 4 + bit]))
+        });
+        let (group, valid) = self.top.compute(&groups.map(|(_, valid)| valid));
+
+        let low = self.muxes[0].compute(&groups.map(|(bits, _)| bits[0]), group);
+        let high = self.muxes[1].compute(&groups.map(|(bits, _)| bits[1]), group);
+
+        valid.then_some([low, high, group[0], group[1]])
+    }
+}
+
+impl Default for Mux2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for Mux4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for Mux16 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for Demux {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for PriorityEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,273 @@
+//! Sequential Logic
+//!
+//! Combinational circuits forget everything between calls. Feeding a gate's output back into
+//! another gate's input gives it memory:
+//!
+//! - [`SRLatch`]: two cross-coupled NOR gates, set by `S` and cleared by `R`
+//! - [`DLatch`]: an SR latch gated by `enable`, following `D` while enabled
+//! - [`DFlipFlop`]: two D latches in a master-slave pair, storing `D` on a rising clock edge
+//! - [`NeuralRegister`]: four flip-flops sharing a clock, a drop-in for the CPU registers
+//!
+//! Every gate is a trained neural [`LogicGate`](super::gates::LogicGate). Feedback loops are
+//! simulated one gate at a time, in a fixed order, until no output changes, and the clock is
+//! driven explicitly with [`DFlipFlop::clock`] or a full low-high [`DFlipFlop::tick`].
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::computer::sequential::DFlipFlop;
+//! let mut flip_flop = DFlipFlop::new();
+//!
+//! flip_flop.clock(false, true); // clock low: the master latch follows D
+//! assert!(!flip_flop.q()); // the output still holds its old value
+//! flip_flop.clock(true, false); // rising edge: the captured 1 reaches Q
+//! assert!(flip_flop.q());
+//! ```
+
+use super::compiled::Gate;
+use super::gates::GateType;
+use super::registry::GateRegistry;
+
+/// Gate evaluations allowed before a feedback loop must have settled
+const MAX_SETTLE_STEPS: usize = 8;
+
+/// A set-reset latch made of two cross-coupled NOR gates
+///
+/// Q = NOR(R, Q'), Q' = NOR(S, Q). Setting both S and R drives both outputs low; releasing
+/// them together leaves the latch set, as the Q gate is evaluated first.
+#[derive(Debug, Clone)]
+pub struct SRLatch {
+    nor_gates: [Gate; 2], // Q, then Q'
+    q: bool,
+    q_bar: bool,
+}
+
+/// A D latch: an SR latch set to `D` while `enable` is high
+///
+/// S = AND(D, Enable), R = AND(NOT D, Enable)
+#[derive(Debug, Clone)]
+pub struct DLatch {
+    not_gate: Gate,
+    and_gates: [Gate; 2], // S, then R
+    latch: SRLatch,
+}
+
+/// A rising-edge D flip-flop made of a master and a slave D latch
+///
+/// The master follows D while the clock is low; the slave copies the master while the clock
+/// is high, so Q only changes as the clock rises.
+#[derive(Debug, Clone)]
+pub struct DFlipFlop {
+    not_gate: Gate, // inverts the clock for the master
+    master: DLatch,
+    slave: DLatch,
+    clock: bool,
+}
+
+/// A 4-bit register stored in neural D flip-flops, with the API of
+/// [`Register4Bit`](super::registers::Register4Bit)
+#[derive(Debug, Clone)]
+pub struct NeuralRegister {
+    flip_flops: [DFlipFlop; 4], // LSB first
+    ticks: usize,
+}
+
+impl SRLatch {
+    /// Create a reset latch with trained NOR gates
+    pub fn new() -> Self {
+        Self::with_registry(&GateRegistry::new())
+    }
+
+    /// Create a reset latch sharing the registry's trained gates
+    pub fn with_registry(registry: &GateRegistry) -> Self {
+        SRLatch {
+            nor_gates: std::array::from_fn(|_| registry.get(GateType::NOR, 2).into()),
+            q: false,
+            q_bar: true,
+        }
+    }
+
+    /// Applies S and R and lets the feedback loop settle, returning Q
+    ///
+    /// # Panics
+    ///
+    /// Panics if the outputs keep changing, which trained NOR gates never do.
+    pub fn update(&mut self, set: bool, reset: bool) -> bool {
+        for _ in 0..MAX_SETTLE_STEPS {
+            let q = self.nor_gates[0].compute(&[reset, self.q_bar]);
+            let q_bar = self.nor_gates[1].compute(&[set, q]);
+            let settled = q == self.q && q_bar == self.q_bar;
+
+            self.q = q;
+            self.q_bar = q_bar;
+            if settled {
+                return self.q;
+            }
+        }
+
+        panic!("SR latch did not settle");
+    }
+
+    /// The stored bit
+    pub fn q(&self) -> bool {
+        self.q
+    }
+
+    /// The complement output, equal to `!q()` unless S and R are both high
+    pub fn q_bar(&self) -> bool {
+        self.q_bar
+    }
+}
+
+impl DLatch {
+    /// Create a D latch with trained gates
+    pub fn new() -> Self {
+        Self::with_registry(&GateRegistry::new())
+    }
+
+    /// Create a D latch sharing the registry's trained gates
+    pub fn with_registry(registry: &GateRegistry) -> Self {
+        DLatch {
+            not_gate: registry.get(GateType::NOT, 1).into(),
+            and_gates: std::array::from_fn(|_| registry.get(GateType::AND, 2).into()),
+            latch: SRLatch::with_registry(registry),
+        }
+    }
+
+    /// Follows `d` while `enable` is high and holds otherwise, returning Q
+    pub fn update(&mut self, d: bool, enable: bool) -> bool {
+        let set = self.and_gates[0].compute(&[d, enable]);
+        let reset = self.and_gates[1].compute(&[self.not_gate.compute(&[d]), enable]);
+
+        self.latch.update(set, reset)
+    }
+
+    /// The stored bit
+    pub fn q(&self) -> bool {
+        self.latch.q()
+    }
+}
+
+impl DFlipFlop {
+    /// Create a flip-flop holding 0, with the clock low
+    pub fn new() -> Self {
+        Self::with_registry(&GateRegistry::new())
+    }
+
+    /// Create a flip-flop sharing the registry's trained gates
+    pub fn with_registry(registry: &GateRegistry) -> Self {
+        DFlipFlop {
+            not_gate: registry.get(GateType::NOT, 1).into(),
+            master: DLatch::with_registry(registry),
+            slave: DLatch::with_registry(registry),
+            clock: false,
+        }
+    }
+
+    /// Drives the clock to `level` with `d` at the input, returning Q
+    pub fn clock(&mut self, level: bool, d: bool) -> bool {
+        let master_enable = self.not_gate.compute(&[level]);
+        let master = self.master.update(d, master_enable);
+        self.clock = level;
+
+        self.slave.update(master, level)
+    }
+
+    /// One full clock cycle, low then high, storing `d`
+    pub fn tick(&mut self, d: bool) -> bool {
+        self.clock(false, d);
+        self.clock(true, d)
+    }
+
+    /// The stored bit
+    pub fn q(&self) -> bool {
+        self.slave.q()
+    }
+
+    /// The current clock level
+    pub fn clock_level(&self) -> bool {
+        self.clock
+    }
+}
+
+impl NeuralRegister {
+    /// Create a register initialized to 0
+    pub fn new() -> Self {
+        Self::with_registry(&GateRegistry::new())
+    }
+
+    /// Create a register initialized to 0, sharing the registry's trained gates
+    pub fn with_registry(registry: &GateRegistry) -> Self {
+        NeuralRegister {
+            flip_flops: std::array::from_fn(|_| DFlipFlop::with_registry(registry)),
+            ticks: 0,
+        }
+    }
+
+    /// Read the register value from the flip-flop outputs
+    pub fn read(&self) -> u8 {
+        self.flip_flops
+            .iter()
+            .enumerate()
+            .filter(|(_, flip_flop)| flip_flop.q())
+            .map(|(bit, _)| 1 << bit)
+            .sum()
+    }
+
+    /// Write a new value (4-bit masked), clocking every flip-flop once
+    pub fn write(&mut self, value: u8) {
+        for (bit, flip_flop) in self.flip_flops.iter_mut().enumerate() {
+            flip_flop.tick(value & (1 << bit) != 0);
+        }
+        self.ticks += 1;
+    }
+
+    /// Increment the register (with wrap-around at 16)
+    pub fn increment(&mut self) {
+        self.write((self.read() + 1) & 0x0F);
+    }
+
+    /// Decrement the register (with wrap-around at 0)
+    pub fn decrement(&mut self) {
+        self.write(self.read().wrapping_sub(1) & 0x0F);
+    }
+
+    /// Clear the register (set to 0)
+    pub fn clear(&mut self) {
+        self.write(0);
+    }
+
+    /// Check if register is zero
+    pub fn is_zero(&self) -> bool {
+        self.read() == 0
+    }
+
+    /// Number of clock cycles since the register was created
+    pub fn ticks(&self) -> usize {
+        self.ticks
+    }
+}
+
+impl Default for SRLatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for DLatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for DFlipFlop {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for NeuralRegister {
+    fn default() -> Self {
+        Self::new()
+    }
+}
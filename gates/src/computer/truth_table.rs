@@ -0,0 +1,402 @@
+//! Truth Tables
+//!
+//! A [`TruthTable`] lists the output of a boolean function for every combination of its
+//! inputs, so any combinational cell can be described and learned by a
+//! [`LogicGate`](super::gates::LogicGate), not only the built-in gate types.
+//!
+//! Rows are in binary counting order with the first input as the most significant bit, and
+//! a table can be written two ways:
+//!
+//! - **Rows:** `00:0 01:1 10:1 11:0`, one `inputs:output` entry per row, in any order
+//! - **Expression:** `a ^ b`, with variables taken as inputs in alphabetical order
+//!
+//! Expressions support `!`/`~`/`NOT`, `&`/`*`/`AND`, `^`/`XOR`, `|`/`+`/`OR` (from highest to
+//! lowest precedence), parentheses and the constants `0` and `1`.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::computer::truth_table::TruthTable;
+//! let xor = TruthTable::parse("00:0 01:1 10:1 11:0").unwrap();
+//! assert_eq!(TruthTable::from_expression("(a | b) & !(a & b)").unwrap(), xor);
+//! assert_eq!(xor.to_string(), "00:0 01:1 10:1 11:0");
+//! ```
+
+use super::gates::{GateType, MAX_INPUTS};
+use std::fmt;
+
+/// Outputs of a boolean function for all `2^inputs` rows
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TruthTable {
+    inputs: usize,
+    outputs: Vec<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Variable(String),
+    Constant(bool),
+    Not,
+    And,
+    Xor,
+    Or,
+    Open,
+    Close,
+}
+
+#[derive(Debug)]
+enum Expr {
+    Variable(usize),
+    Constant(bool),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Xor(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+impl TruthTable {
+    /// Creates a table from one output per row, in binary counting order
+    ///
+    /// # Panics
+    ///
+    /// Panics if `inputs` is zero or above [`MAX_INPUTS`], or if there are not `2^inputs`
+    /// outputs.
+    pub fn new(inputs: usize, outputs: Vec<bool>) -> Self {
+        if !(1..=MAX_INPUTS).contains(&inputs) {
+            panic!("Truth table must have between 1 and {} inputs", MAX_INPUTS);
+        }
+        if outputs.len() != 1 << inputs {
+            panic!("Expected {} outputs, got {}", 1 << inputs, outputs.len());
+        }
+
+        TruthTable { inputs, outputs }
+    }
+
+    /// The table of a built-in gate type
+    pub fn of(gate_type: &GateType, inputs: usize) -> Self {
+        let outputs = (0..1usize << inputs)
+            .map(|row| gate_type.evaluate(&row_bits(row, inputs)))
+            .collect();
+
+        TruthTable::new(inputs, outputs)
+    }
+
+    /// Parses `inputs:output` entries separated by whitespace or commas, e.g.
+    /// `00:0 01:1 10:1 11:0`. Every row must appear exactly once.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let entries: Vec<&str> = text
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|entry| !entry.is_empty())
+            .collect();
+        let inputs = match entries.first() {
+            Some(entry) => entry.split(':').next().unwrap_or("").len(),
+            None => return Err("Empty truth table".to_string()),
+        };
+        if !(1..=MAX_INPUTS).contains(&inputs) {
+            return Err(format!(
+                "Truth table must have between 1 and {} inputs",
+                MAX_INPUTS
+            ));
+        }
+
+        let mut outputs: Vec<Option<bool>> = vec![None; 1 << inputs];
+        for entry in entries {
+            let invalid = || format!("Invalid entry {:?}", entry);
+            let (bits, output) = entry.split_once(':').ok_or_else(invalid)?;
+            if bits.len() != inputs {
+                return Err(format!(
+                    "Entry {:?} has {} inputs, expected {}",
+                    entry,
+                    bits.len(),
+                    inputs
+                ));
+            }
+
+            let row = usize::from_str_radix(bits, 2).map_err(|_| invalid())?;
+            let output = match output {
+                "0" => false,
+                "1" => true,
+                _ => return Err(invalid()),
+            };
+            if outputs[row].replace(output).is_some() {
+                return Err(format!("Duplicate row {}", bits));
+            }
+        }
+
+        let outputs = outputs
+            .iter()
+            .enumerate()
+            .map(|(row, output)| {
+                output.ok_or_else(|| format!("Missing row {:0width$b}", row, width = inputs))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(TruthTable::new(inputs, outputs))
+    }
+
+    /// Builds the table of a boolean expression such as `a & !b | c`
+    ///
+    /// The inputs are the expression's variables in alphabetical order.
+    pub fn from_expression(expression: &str) -> Result<Self, String> {
+        let tokens = tokenize(expression)?;
+
+        let mut variables: Vec<String> = tokens
+            .iter()
+            .filter_map(|token| match token {
+                Token::Variable(name) => Some(name.clone()),
+                _ => None,
+            })
+            .collect();
+        variables.sort();
+        variables.dedup();
+        if variables.is_empty() {
+            return Err("Expression has no variables".to_string());
+        }
+        if variables.len() > MAX_INPUTS {
+            return Err(format!(
+                "Expression has {} variables, at most {} are supported",
+                variables.len(),
+                MAX_INPUTS
+            ));
+        }
+
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+            variables: &variables,
+        };
+        let expr = parser.or()?;
+        if parser.position < tokens.len() {
+            return Err(format!("Unexpected {:?}", tokens[parser.position]));
+        }
+
+        let inputs = variables.len();
+        let outputs = (0..1usize << inputs)
+            .map(|row| expr.evaluate(&row_bits(row, inputs)))
+            .collect();
+
+        Ok(TruthTable::new(inputs, outputs))
+    }
+
+    /// Number of inputs
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    /// One output per row, in binary counting order
+    pub fn outputs(&self) -> &[bool] {
+        &self.outputs
+    }
+
+    /// The output for one combination of inputs
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs does not match.
+    pub fn evaluate(&self, inputs: &[bool]) -> bool {
+        if inputs.len() != self.inputs {
+            panic!(
+                "Truth table expects {} input(s), got {}",
+                self.inputs,
+                inputs.len()
+            );
+        }
+
+        let row = inputs
+            .iter()
+            .fold(0, |row, bit| (row << 1) | usize::from(*bit));
+        self.outputs[row]
+    }
+
+    /// All rows as `(inputs, output)` pairs of 0.0/1.0 values, ready for training
+    pub fn rows(&self) -> Vec<(Vec<f64>, f64)> {
+        self.outputs
+            .iter()
+            .enumerate()
+            .map(|(row, output)| {
+                let inputs = row_bits(row, self.inputs)
+                    .iter()
+                    .map(|bit| if *bit { 1.0 } else { 0.0 })
+                    .collect();
+                (inputs, if *output { 1.0 } else { 0.0 })
+            })
+            .collect()
+    }
+
+    /// The outputs as a string of 0s and 1s, e.g. `0110` for XOR
+    pub fn output_bits(&self) -> String {
+        self.outputs
+            .iter()
+            .map(|output| if *output { '1' } else { '0' })
+            .collect()
+    }
+
+    /// Parses a string of `2^n` 0s and 1s as written by [`TruthTable::output_bits`]
+    pub fn from_output_bits(bits: &str) -> Result<Self, String> {
+        let inputs = (1..=MAX_INPUTS)
+            .find(|inputs| 1 << inputs == bits.len())
+            .ok_or_else(|| format!("Expected 2^n outputs, got {}", bits.len()))?;
+        let outputs = bits
+            .chars()
+            .map(|c| match c {
+                '0' => Ok(false),
+                '1' => Ok(true),
+                _ => Err(format!("Invalid output {:?}", c)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(TruthTable::new(inputs, outputs))
+    }
+}
+
+impl fmt::Display for TruthTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries: Vec<String> = self
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(row, output)| {
+                format!(
+                    "{:0width$b}:{}",
+                    row,
+                    u8::from(*output),
+                    width = self.inputs
+                )
+            })
+            .collect();
+        write!(f, "{}", entries.join(" "))
+    }
+}
+
+/// The bits of `row`, most significant first
+fn row_bits(row: usize, inputs: usize) -> Vec<bool> {
+    (0..inputs)
+        .map(|bit| (row >> (inputs - 1 - bit)) & 1 == 1)
+        .collect()
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '!' | '~' => Token::Not,
+            '&' | '*' => Token::And,
+            '^' => Token::Xor,
+            '|' | '+' => Token::Or,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '0' => Token::Constant(false),
+            '1' => Token::Constant(true),
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                tokens.push(match word.to_uppercase().as_str() {
+                    "NOT" => Token::Not,
+                    "AND" => Token::And,
+                    "XOR" => Token::Xor,
+                    "OR" => Token::Or,
+                    _ => Token::Variable(word),
+                });
+                continue;
+            }
+            _ => return Err(format!("Unexpected character {:?} at column {}", c, i + 1)),
+        };
+        tokens.push(token);
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent over the tokens, one method per precedence level
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    variables: &'a [String],
+}
+
+impl Parser<'_> {
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.xor()?;
+        while self.accept(&Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.xor()?));
+        }
+        Ok(expr)
+    }
+
+    fn xor(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.accept(&Token::Xor) {
+            expr = Expr::Xor(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.not()?;
+        while self.accept(&Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> Result<Expr, String> {
+        if self.accept(&Token::Not) {
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+
+        let token = self
+            .tokens
+            .get(self.position)
+            .ok_or_else(|| "Unexpected end of expression".to_string())?;
+        self.position += 1;
+
+        match token {
+            Token::Variable(name) => Ok(Expr::Variable(
+                self.variables.iter().position(|v| v == name).unwrap(),
+            )),
+            Token::Constant(value) => Ok(Expr::Constant(*value)),
+            Token::Open => {
+                let expr = self.or()?;
+                if !self.accept(&Token::Close) {
+                    return Err("Expected ')'".to_string());
+                }
+                Ok(expr)
+            }
+            token => Err(format!("Unexpected {:?}", token)),
+        }
+    }
+
+    fn accept(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.position) == Some(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Expr {
+    fn evaluate(&self, inputs: &[bool]) -> bool {
+        match self {
+            Expr::Variable(index) => inputs[*index],
+            Expr::Constant(value) => *value,
+            Expr::Not(expr) => !expr.evaluate(inputs),
+            Expr::And(a, b) => a.evaluate(inputs) && b.evaluate(inputs),
+            Expr::Xor(a, b) => a.evaluate(inputs) != b.evaluate(inputs),
+            Expr::Or(a, b) => a.evaluate(inputs) || b.evaluate(inputs),
+        }
+    }
+}
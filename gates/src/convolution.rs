@@ -0,0 +1,529 @@
+//! Convolution and Pooling Layers
+//!
+//! Dense layers connect every input to every neuron, so they have to learn the same pattern
+//! separately at every position. A convolution slides a small set of shared kernels over the
+//! input instead, which makes it a natural fit for spatial patterns such as the segments of a
+//! seven-segment digit. Pooling then shrinks each feature map by keeping the maximum or the
+//! average of every window.
+//!
+//! # Data Layout
+//!
+//! Like every other layer, convolution and pooling layers take a `[batch_size, features]`
+//! matrix. Each row holds one sample, flattened channel by channel and, within a channel, row
+//! by row: element `(c, y, x)` of a `channels × height × width` sample is at column
+//! `c·height·width + y·width + x`. One-dimensional layers use the same layout with a height
+//! of one. Output rows follow the same layout, so layers can be stacked directly and a dense
+//! layer can read the result (see [`Layer::flatten`](crate::layer::Layer::flatten)).
+//!
+//! # Convolution
+//!
+//! A convolution with `output_channels` kernels of size `kernel_height × kernel_width` computes
+//!
+//! ```text
+//! out[o, y, x] = sigmoid(bias[o] + Σ kernels[o, c, ky, kx] · in[c, y·stride + ky - padding,
+//!                                                                   x·stride + kx - padding])
+//! ```
+//!
+//! where positions outside the input (the padding) read as zero. The sigmoid matches the
+//! activation of dense layers. The kernels are stored as an
+//! `output_channels × (input_channels · kernel_height · kernel_width)` matrix.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::convolution::{Convolution, Pooling};
+//! // 1 channel 5×3 image, 4 kernels of 3×3 with padding 1, then 2×2 max pooling
+//! let conv = Convolution::conv2d(1, (5, 3), 4, (3, 3), 42).with_padding(1);
+//! assert_eq!(conv.output_shape(), (4, 5, 3));
+//!
+//! let pool = Pooling::max_2d(4, (5, 3), (2, 2));
+//! assert_eq!(pool.output_shape(), (4, 2, 1));
+//! assert_eq!(pool.output_size(), 8);
+//! ```
+
+use aspirina_core::calc::Calc;
+use aspirina_core::matrix::Matrix;
+use aspirina_core::random::Rng;
+
+/// Size of one sample: channels, height and width.
+type Shape = (usize, usize, usize);
+
+/// Sliding-window geometry shared by convolution and pooling.
+#[derive(Debug, Clone, PartialEq)]
+struct Window {
+    input: Shape,
+    kernel: (usize, usize),
+    stride: (usize, usize),
+    padding: (usize, usize),
+    /// 1D windows only slide, pad and stride along the width
+    one_dimensional: bool,
+}
+
+impl Window {
+    /// Sets the same stride on every axis the window slides along.
+    fn set_stride(&mut self, stride: usize) {
+        self.stride = if self.one_dimensional {
+            (1, stride)
+        } else {
+            (stride, stride)
+        };
+        self.validate();
+    }
+
+    /// Sets the same padding on every axis the window slides along.
+    fn set_padding(&mut self, padding: usize) {
+        self.padding = if self.one_dimensional {
+            (0, padding)
+        } else {
+            (padding, padding)
+        };
+        self.validate();
+    }
+
+    fn output_height(&self) -> usize {
+        (self.input.1 + 2 * self.padding.0 - self.kernel.0) / self.stride.0 + 1
+    }
+
+    fn output_width(&self) -> usize {
+        (self.input.2 + 2 * self.padding.1 - self.kernel.1) / self.stride.1 + 1
+    }
+
+    fn validate(&self) {
+        if self.stride.0 == 0 || self.stride.1 == 0 {
+            panic!("Stride must be greater than zero");
+        }
+        if self.input.1 + 2 * self.padding.0 < self.kernel.0
+            || self.input.2 + 2 * self.padding.1 < self.kernel.1
+        {
+            panic!("Kernel is larger than the padded input");
+        }
+    }
+
+    /// Column of input element `(channel, y, x)` in a flattened sample, or `None` when the
+    /// position (before removing the padding) falls outside the input.
+    fn input_index(&self, channel: usize, y: usize, x: usize) -> Option<usize> {
+        let (_, height, width) = self.input;
+        let y = y.checked_sub(self.padding.0).filter(|y| *y < height)?;
+        let x = x.checked_sub(self.padding.1).filter(|x| *x < width)?;
+
+        Some(channel * height * width + y * width + x)
+    }
+
+    /// Input columns covered by the window at output position `(y, x)` of `channel`, in
+    /// kernel order. Padding positions are `None`.
+    fn receptive_field(&self, channel: usize, y: usize, x: usize) -> Vec<Option<usize>> {
+        let mut field = Vec::with_capacity(self.kernel.0 * self.kernel.1);
+
+        for ky in 0..self.kernel.0 {
+            for kx in 0..self.kernel.1 {
+                field.push(self.input_index(
+                    channel,
+                    y * self.stride.0 + ky,
+                    x * self.stride.1 + kx,
+                ));
+            }
+        }
+
+        field
+    }
+
+    fn check_input(&self, input: &Matrix) {
+        let (channels, height, width) = self.input;
+        if input
+            .data
+            .iter()
+            .any(|row| row.len() != channels * height * width)
+        {
+            panic!("Incompatible dimensions");
+        }
+    }
+}
+
+/// A 1D or 2D convolution layer with learned kernels and a bias per output channel.
+#[derive(Debug, Clone)]
+pub struct Convolution {
+    /// Kernel weights, shape `output_channels × (input_channels · kernel_height · kernel_width)`
+    pub kernels: Matrix,
+    /// One bias per output channel, shape `1 × output_channels`
+    pub bias: Matrix,
+    window: Window,
+}
+
+impl Convolution {
+    /// Creates a 1D convolution over `input_channels` sequences of `input_length` values.
+    ///
+    /// Kernels are initialized uniformly in `±1/√fan_in` from `seed`; stride defaults to 1
+    /// and padding to 0.
+    pub fn conv1d(
+        input_channels: usize,
+        input_length: usize,
+        output_channels: usize,
+        kernel_size: usize,
+        seed: u64,
+    ) -> Self {
+        Self::build(
+            (input_channels, 1, input_length),
+            output_channels,
+            (1, kernel_size),
+            seed,
+            true,
+        )
+    }
+
+    /// Creates a 2D convolution over `input_channels` images of `(height, width)` pixels.
+    ///
+    /// Kernels are initialized uniformly in `±1/√fan_in` from `seed`; stride defaults to 1
+    /// and padding to 0.
+    pub fn conv2d(
+        input_channels: usize,
+        input_size: (usize, usize),
+        output_channels: usize,
+        kernel_size: (usize, usize),
+        seed: u64,
+    ) -> Self {
+        Self::build(
+            (input_channels, input_size.0, input_size.1),
+            output_channels,
+            kernel_size,
+            seed,
+            false,
+        )
+    }
+
+    fn build(
+        input: Shape,
+        output_channels: usize,
+        kernel: (usize, usize),
+        seed: u64,
+        one_dimensional: bool,
+    ) -> Self {
+        let fan_in = input.0 * kernel.0 * kernel.1;
+        let limit = 1.0 / (fan_in as f64).sqrt();
+        let mut rng = Rng::new(seed);
+
+        let kernels = Matrix::new(
+            (0..output_channels)
+                .map(|_| (0..fan_in).map(|_| rng.range(-limit, limit)).collect())
+                .collect(),
+        );
+
+        let window = Window {
+            input,
+            kernel,
+            stride: (1, 1),
+            padding: (0, 0),
+            one_dimensional,
+        };
+        window.validate();
+
+        Self {
+            kernels,
+            bias: Matrix::new(vec![vec![0.0; output_channels]]),
+            window,
+        }
+    }
+
+    /// Sets the step between neighbouring windows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    pub fn with_stride(mut self, stride: usize) -> Self {
+        self.window.set_stride(stride);
+        self
+    }
+
+    /// Sets the number of zeros added on each side of the input.
+    pub fn with_padding(mut self, padding: usize) -> Self {
+        self.window.set_padding(padding);
+        self
+    }
+
+    /// Output channels, height and width of one sample (height is 1 for 1D convolutions).
+    pub fn output_shape(&self) -> Shape {
+        (
+            self.kernels.data.len(),
+            self.window.output_height(),
+            self.window.output_width(),
+        )
+    }
+
+    /// Number of output features per sample.
+    pub fn output_size(&self) -> usize {
+        let (channels, height, width) = self.output_shape();
+        channels * height * width
+    }
+
+    pub(crate) fn forward(&self, input: &Matrix) -> Matrix {
+        self.window.check_input(input);
+        let (output_channels, height, width) = self.output_shape();
+        let input_channels = self.window.input.0;
+
+        let output = input
+            .data
+            .iter()
+            .map(|sample| {
+                let mut row = Vec::with_capacity(output_channels * height * width);
+
+                for (kernel, bias) in self.kernels.data.iter().zip(self.bias.data[0].iter()) {
+                    for y in 0..height {
+                        for x in 0..width {
+                            let mut sum = *bias;
+                            let mut weights = kernel.iter();
+
+                            for channel in 0..input_channels {
+                                for index in self.window.receptive_field(channel, y, x) {
+                                    let weight = weights.next().unwrap();
+                                    if let Some(index) = index {
+                                        sum += weight * sample[index];
+                                    }
+                                }
+                            }
+
+                            row.push(Calc::sigmoid(&sum));
+                        }
+                    }
+                }
+
+                row
+            })
+            .collect();
+
+        Matrix::new(output)
+    }
+
+    pub(crate) fn backward(
+        &self,
+        input: &Matrix,
+        forwarded: &Matrix,
+        error: Matrix,
+    ) -> (Matrix, Vec<Matrix>) {
+        let (output_channels, height, width) = self.output_shape();
+        let input_channels = self.window.input.0;
+        let delta = Matrix::naive_multiply(forwarded.derivative(), error);
+
+        let mut kernel_adjustment = vec![vec![0.0; self.kernels.data[0].len()]; output_channels];
+        let mut bias_adjustment = vec![0.0; output_channels];
+        let mut previous_error = vec![vec![0.0; input.data[0].len()]; input.data.len()];
+
+        for ((sample, delta_row), previous_row) in input
+            .data
+            .iter()
+            .zip(delta.data.iter())
+            .zip(previous_error.iter_mut())
+        {
+            for o in 0..output_channels {
+                for y in 0..height {
+                    for x in 0..width {
+                        let d = delta_row[o * height * width + y * width + x];
+                        bias_adjustment[o] += d;
+
+                        let mut k = 0;
+                        for channel in 0..input_channels {
+                            for index in self.window.receptive_field(channel, y, x) {
+                                if let Some(index) = index {
+                                    kernel_adjustment[o][k] += d * sample[index];
+                                    previous_row[index] += d * self.kernels.data[o][k];
+                                }
+                                k += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        (
+            Matrix::new(previous_error),
+            vec![
+                Matrix::new(kernel_adjustment),
+                Matrix::new(vec![bias_adjustment]),
+            ],
+        )
+    }
+}
+
+/// How a [`Pooling`] layer summarizes each window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolingKind {
+    Max,
+    Average,
+}
+
+/// A 1D or 2D pooling layer. It has no trainable parameters.
+#[derive(Debug, Clone)]
+pub struct Pooling {
+    kind: PoolingKind,
+    window: Window,
+    /// For max pooling, the input column chosen for every output of the last forward pass
+    winners: Option<Vec<Vec<usize>>>,
+}
+
+impl Pooling {
+    /// Max pooling over windows of `size` values in `channels` sequences of `length` values.
+    pub fn max_1d(channels: usize, length: usize, size: usize) -> Self {
+        Self::build(PoolingKind::Max, (channels, 1, length), (1, size), true)
+    }
+
+    /// Average pooling over windows of `size` values in `channels` sequences of `length` values.
+    pub fn average_1d(channels: usize, length: usize, size: usize) -> Self {
+        Self::build(PoolingKind::Average, (channels, 1, length), (1, size), true)
+    }
+
+    /// Max pooling over `size` windows in `channels` images of `input_size` pixels.
+    pub fn max_2d(channels: usize, input_size: (usize, usize), size: (usize, usize)) -> Self {
+        Self::build(
+            PoolingKind::Max,
+            (channels, input_size.0, input_size.1),
+            size,
+            false,
+        )
+    }
+
+    /// Average pooling over `size` windows in `channels` images of `input_size` pixels.
+    pub fn average_2d(channels: usize, input_size: (usize, usize), size: (usize, usize)) -> Self {
+        Self::build(
+            PoolingKind::Average,
+            (channels, input_size.0, input_size.1),
+            size,
+            false,
+        )
+    }
+
+    fn build(kind: PoolingKind, input: Shape, size: (usize, usize), one_dimensional: bool) -> Self {
+        let window = Window {
+            input,
+            kernel: size,
+            stride: size,
+            padding: (0, 0),
+            one_dimensional,
+        };
+        window.validate();
+
+        Self {
+            kind,
+            window,
+            winners: None,
+        }
+    }
+
+    /// Sets the step between windows. By default windows do not overlap (stride = size).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    pub fn with_stride(mut self, stride: usize) -> Self {
+        self.window.set_stride(stride);
+        self
+    }
+
+    /// Whether this layer keeps the maximum or the average of each window.
+    pub fn kind(&self) -> PoolingKind {
+        self.kind
+    }
+
+    /// Channels, height and width of one output sample.
+    pub fn output_shape(&self) -> Shape {
+        (
+            self.window.input.0,
+            self.window.output_height(),
+            self.window.output_width(),
+        )
+    }
+
+    /// Number of output features per sample.
+    pub fn output_size(&self) -> usize {
+        let (channels, height, width) = self.output_shape();
+        channels * height * width
+    }
+
+    pub(crate) fn forward(&mut self, input: &Matrix) -> Matrix {
+        self.window.check_input(input);
+        let (channels, height, width) = self.output_shape();
+        let mut winners = Vec::with_capacity(input.data.len());
+
+        let output = input
+            .data
+            .iter()
+            .map(|sample| {
+                let mut row = Vec::with_capacity(channels * height * width);
+                let mut sample_winners = Vec::new();
+
+                for channel in 0..channels {
+                    for y in 0..height {
+                        for x in 0..width {
+                            let field: Vec<usize> = self
+                                .window
+                                .receptive_field(channel, y, x)
+                                .into_iter()
+                                .flatten()
+                                .collect();
+
+                            match self.kind {
+                                PoolingKind::Max => {
+                                    let winner = field
+                                        .iter()
+                                        .copied()
+                                        .max_by(|a, b| sample[*a].total_cmp(&sample[*b]))
+                                        .unwrap();
+                                    sample_winners.push(winner);
+                                    row.push(sample[winner]);
+                                }
+                                PoolingKind::Average => {
+                                    let sum: f64 = field.iter().map(|index| sample[*index]).sum();
+                                    row.push(sum / field.len() as f64);
+                                }
+                            }
+                        }
+                    }
+                }
+
+                winners.push(sample_winners);
+                row
+            })
+            .collect();
+
+        self.winners = Some(winners);
+        Matrix::new(output)
+    }
+
+    pub(crate) fn backward(&self, input: &Matrix, error: Matrix) -> Matrix {
+        let (channels, height, width) = self.output_shape();
+        let mut previous_error = vec![vec![0.0; input.data[0].len()]; input.data.len()];
+
+        for (sample, (error_row, previous_row)) in
+            error.data.iter().zip(previous_error.iter_mut()).enumerate()
+        {
+            match self.kind {
+                PoolingKind::Max => {
+                    let winners = &self.winners.as_ref().unwrap()[sample];
+                    for (e, winner) in error_row.iter().zip(winners.iter()) {
+                        previous_row[*winner] += e;
+                    }
+                }
+                PoolingKind::Average => {
+                    for channel in 0..channels {
+                        for y in 0..height {
+                            for x in 0..width {
+                                let e = error_row[channel * height * width + y * width + x];
+                                let field: Vec<usize> = self
+                                    .window
+                                    .receptive_field(channel, y, x)
+                                    .into_iter()
+                                    .flatten()
+                                    .collect();
+                                let share = e / field.len() as f64;
+
+                                for index in field {
+                                    previous_row[index] += share;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Matrix::new(previous_error)
+    }
+}
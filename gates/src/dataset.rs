@@ -0,0 +1,460 @@
+//! Datasets and Mini-Batch Loading
+//!
+//! Training data used to live in hand-written `Matrix::new(vec![...])` literals. This module
+//! gives it a home: a [`Dataset`] is an indexed collection of `(input, target)` samples, which
+//! can be split into training, validation and test sets, rescaled, and fed to a network in
+//! shuffled mini-batches by a [`DataLoader`].
+//!
+//! # Datasets
+//!
+//! - [`InMemoryDataset`]: samples held in vectors, built directly or from the input/target
+//!   matrices used throughout the crate
+//! - [`CsvDataset`]: samples read from comma-separated text, with the last columns as targets
+//!
+//! # Splitting and Scaling
+//!
+//! [`split`] shuffles the sample indices with a seed and cuts them into three
+//! [`InMemoryDataset`]s. A [`Scaler`] learns per-feature statistics from the training split
+//! only, then applies the same transformation to every split, so no information from the
+//! validation or test data leaks into training.
+//!
+//! # Batch Layout
+//!
+//! Each batch is an `(input, targets)` pair ready for [`NeuralNetwork::train`]: the input is
+//! `[batch_size, input_features]` and the targets are `[target_features, batch_size]`.
+//!
+//! [`NeuralNetwork::train`]: crate::neural_network::NeuralNetwork::train
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::dataset::{split, DataLoader, Dataset, CsvDataset, Scaler};
+//! let csv = "x1,x2,y\n0,0,0\n0,1,1\n1,0,1\n1,1,0\n2,2,0\n2,3,1\n3,2,1\n3,3,0\n";
+//! let dataset = CsvDataset::parse(csv, 1).unwrap();
+//!
+//! let splits = split(&dataset, 0.25, 0.0, 42);
+//! let scaler = Scaler::min_max(&splits.train);
+//! let train = scaler.transform(&splits.train);
+//!
+//! let mut loader = DataLoader::new(&train, 2).with_shuffle(7);
+//! for (input, targets) in loader.batches() {
+//!     assert_eq!(input.data.len(), 2);
+//!     assert_eq!(targets.data[0].len(), 2);
+//! }
+//! ```
+
+use aspirina_core::matrix::Matrix;
+use aspirina_core::random::Rng;
+use std::fs;
+use std::path::Path;
+
+/// An indexed collection of `(input, target)` samples.
+pub trait Dataset {
+    /// Number of samples.
+    fn len(&self) -> usize;
+
+    /// Returns the input and target vectors of the sample at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    fn get(&self, index: usize) -> (Vec<f64>, Vec<f64>);
+
+    /// Returns `true` if the dataset has no samples.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A dataset whose samples are held in memory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InMemoryDataset {
+    inputs: Vec<Vec<f64>>,
+    targets: Vec<Vec<f64>>,
+}
+
+impl InMemoryDataset {
+    /// Creates a dataset from one input vector and one target vector per sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs and targets differ.
+    pub fn new(inputs: Vec<Vec<f64>>, targets: Vec<Vec<f64>>) -> Self {
+        if inputs.len() != targets.len() {
+            panic!("Expected one target per input sample");
+        }
+
+        Self { inputs, targets }
+    }
+
+    /// Creates a dataset from matrices in the layout accepted by
+    /// [`NeuralNetwork::train`](crate::neural_network::NeuralNetwork::train): input
+    /// `[samples, input_features]`, targets `[target_features, samples]`.
+    pub fn from_matrices(input: &Matrix, targets: &Matrix) -> Self {
+        Self::new(input.data.clone(), targets.transpose().data)
+    }
+
+    /// Input vectors, one per sample.
+    pub fn inputs(&self) -> &[Vec<f64>] {
+        &self.inputs
+    }
+
+    /// Target vectors, one per sample.
+    pub fn targets(&self) -> &[Vec<f64>] {
+        &self.targets
+    }
+
+    /// Copies the samples at `indices` (in that order) into a new dataset.
+    pub fn subset(dataset: &dyn Dataset, indices: &[usize]) -> Self {
+        let (inputs, targets) = indices.iter().map(|&index| dataset.get(index)).unzip();
+
+        Self { inputs, targets }
+    }
+}
+
+impl Dataset for InMemoryDataset {
+    fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    fn get(&self, index: usize) -> (Vec<f64>, Vec<f64>) {
+        (self.inputs[index].clone(), self.targets[index].clone())
+    }
+}
+
+/// A dataset read from comma-separated values.
+///
+/// Every row holds the input features followed by `target_columns` target values. A first
+/// line in which no field parses as a number is treated as a header and its names are kept;
+/// a first line mixing numbers and text is an error. Blank lines are skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvDataset {
+    header: Option<Vec<String>>,
+    samples: InMemoryDataset,
+}
+
+impl CsvDataset {
+    /// Reads and parses the CSV file at `path`.
+    pub fn load(path: impl AsRef<Path>, target_columns: usize) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|error| format!("Cannot read {}: {}", path.display(), error))?;
+
+        Self::parse(&text, target_columns)
+    }
+
+    /// Parses CSV text whose last `target_columns` columns are the targets.
+    pub fn parse(text: &str, target_columns: usize) -> Result<Self, String> {
+        let mut header = None;
+        let mut inputs = Vec::new();
+        let mut targets = Vec::new();
+        let mut width = None;
+
+        for (line_idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let values: Result<Vec<f64>, _> = fields.iter().map(|f| f.parse::<f64>()).collect();
+
+            let values = match values {
+                Ok(values) => values,
+                Err(_)
+                    if width.is_none()
+                        && header.is_none()
+                        && fields.iter().all(|f| f.parse::<f64>().is_err()) =>
+                {
+                    header = Some(fields.iter().map(|f| f.to_string()).collect());
+                    width = Some(fields.len());
+                    continue;
+                }
+                Err(_) => {
+                    return Err(format!("Line {}: expected numeric values", line_idx + 1));
+                }
+            };
+
+            let expected = *width.get_or_insert(values.len());
+            if values.len() != expected {
+                return Err(format!(
+                    "Line {}: expected {} columns, found {}",
+                    line_idx + 1,
+                    expected,
+                    values.len()
+                ));
+            }
+            if target_columns >= values.len() {
+                return Err(format!(
+                    "Line {}: {} target columns leave no input columns",
+                    line_idx + 1,
+                    target_columns
+                ));
+            }
+
+            let split_at = values.len() - target_columns;
+            targets.push(values[split_at..].to_vec());
+            inputs.push(values[..split_at].to_vec());
+        }
+
+        Ok(Self {
+            header,
+            samples: InMemoryDataset::new(inputs, targets),
+        })
+    }
+
+    /// Column names from the header line, if the file had one.
+    pub fn header(&self) -> Option<&[String]> {
+        self.header.as_deref()
+    }
+}
+
+impl Dataset for CsvDataset {
+    fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    fn get(&self, index: usize) -> (Vec<f64>, Vec<f64>) {
+        self.samples.get(index)
+    }
+}
+
+/// The result of [`split`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Split {
+    pub train: InMemoryDataset,
+    pub validation: InMemoryDataset,
+    pub test: InMemoryDataset,
+}
+
+/// Shuffles `dataset` with `seed` and splits it into training, validation and test sets.
+///
+/// `validation` and `test` are the fractions of samples assigned to those sets (rounded down);
+/// the remaining samples form the training set.
+///
+/// # Panics
+///
+/// Panics if either fraction is negative or they add up to 1 or more.
+pub fn split(dataset: &dyn Dataset, validation: f64, test: f64, seed: u64) -> Split {
+    if validation < 0.0 || test < 0.0 || validation + test >= 1.0 {
+        panic!("Validation and test fractions must be non-negative and sum to less than 1");
+    }
+
+    let mut indices: Vec<usize> = (0..dataset.len()).collect();
+    Rng::new(seed).shuffle(&mut indices);
+
+    let validation_count = (dataset.len() as f64 * validation) as usize;
+    let test_count = (dataset.len() as f64 * test) as usize;
+
+    let (validation_indices, rest) = indices.split_at(validation_count);
+    let (test_indices, train_indices) = rest.split_at(test_count);
+
+    Split {
+        train: InMemoryDataset::subset(dataset, train_indices),
+        validation: InMemoryDataset::subset(dataset, validation_indices),
+        test: InMemoryDataset::subset(dataset, test_indices),
+    }
+}
+
+/// How a [`Scaler`] rescales each input feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scaling {
+    /// Maps the training minimum to 0 and maximum to 1
+    MinMax,
+    /// Shifts to zero mean and unit standard deviation
+    Standard,
+}
+
+/// Per-feature input rescaling fitted on a training set.
+///
+/// Each feature is transformed as `(x - offset) / scale`. Features that are constant in the
+/// training data keep a scale of 1 so they are shifted but never divided by zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scaler {
+    scaling: Scaling,
+    offset: Vec<f64>,
+    scale: Vec<f64>,
+}
+
+impl Scaler {
+    /// Fits a min-max normalization to the inputs of `dataset`.
+    pub fn min_max(dataset: &dyn Dataset) -> Self {
+        Self::fit(dataset, Scaling::MinMax)
+    }
+
+    /// Fits a standardization (z-score) to the inputs of `dataset`.
+    pub fn standard(dataset: &dyn Dataset) -> Self {
+        Self::fit(dataset, Scaling::Standard)
+    }
+
+    /// Fits the given kind of scaling to the inputs of `dataset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dataset` is empty.
+    pub fn fit(dataset: &dyn Dataset, scaling: Scaling) -> Self {
+        if dataset.is_empty() {
+            panic!("Cannot fit a scaler to an empty dataset");
+        }
+
+        let inputs: Vec<Vec<f64>> = (0..dataset.len()).map(|i| dataset.get(i).0).collect();
+        let features = inputs[0].len();
+        let count = inputs.len() as f64;
+
+        let (offset, spread): (Vec<f64>, Vec<f64>) = (0..features)
+            .map(|j| {
+                let column = inputs.iter().map(|row| row[j]);
+
+                match scaling {
+                    Scaling::MinMax => {
+                        let min = column.clone().fold(f64::INFINITY, f64::min);
+                        let max = column.fold(f64::NEG_INFINITY, f64::max);
+                        (min, max - min)
+                    }
+                    Scaling::Standard => {
+                        let mean = column.clone().sum::<f64>() / count;
+                        let variance = column.map(|x| (x - mean).powi(2)).sum::<f64>() / count;
+                        (mean, variance.sqrt())
+                    }
+                }
+            })
+            .unzip();
+
+        let scale = spread
+            .into_iter()
+            .map(|s| if s > 0.0 { s } else { 1.0 })
+            .collect();
+
+        Self {
+            scaling,
+            offset,
+            scale,
+        }
+    }
+
+    /// The kind of scaling that was fitted.
+    pub fn scaling(&self) -> Scaling {
+        self.scaling
+    }
+
+    /// Rescales a single input vector.
+    pub fn transform_sample(&self, input: &[f64]) -> Vec<f64> {
+        input
+            .iter()
+            .zip(self.offset.iter().zip(self.scale.iter()))
+            .map(|(x, (offset, scale))| (x - offset) / scale)
+            .collect()
+    }
+
+    /// Rescales every row of an input matrix (`[samples, input_features]`).
+    pub fn transform_input(&self, input: &Matrix) -> Matrix {
+        Matrix::new(
+            input
+                .data
+                .iter()
+                .map(|row| self.transform_sample(row))
+                .collect(),
+        )
+    }
+
+    /// Returns a copy of `dataset` with rescaled inputs; targets are left unchanged.
+    pub fn transform(&self, dataset: &dyn Dataset) -> InMemoryDataset {
+        let (inputs, targets) = (0..dataset.len())
+            .map(|i| {
+                let (input, target) = dataset.get(i);
+                (self.transform_sample(&input), target)
+            })
+            .unzip();
+
+        InMemoryDataset::new(inputs, targets)
+    }
+}
+
+/// Produces mini-batches from a dataset.
+///
+/// Without shuffling, batches follow the dataset order. With [`with_shuffle`](Self::with_shuffle)
+/// every call to [`batches`](Self::batches) draws a new order from the seeded generator, so each
+/// epoch sees the samples in a different but reproducible order. The last batch is smaller when
+/// the dataset size is not a multiple of the batch size.
+pub struct DataLoader<'a> {
+    dataset: &'a dyn Dataset,
+    batch_size: usize,
+    rng: Option<Rng>,
+}
+
+impl<'a> DataLoader<'a> {
+    /// Creates a loader yielding batches of `batch_size` samples in dataset order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    pub fn new(dataset: &'a dyn Dataset, batch_size: usize) -> Self {
+        if batch_size == 0 {
+            panic!("Batch size must be greater than zero");
+        }
+
+        Self {
+            dataset,
+            batch_size,
+            rng: None,
+        }
+    }
+
+    /// Shuffles the samples before every epoch using a generator seeded with `seed`.
+    pub fn with_shuffle(mut self, seed: u64) -> Self {
+        self.rng = Some(Rng::new(seed));
+        self
+    }
+
+    /// Number of batches per epoch.
+    pub fn len(&self) -> usize {
+        self.dataset.len().div_ceil(self.batch_size)
+    }
+
+    /// Returns `true` if the underlying dataset is empty.
+    pub fn is_empty(&self) -> bool {
+        self.dataset.is_empty()
+    }
+
+    /// Returns an iterator over one epoch of `(input, targets)` batches.
+    pub fn batches(&mut self) -> Batches<'a> {
+        let mut order: Vec<usize> = (0..self.dataset.len()).collect();
+        if let Some(rng) = self.rng.as_mut() {
+            rng.shuffle(&mut order);
+        }
+
+        Batches {
+            dataset: self.dataset,
+            order,
+            batch_size: self.batch_size,
+            position: 0,
+        }
+    }
+}
+
+/// One epoch of mini-batches, created by [`DataLoader::batches`].
+pub struct Batches<'a> {
+    dataset: &'a dyn Dataset,
+    order: Vec<usize>,
+    batch_size: usize,
+    position: usize,
+}
+
+impl Iterator for Batches<'_> {
+    type Item = (Matrix, Matrix);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.order.len() {
+            return None;
+        }
+
+        let end = (self.position + self.batch_size).min(self.order.len());
+        let (inputs, targets): (Vec<_>, Vec<_>) = self.order[self.position..end]
+            .iter()
+            .map(|&index| self.dataset.get(index))
+            .unzip();
+        self.position = end;
+
+        Some((Matrix::new(inputs), Matrix::new(targets).transpose()))
+    }
+}
@@ -0,0 +1,82 @@
+//! Dropout Regularization
+//!
+//! Dropout randomly silences a fraction of activations while training, so the network cannot
+//! rely on any single neuron. This implementation uses *inverted* dropout: surviving values are
+//! scaled by `1 / (1 - rate)` during training, which keeps the expected activation unchanged and
+//! lets the layer pass values through untouched at inference time.
+//!
+//! The mask is drawn from a seeded [`Rng`], so training runs are reproducible. Dropout is only
+//! active while the owning network is in training mode (see
+//! [`NeuralNetwork::eval`](crate::neural_network::NeuralNetwork::eval)).
+
+use aspirina_core::matrix::Matrix;
+use aspirina_core::random::Rng;
+
+/// State of a dropout layer: the drop rate, its random source and the last mask applied.
+#[derive(Debug, Clone)]
+pub struct Dropout {
+    rate: f64,
+    rng: Rng,
+    mask: Option<Matrix>,
+}
+
+impl Dropout {
+    /// Creates a dropout layer that drops each activation with probability `rate`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is not in `[0, 1)`.
+    pub fn new(rate: f64, seed: u64) -> Self {
+        if !(0.0..1.0).contains(&rate) {
+            panic!("Dropout rate must be in [0, 1)");
+        }
+
+        Self {
+            rate,
+            rng: Rng::new(seed),
+            mask: None,
+        }
+    }
+
+    /// Probability of dropping each activation.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub(crate) fn forward(&mut self, input: &Matrix, training: bool) -> Matrix {
+        if !training || self.rate == 0.0 {
+            self.mask = None;
+            return input.clone();
+        }
+
+        let keep = 1.0 - self.rate;
+        let mask = Matrix::new(
+            input
+                .data
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|_| {
+                            if self.rng.next_f64() < keep {
+                                1.0 / keep
+                            } else {
+                                0.0
+                            }
+                        })
+                        .collect()
+                })
+                .collect(),
+        );
+
+        let output = Matrix::naive_multiply(input.clone(), mask.clone());
+        self.mask = Some(mask);
+        output
+    }
+
+    pub(crate) fn backward(&self, error: Matrix) -> Matrix {
+        match &self.mask {
+            Some(mask) => Matrix::naive_multiply(error, mask.clone()),
+            None => error,
+        }
+    }
+}
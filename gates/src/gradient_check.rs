@@ -0,0 +1,135 @@
+//! Numerical Gradient Checking
+//!
+//! This module verifies the gradients produced by backpropagation against a finite-difference
+//! approximation. Each trainable parameter (dense weights, normalization scales and shifts) is
+//! nudged by `±epsilon`, the loss is recomputed, and the slope
+//! `(loss(w + ε) - loss(w - ε)) / 2ε` is compared with the analytic gradient returned by
+//! [`NeuralNetwork::gradients`].
+//!
+//! # Relative Error
+//!
+//! For every parameter the relative error is
+//!
+//! ```text
+//! |analytic - numerical| / max(|analytic| + |numerical|, 1e-8)
+//! ```
+//!
+//! The floor in the denominator keeps parameters whose gradient is practically zero from reporting
+//! huge errors. A correct backward pass typically stays below `1e-6` with `epsilon = 1e-5`.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::gradient_check::check_gradients;
+//! # use aspirina_gates::neural_network::NeuralNetwork;
+//! # use aspirina_gates::layer::Layer;
+//! # use aspirina_core::matrix::Matrix;
+//! let network = NeuralNetwork::new(vec![
+//!     Layer::new(Matrix::new(vec![vec![0.1, 0.2], vec![0.3, 0.4]])),
+//!     Layer::new(Matrix::new(vec![vec![0.5, 0.6]])),
+//! ]);
+//!
+//! let input = Matrix::new(vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+//! let targets = Matrix::new(vec![vec![1.0, 1.0]]);
+//!
+//! let report = check_gradients(&network, input, targets, 1e-5);
+//! assert!(report.max_relative_error() < 1e-6);
+//! ```
+
+use crate::neural_network::NeuralNetwork;
+use aspirina_core::matrix::Matrix;
+
+/// Gradient check result for a single layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerGradientCheck {
+    /// Index of the layer, counting from the input side
+    pub layer: usize,
+    /// Largest relative error found among the layer's parameters (zero for layers without any)
+    pub max_relative_error: f64,
+}
+
+/// Gradient check results for every layer of a network.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradientCheckReport {
+    /// One entry per layer, ordered from input to output
+    pub layers: Vec<LayerGradientCheck>,
+}
+
+impl GradientCheckReport {
+    /// Largest relative error across all layers.
+    pub fn max_relative_error(&self) -> f64 {
+        self.layers
+            .iter()
+            .map(|layer| layer.max_relative_error)
+            .fold(0.0, f64::max)
+    }
+
+    /// Returns `true` when every layer stays within `tolerance`.
+    pub fn passed(&self, tolerance: f64) -> bool {
+        self.max_relative_error() <= tolerance
+    }
+}
+
+/// Compares the analytic gradients of `network` with central finite differences.
+///
+/// Every layer is restored before returning, including batch normalization running statistics
+/// that the check's forward passes would otherwise overwrite, so the check can run on a network
+/// that is in the middle of training. Dropout draws a fresh mask on every forward pass, so
+/// networks containing dropout layers should be checked in evaluation mode.
+///
+/// # Parameters
+///
+/// * `network` - The network whose backward pass is being verified
+/// * `input` - Input batch, shape `[batch_size, input_features]`
+/// * `targets` - Targets in the layout accepted by [`NeuralNetwork::train`]
+/// * `epsilon` - Perturbation applied to each parameter (e.g. `1e-5`)
+pub fn check_gradients(
+    network: &NeuralNetwork,
+    input: Matrix,
+    targets: Matrix,
+    epsilon: f64,
+) -> GradientCheckReport {
+    let snapshot = network.snapshot();
+    let analytic = network.gradients(input.clone(), targets.clone());
+    let mut parameters = network.parameters();
+    let mut layers = Vec::with_capacity(analytic.len());
+
+    for (idx, layer_gradients) in analytic.iter().enumerate() {
+        let mut max_relative_error: f64 = 0.0;
+
+        for (p, gradient) in layer_gradients.iter().enumerate() {
+            for (i, row) in gradient.data.iter().enumerate() {
+                for (j, &analytic_value) in row.iter().enumerate() {
+                    let original = parameters[idx][p].data[i][j];
+
+                    parameters[idx][p].data[i][j] = original + epsilon;
+                    network.set_parameters(parameters.clone());
+                    let loss_plus = network.loss(input.clone(), targets.clone());
+
+                    parameters[idx][p].data[i][j] = original - epsilon;
+                    network.set_parameters(parameters.clone());
+                    let loss_minus = network.loss(input.clone(), targets.clone());
+
+                    parameters[idx][p].data[i][j] = original;
+
+                    let numerical_value = (loss_plus - loss_minus) / (2.0 * epsilon);
+                    max_relative_error =
+                        max_relative_error.max(relative_error(analytic_value, numerical_value));
+                }
+            }
+        }
+
+        layers.push(LayerGradientCheck {
+            layer: idx,
+            max_relative_error,
+        });
+    }
+
+    network.restore(snapshot);
+
+    GradientCheckReport { layers }
+}
+
+fn relative_error(analytic: f64, numerical: f64) -> f64 {
+    (analytic - numerical).abs() / (analytic.abs() + numerical.abs()).max(1e-8)
+}
@@ -3,9 +3,36 @@
 //! This module provides the `Layer` struct which represents individual layers in a neural network.
 //! Each layer contains a weight matrix and can store forward propagation results for use during
 //! backpropagation training.
+//!
+//! Besides dense (fully connected) layers, a layer can be one of the special kinds listed in
+//! [`LayerKind`], such as dropout. Every kind knows how to run its own forward and backward pass,
+//! so the network can treat all layers uniformly.
 
+use crate::convolution::{Convolution, Pooling};
+use crate::dropout::Dropout;
+use crate::normalization::{BatchNorm, LayerNorm};
+use aspirina_core::calc::Calc;
 use aspirina_core::matrix::Matrix;
 
+/// The kind of computation a layer performs.
+#[derive(Debug, Clone)]
+pub enum LayerKind {
+    /// Fully connected layer: `sigmoid(input × matrixᵀ)`
+    Dense,
+    /// Inverted dropout, active only while the network is training
+    Dropout(Dropout),
+    /// Batch normalization with learned scale and shift
+    BatchNorm(BatchNorm),
+    /// Layer normalization with learned scale and shift
+    LayerNorm(LayerNorm),
+    /// 1D or 2D convolution with learned kernels and bias, followed by a sigmoid
+    Convolution(Convolution),
+    /// 1D or 2D max or average pooling
+    Pooling(Pooling),
+    /// Marks the hand-over from feature maps to dense layers; passes values through unchanged
+    Flatten,
+}
+
 /// Represents a single layer in a neural network.
 ///
 /// A layer is a fundamental building block of neural networks that contains weights (connections)
@@ -56,6 +83,9 @@ pub struct Layer {
     /// during the forward pass. These values are essential for computing gradients
     /// during backpropagation training.
     pub forwarded: Option<Matrix>,
+
+    /// The computation this layer performs. Layers built with [`Layer::new`] are dense.
+    pub kind: LayerKind,
 }
 
 impl Layer {
@@ -90,6 +120,207 @@ impl Layer {
         Self {
             matrix,
             forwarded: None,
+            kind: LayerKind::Dense,
+        }
+    }
+
+    /// Creates a dropout layer that drops each activation with probability `rate`.
+    ///
+    /// Dropout layers have no weights, so their `matrix` is empty. The `seed` makes the
+    /// sequence of dropout masks reproducible.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use aspirina_core::matrix::Matrix;
+    /// use aspirina_gates::layer::{Layer, LayerKind};
+    ///
+    /// let layer = Layer::dropout(0.2, 42);
+    /// assert!(matches!(layer.kind, LayerKind::Dropout(_)));
+    /// ```
+    pub fn dropout(rate: f64, seed: u64) -> Self {
+        Self {
+            matrix: Matrix::new(vec![]),
+            forwarded: None,
+            kind: LayerKind::Dropout(Dropout::new(rate, seed)),
+        }
+    }
+
+    /// Creates a batch normalization layer for `features` inputs.
+    ///
+    /// The layer normalizes each feature across the batch, then applies a learned scale
+    /// and shift. Its `matrix` is empty; the scale and shift live in the [`BatchNorm`] state.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use aspirina_gates::layer::{Layer, LayerKind};
+    ///
+    /// let layer = Layer::batch_norm(4);
+    /// assert_eq!(layer.parameters().len(), 2); // gamma and beta
+    /// ```
+    pub fn batch_norm(features: usize) -> Self {
+        Self {
+            matrix: Matrix::new(vec![]),
+            forwarded: None,
+            kind: LayerKind::BatchNorm(BatchNorm::new(features)),
+        }
+    }
+
+    /// Creates a layer normalization layer for `features` inputs.
+    ///
+    /// The layer normalizes each sample across its features, then applies a learned scale
+    /// and shift. Its `matrix` is empty; the scale and shift live in the [`LayerNorm`] state.
+    pub fn layer_norm(features: usize) -> Self {
+        Self {
+            matrix: Matrix::new(vec![]),
+            forwarded: None,
+            kind: LayerKind::LayerNorm(LayerNorm::new(features)),
+        }
+    }
+
+    /// Creates a convolution layer. Its `matrix` is empty; the kernels live in the
+    /// [`Convolution`] state.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use aspirina_gates::convolution::Convolution;
+    /// use aspirina_gates::layer::Layer;
+    ///
+    /// let layer = Layer::convolution(Convolution::conv1d(1, 8, 2, 3, 7));
+    /// assert_eq!(layer.parameters().len(), 2); // kernels and bias
+    /// ```
+    pub fn convolution(convolution: Convolution) -> Self {
+        Self {
+            matrix: Matrix::new(vec![]),
+            forwarded: None,
+            kind: LayerKind::Convolution(convolution),
+        }
+    }
+
+    /// Creates a max or average pooling layer.
+    pub fn pooling(pooling: Pooling) -> Self {
+        Self {
+            matrix: Matrix::new(vec![]),
+            forwarded: None,
+            kind: LayerKind::Pooling(pooling),
+        }
+    }
+
+    /// Creates a flatten layer.
+    ///
+    /// Feature maps are already stored as one flattened row per sample, so this layer does
+    /// not change any values. It documents where the network switches from convolution and
+    /// pooling layers to dense layers.
+    pub fn flatten() -> Self {
+        Self {
+            matrix: Matrix::new(vec![]),
+            forwarded: None,
+            kind: LayerKind::Flatten,
+        }
+    }
+
+    /// Returns copies of this layer's trainable parameters.
+    ///
+    /// Dense layers return their weight matrix, normalization layers return `gamma` and
+    /// `beta`, convolution layers return their kernels and bias, and dropout, pooling and
+    /// flatten layers return nothing.
+    pub fn parameters(&self) -> Vec<Matrix> {
+        match &self.kind {
+            LayerKind::Dense => vec![self.matrix.clone()],
+            LayerKind::Dropout(_) | LayerKind::Pooling(_) | LayerKind::Flatten => vec![],
+            LayerKind::BatchNorm(norm) => vec![norm.gamma.clone(), norm.beta.clone()],
+            LayerKind::LayerNorm(norm) => vec![norm.gamma.clone(), norm.beta.clone()],
+            LayerKind::Convolution(conv) => vec![conv.kernels.clone(), conv.bias.clone()],
+        }
+    }
+
+    /// Replaces this layer's trainable parameters, in the order returned by
+    /// [`parameters`](Self::parameters).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of matrices does not match the layer kind.
+    pub fn set_parameters(&mut self, parameters: Vec<Matrix>) {
+        if parameters.len() != self.parameters().len() {
+            panic!("Parameter count does not match layer kind");
+        }
+
+        let mut parameters = parameters.into_iter();
+
+        match &mut self.kind {
+            LayerKind::Dense => self.matrix = parameters.next().unwrap(),
+            LayerKind::Dropout(_) | LayerKind::Pooling(_) | LayerKind::Flatten => {}
+            LayerKind::BatchNorm(norm) => {
+                norm.gamma = parameters.next().unwrap();
+                norm.beta = parameters.next().unwrap();
+            }
+            LayerKind::LayerNorm(norm) => {
+                norm.gamma = parameters.next().unwrap();
+                norm.beta = parameters.next().unwrap();
+            }
+            LayerKind::Convolution(conv) => {
+                conv.kernels = parameters.next().unwrap();
+                conv.bias = parameters.next().unwrap();
+            }
+        }
+    }
+
+    /// Returns the weights subject to regularization, which are always the first parameter.
+    pub(crate) fn weights(&self) -> Option<&Matrix> {
+        match &self.kind {
+            LayerKind::Dense => Some(&self.matrix),
+            LayerKind::Convolution(conv) => Some(&conv.kernels),
+            _ => None,
+        }
+    }
+
+    /// Runs the forward pass, storing the output in `forwarded`.
+    pub(crate) fn forward(&mut self, input: &Matrix, training: bool) -> Matrix {
+        let output = match &mut self.kind {
+            LayerKind::Dense => Matrix::new(
+                (input.clone() * self.matrix.transpose())
+                    .data
+                    .iter()
+                    .map(|row| row.iter().map(Calc::sigmoid).collect())
+                    .collect(),
+            ),
+            LayerKind::Dropout(dropout) => dropout.forward(input, training),
+            LayerKind::BatchNorm(norm) => norm.forward(input, training),
+            LayerKind::LayerNorm(norm) => norm.forward(input),
+            LayerKind::Convolution(conv) => conv.forward(input),
+            LayerKind::Pooling(pooling) => pooling.forward(input),
+            LayerKind::Flatten => input.clone(),
+        };
+
+        self.forwarded = Some(output.clone());
+        output
+    }
+
+    /// Runs the backward pass for the most recent forward pass.
+    ///
+    /// `error` is the difference between desired and actual output of this layer (the
+    /// negative loss gradient). Returns the error to propagate to the previous layer and
+    /// one adjustment per trainable parameter, in the order of [`parameters`](Self::parameters).
+    pub(crate) fn backward(&self, input: &Matrix, error: Matrix) -> (Matrix, Vec<Matrix>) {
+        match &self.kind {
+            LayerKind::Dense => {
+                let forwarded = self.forwarded.clone().unwrap();
+                let delta = Matrix::naive_multiply(forwarded.derivative(), error);
+                let previous_error = delta.clone() * self.matrix.clone();
+                let adjustment = (input.transpose() * delta).transpose();
+
+                (previous_error, vec![adjustment])
+            }
+            LayerKind::Dropout(dropout) => (dropout.backward(error), vec![]),
+            LayerKind::BatchNorm(norm) => norm.backward(error),
+            LayerKind::LayerNorm(norm) => norm.backward(error),
+            LayerKind::Convolution(conv) => {
+                conv.backward(input, self.forwarded.as_ref().unwrap(), error)
+            }
+            LayerKind::Pooling(pooling) => (pooling.backward(input, error), vec![]),
+            LayerKind::Flatten => (error, vec![]),
         }
     }
 }
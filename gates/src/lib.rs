@@ -1,5 +1,16 @@
+pub mod callbacks;
+pub mod convolution;
+pub mod dataset;
+pub mod dropout;
+pub mod gradient_check;
 pub mod layer;
+pub mod lr_schedule;
+pub mod metrics;
+pub mod music;
 pub mod neural_network;
+pub mod normalization;
+pub mod regularization;
+pub mod trainer;
 
 pub mod training {
     pub mod and_gate;
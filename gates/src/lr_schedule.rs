@@ -0,0 +1,258 @@
+//! Learning-Rate Schedules
+//!
+//! A fixed learning rate is a compromise: large steps make fast early progress but bounce
+//! around the minimum, small steps settle precisely but take forever to get there. A schedule
+//! changes the learning rate as training progresses so both phases get a suitable step size.
+//!
+//! # Available Schedules
+//!
+//! - [`ConstantLr`]: the same rate forever
+//! - [`StepDecay`]: multiply by `gamma` every `step_size` steps
+//! - [`ExponentialDecay`]: multiply by `gamma` every step
+//! - [`CosineAnnealingWarmRestarts`]: follow a cosine from `max_lr` down to `min_lr`, then jump
+//!   back up; each cycle can be longer than the last
+//! - [`LinearWarmup`]: ramp up linearly, then hand over to another schedule
+//! - [`ReduceOnPlateau`]: cut the rate when the validation loss stops improving
+//!
+//! A schedule is asked for the learning rate of each step (an epoch or a mini-batch, depending
+//! on how the training loop counts) and is told about the validation loss after every epoch.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::lr_schedule::{LrSchedule, StepDecay};
+//! let mut schedule = StepDecay::new(1.0, 10, 0.5);
+//!
+//! assert_eq!(schedule.learning_rate(0), 1.0);
+//! assert_eq!(schedule.learning_rate(10), 0.5);
+//! assert_eq!(schedule.learning_rate(25), 0.25);
+//! ```
+
+use std::f64::consts::PI;
+
+/// A rule for choosing the learning rate at each training step.
+pub trait LrSchedule {
+    /// Returns the learning rate for the zero-based training `step`.
+    fn learning_rate(&mut self, step: usize) -> f64;
+
+    /// Reports the latest validation loss. Schedules that do not react to it ignore the call.
+    fn observe(&mut self, _validation_loss: f64) {}
+}
+
+/// A learning rate that never changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstantLr {
+    learning_rate: f64,
+}
+
+impl ConstantLr {
+    /// Creates a schedule that always returns `learning_rate`.
+    pub fn new(learning_rate: f64) -> Self {
+        Self { learning_rate }
+    }
+}
+
+impl LrSchedule for ConstantLr {
+    fn learning_rate(&mut self, _step: usize) -> f64 {
+        self.learning_rate
+    }
+}
+
+/// Multiplies the learning rate by `gamma` every `step_size` steps.
+///
+/// `lr(step) = initial · gamma^⌊step / step_size⌋`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepDecay {
+    initial: f64,
+    step_size: usize,
+    gamma: f64,
+}
+
+impl StepDecay {
+    /// Creates a step decay starting at `initial`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step_size` is zero.
+    pub fn new(initial: f64, step_size: usize, gamma: f64) -> Self {
+        if step_size == 0 {
+            panic!("Step size must be greater than zero");
+        }
+
+        Self {
+            initial,
+            step_size,
+            gamma,
+        }
+    }
+}
+
+impl LrSchedule for StepDecay {
+    fn learning_rate(&mut self, step: usize) -> f64 {
+        self.initial * self.gamma.powi((step / self.step_size) as i32)
+    }
+}
+
+/// Multiplies the learning rate by `gamma` every step.
+///
+/// `lr(step) = initial · gamma^step`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExponentialDecay {
+    initial: f64,
+    gamma: f64,
+}
+
+impl ExponentialDecay {
+    /// Creates an exponential decay starting at `initial`.
+    pub fn new(initial: f64, gamma: f64) -> Self {
+        Self { initial, gamma }
+    }
+}
+
+impl LrSchedule for ExponentialDecay {
+    fn learning_rate(&mut self, step: usize) -> f64 {
+        self.initial * self.gamma.powi(step as i32)
+    }
+}
+
+/// Cosine annealing with warm restarts (SGDR).
+///
+/// Within a cycle of length `T`, at position `t`:
+/// `lr = min_lr + ½ (max_lr - min_lr) (1 + cos(π t / T))`.
+/// When a cycle ends, the rate jumps back to `max_lr` and the next cycle is
+/// `period_multiplier` times longer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CosineAnnealingWarmRestarts {
+    max_lr: f64,
+    min_lr: f64,
+    period: usize,
+    period_multiplier: usize,
+}
+
+impl CosineAnnealingWarmRestarts {
+    /// Creates a cosine schedule whose first cycle lasts `period` steps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` or `period_multiplier` is zero.
+    pub fn new(max_lr: f64, min_lr: f64, period: usize, period_multiplier: usize) -> Self {
+        if period == 0 || period_multiplier == 0 {
+            panic!("Period and period multiplier must be greater than zero");
+        }
+
+        Self {
+            max_lr,
+            min_lr,
+            period,
+            period_multiplier,
+        }
+    }
+}
+
+impl LrSchedule for CosineAnnealingWarmRestarts {
+    fn learning_rate(&mut self, step: usize) -> f64 {
+        let mut position = step;
+        let mut period = self.period;
+
+        while position >= period {
+            position -= period;
+            period *= self.period_multiplier;
+        }
+
+        let progress = position as f64 / period as f64;
+        self.min_lr + 0.5 * (self.max_lr - self.min_lr) * (1.0 + (PI * progress).cos())
+    }
+}
+
+/// Ramps the learning rate up linearly, then defers to another schedule.
+///
+/// During the first `warmup_steps` steps the rate grows from `lr₀ / warmup_steps` to `lr₀`,
+/// where `lr₀` is the inner schedule's first learning rate. After warm-up the inner schedule
+/// runs as if training had just started.
+pub struct LinearWarmup {
+    warmup_steps: usize,
+    inner: Box<dyn LrSchedule>,
+}
+
+impl LinearWarmup {
+    /// Warms up over `warmup_steps` steps before handing over to `inner`.
+    pub fn new(warmup_steps: usize, inner: impl LrSchedule + 'static) -> Self {
+        Self {
+            warmup_steps,
+            inner: Box::new(inner),
+        }
+    }
+}
+
+impl LrSchedule for LinearWarmup {
+    fn learning_rate(&mut self, step: usize) -> f64 {
+        if step < self.warmup_steps {
+            let target = self.inner.learning_rate(0);
+            target * (step + 1) as f64 / self.warmup_steps as f64
+        } else {
+            self.inner.learning_rate(step - self.warmup_steps)
+        }
+    }
+
+    fn observe(&mut self, validation_loss: f64) {
+        self.inner.observe(validation_loss);
+    }
+}
+
+/// Multiplies the learning rate by `factor` when the validation loss stops improving.
+///
+/// A loss counts as an improvement when it beats the best loss so far by more than
+/// `threshold`. Once more than `patience` consecutive observations fail to improve, the
+/// rate is reduced (never below `min_lr`) and the count starts again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReduceOnPlateau {
+    learning_rate: f64,
+    factor: f64,
+    patience: usize,
+    min_lr: f64,
+    threshold: f64,
+    best: f64,
+    stale: usize,
+}
+
+impl ReduceOnPlateau {
+    /// Starts at `initial` and multiplies by `factor` once the loss has been stale for longer
+    /// than `patience` observations.
+    pub fn new(initial: f64, factor: f64, patience: usize, min_lr: f64) -> Self {
+        Self {
+            learning_rate: initial,
+            factor,
+            patience,
+            min_lr,
+            threshold: 1e-4,
+            best: f64::INFINITY,
+            stale: 0,
+        }
+    }
+
+    /// Sets the minimum decrease that counts as an improvement (defaults to `1e-4`).
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+}
+
+impl LrSchedule for ReduceOnPlateau {
+    fn learning_rate(&mut self, _step: usize) -> f64 {
+        self.learning_rate
+    }
+
+    fn observe(&mut self, validation_loss: f64) {
+        if validation_loss < self.best - self.threshold {
+            self.best = validation_loss;
+            self.stale = 0;
+            return;
+        }
+
+        self.stale += 1;
+        if self.stale > self.patience {
+            self.learning_rate = (self.learning_rate * self.factor).max(self.min_lr);
+            self.stale = 0;
+        }
+    }
+}
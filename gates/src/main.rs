@@ -23,9 +23,11 @@ fn main() {
     println!("14. Test CPU");
     println!("15. Test Assembler");
     println!("16. Test Interpreter");
+    println!("=== Music ===");
+    println!("17. Chord Progression Predictor");
     println!("0. Exit");
     println!();
-    print!("Select a scenario (0-16): ");
+    print!("Select a scenario (0-17): ");
     io::stdout().flush().unwrap();
 
     let mut input = String::new();
@@ -75,7 +77,11 @@ fn main() {
             println!("=== Neural Computer Interpreter Test ===");
             aspirina_gates::computer::interpreter::test();
         }
+        "17" => {
+            println!("=== Chord Progression Predictor ===");
+            aspirina_gates::music::test();
+        }
         "0" => println!("Goodbye!"),
-        _ => println!("Invalid selection. Please run again and choose 0-16."),
+        _ => println!("Invalid selection. Please run again and choose 0-17."),
     }
 }
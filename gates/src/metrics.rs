@@ -0,0 +1,243 @@
+//! Evaluation Metrics
+//!
+//! Functions that score a network's predictions against the expected values, so tests and
+//! demos can assert on a number instead of inspecting each output by hand.
+//!
+//! Every function takes `predictions` and `targets` as matrices of the same shape and compares
+//! them element by element; each element counts as one prediction. The output of
+//! [`NeuralNetwork::predict`](crate::neural_network::NeuralNetwork::predict) is
+//! `[samples, outputs]`, so targets written in the layout accepted by `train`
+//! (`[outputs, samples]`) need to be transposed first.
+//!
+//! # Classification
+//!
+//! A prediction is positive when it is above the decision `threshold` (usually 0.5) and a
+//! target is positive when it is above 0.5. From these the [`ConfusionMatrix`] counts true and
+//! false positives and negatives, which give [`accuracy`], [`precision`], [`recall`] and
+//! [`f1_score`]. [`roc_auc`] uses the raw scores instead and measures how well they rank the
+//! positive samples above the negative ones, independently of any threshold.
+//!
+//! # Regression
+//!
+//! - [`mean_squared_error`]: average of `(prediction - target)²`
+//! - [`r_squared`]: fraction of the target variance explained by the predictions
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::metrics::{accuracy, ConfusionMatrix};
+//! # use aspirina_core::matrix::Matrix;
+//! let predictions = Matrix::new(vec![vec![0.9], vec![0.2], vec![0.7], vec![0.4]]);
+//! let targets = Matrix::new(vec![vec![1.0], vec![0.0], vec![0.0], vec![0.0]]);
+//!
+//! assert_eq!(accuracy(&predictions, &targets, 0.5), 0.75);
+//!
+//! let confusion = ConfusionMatrix::new(&predictions, &targets, 0.5);
+//! assert_eq!(confusion.false_positives, 1);
+//! assert_eq!(confusion.precision(), 0.5);
+//! ```
+
+use aspirina_core::matrix::Matrix;
+use std::ops::AddAssign;
+
+/// Counts of correct and incorrect binary predictions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConfusionMatrix {
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub true_negatives: usize,
+    pub false_negatives: usize,
+}
+
+impl ConfusionMatrix {
+    /// Counts the predictions above `threshold` against the targets above 0.5.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `predictions` and `targets` have different shapes.
+    pub fn new(predictions: &Matrix, targets: &Matrix, threshold: f64) -> Self {
+        let mut confusion = Self::default();
+
+        for (prediction, target) in pairs(predictions, targets) {
+            match (prediction > threshold, target > 0.5) {
+                (true, true) => confusion.true_positives += 1,
+                (true, false) => confusion.false_positives += 1,
+                (false, false) => confusion.true_negatives += 1,
+                (false, true) => confusion.false_negatives += 1,
+            }
+        }
+
+        confusion
+    }
+
+    /// Total number of predictions counted.
+    pub fn total(&self) -> usize {
+        self.true_positives + self.false_positives + self.true_negatives + self.false_negatives
+    }
+
+    /// Fraction of predictions that were correct, or 0 when nothing was counted.
+    pub fn accuracy(&self) -> f64 {
+        ratio(self.true_positives + self.true_negatives, self.total())
+    }
+
+    /// Fraction of positive predictions that were correct, or 0 when nothing was predicted
+    /// positive.
+    pub fn precision(&self) -> f64 {
+        ratio(
+            self.true_positives,
+            self.true_positives + self.false_positives,
+        )
+    }
+
+    /// Fraction of positive targets that were found, or 0 when there were none.
+    pub fn recall(&self) -> f64 {
+        ratio(
+            self.true_positives,
+            self.true_positives + self.false_negatives,
+        )
+    }
+
+    /// Harmonic mean of precision and recall, or 0 when both are 0.
+    pub fn f1_score(&self) -> f64 {
+        let precision = self.precision();
+        let recall = self.recall();
+
+        if precision + recall == 0.0 {
+            0.0
+        } else {
+            2.0 * precision * recall / (precision + recall)
+        }
+    }
+}
+
+impl AddAssign for ConfusionMatrix {
+    fn add_assign(&mut self, other: Self) {
+        self.true_positives += other.true_positives;
+        self.false_positives += other.false_positives;
+        self.true_negatives += other.true_negatives;
+        self.false_negatives += other.false_negatives;
+    }
+}
+
+/// Fraction of predictions on the same side of `threshold` as their targets are of 0.5.
+pub fn accuracy(predictions: &Matrix, targets: &Matrix, threshold: f64) -> f64 {
+    ConfusionMatrix::new(predictions, targets, threshold).accuracy()
+}
+
+/// See [`ConfusionMatrix::precision`].
+pub fn precision(predictions: &Matrix, targets: &Matrix, threshold: f64) -> f64 {
+    ConfusionMatrix::new(predictions, targets, threshold).precision()
+}
+
+/// See [`ConfusionMatrix::recall`].
+pub fn recall(predictions: &Matrix, targets: &Matrix, threshold: f64) -> f64 {
+    ConfusionMatrix::new(predictions, targets, threshold).recall()
+}
+
+/// See [`ConfusionMatrix::f1_score`].
+pub fn f1_score(predictions: &Matrix, targets: &Matrix, threshold: f64) -> f64 {
+    ConfusionMatrix::new(predictions, targets, threshold).f1_score()
+}
+
+/// Area under the ROC curve of `scores` for the binary `targets`.
+///
+/// Equals the probability that a randomly chosen positive sample scores higher than a randomly
+/// chosen negative one, with ties counting one half. Returns `None` when the targets contain
+/// only one class, since the curve is undefined.
+pub fn roc_auc(scores: &Matrix, targets: &Matrix) -> Option<f64> {
+    let mut samples: Vec<(f64, bool)> = pairs(scores, targets)
+        .map(|(score, target)| (score, target > 0.5))
+        .collect();
+    samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let positives = samples.iter().filter(|(_, positive)| *positive).count();
+    let negatives = samples.len() - positives;
+    if positives == 0 || negatives == 0 {
+        return None;
+    }
+
+    // Sum the (1-based, tie-averaged) ranks of the positive samples.
+    let mut positive_rank_sum = 0.0;
+    let mut start = 0;
+    while start < samples.len() {
+        let mut end = start;
+        while end < samples.len() && samples[end].0 == samples[start].0 {
+            end += 1;
+        }
+
+        let average_rank = (start + end + 1) as f64 / 2.0;
+        let tied_positives = samples[start..end].iter().filter(|(_, p)| *p).count();
+        positive_rank_sum += average_rank * tied_positives as f64;
+        start = end;
+    }
+
+    let positives = positives as f64;
+    let u = positive_rank_sum - positives * (positives + 1.0) / 2.0;
+    Some(u / (positives * negatives as f64))
+}
+
+/// Average squared difference between predictions and targets.
+pub fn mean_squared_error(predictions: &Matrix, targets: &Matrix) -> f64 {
+    let (sum, count) = pairs(predictions, targets).fold((0.0, 0), |(sum, count), (p, t)| {
+        (sum + (p - t).powi(2), count + 1)
+    });
+
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f64
+    }
+}
+
+/// Coefficient of determination: `1 - SS_residual / SS_total`.
+///
+/// 1 is a perfect fit and 0 is no better than always predicting the mean target; worse
+/// predictions give negative values. When the targets are constant, returns 1 for a perfect
+/// fit and 0 otherwise.
+pub fn r_squared(predictions: &Matrix, targets: &Matrix) -> f64 {
+    let values: Vec<(f64, f64)> = pairs(predictions, targets).collect();
+    let mean = values.iter().map(|(_, t)| t).sum::<f64>() / values.len().max(1) as f64;
+
+    let residual: f64 = values.iter().map(|(p, t)| (t - p).powi(2)).sum();
+    let total: f64 = values.iter().map(|(_, t)| (t - mean).powi(2)).sum();
+
+    if total == 0.0 {
+        if residual == 0.0 {
+            1.0
+        } else {
+            0.0
+        }
+    } else {
+        1.0 - residual / total
+    }
+}
+
+/// Iterates over matching `(prediction, target)` elements.
+fn pairs<'a>(
+    predictions: &'a Matrix,
+    targets: &'a Matrix,
+) -> impl Iterator<Item = (f64, f64)> + 'a {
+    let same_shape = predictions.data.len() == targets.data.len()
+        && predictions
+            .data
+            .iter()
+            .zip(targets.data.iter())
+            .all(|(p, t)| p.len() == t.len());
+    if !same_shape {
+        panic!("Incompatible dimensions");
+    }
+
+    predictions
+        .data
+        .iter()
+        .zip(targets.data.iter())
+        .flat_map(|(p, t)| p.iter().copied().zip(t.iter().copied()))
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
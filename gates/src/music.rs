@@ -0,0 +1,563 @@
+//! Chord Progression Prediction
+//!
+//! Phase 6 of the roadmap: a neural network that suggests the next chord of a progression.
+//!
+//! # Chord Encoding
+//!
+//! A chord is a root pitch class and a quality, encoded in one byte as
+//! `quality × 12 + root`, with roots counted in semitones from C:
+//!
+//! | Quality     | Codes       | Examples                      |
+//! |-------------|-------------|-------------------------------|
+//! | Major       | 0x00 - 0x0B | C = 0x00, C# = 0x01, D = 0x02 |
+//! | Minor       | 0x0C - 0x17 | Cm = 0x0C, Am = 0x15          |
+//! | Dominant 7  | 0x18 - 0x23 | C7 = 0x18, G7 = 0x1F          |
+//! | Diminished  | 0x24 - 0x2F | Bdim = 0x2F                   |
+//!
+//! # Roman Numerals
+//!
+//! Progressions are usually written relative to the key: `I` is the chord on the first note
+//! of the scale, `V` the one on the fifth. Upper case numerals are major chords, lower case
+//! numerals minor ones; a `7` suffix makes a dominant seventh and `°` (or `dim`) a diminished
+//! chord. A `b` or `#` prefix lowers or raises the root by a semitone. In C major, I-V-vi-IV
+//! is C-G-Am-F, or roots `[0, 7, 9, 5]`.
+//!
+//! # Prediction
+//!
+//! [`MusicPredictor`] transposes every progression to C, so it learns functional harmony
+//! ("after ii and V comes I") once for all keys. Its input is the one-hot encoding of the
+//! last `context` chords plus the genre; its output has one unit per chord code, and the
+//! suggestions are the chords ranked by output. Progressions are treated as loops, so the
+//! chord after the last one is the first.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::music::{ChordProgression, Genre, MusicPredictor, JAZZ_TURNAROUND};
+//! let progression = ChordProgression::from_roman(JAZZ_TURNAROUND, 2, Genre::Jazz).unwrap();
+//! assert_eq!(progression.names(), vec!["Em", "A", "D"]);
+//!
+//! let predictor = MusicPredictor::new(2, 16, 42);
+//! predictor.train(&[progression.clone()], 2000).unwrap();
+//!
+//! let suggestions = predictor.suggest(&progression.prefix(2), 3).unwrap();
+//! assert_eq!(suggestions[0].chord.to_string(), "D");
+//! ```
+
+use crate::layer::Layer;
+use crate::neural_network::NeuralNetwork;
+use aspirina_core::matrix::Matrix;
+use aspirina_core::random::Rng;
+use std::fmt;
+
+/// Number of distinct chord codes.
+pub const CHORD_COUNT: usize = 48;
+
+/// The most common pop progression.
+pub const POP: &str = "I-V-vi-IV";
+/// The ii-V-I cadence of jazz standards.
+pub const JAZZ_TURNAROUND: &str = "ii-V-I";
+/// The twelve bars of a blues, in dominant sevenths.
+pub const TWELVE_BAR_BLUES: &str = "I7-I7-I7-I7-IV7-IV7-I7-I7-V7-IV7-I7-V7";
+
+const NOTE_NAMES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+const MAJOR_SCALE: [u8; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+/// The sound of a chord, independent of its root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quality {
+    Major,
+    Minor,
+    Dominant7,
+    Diminished,
+}
+
+/// A chord: root pitch class (0 = C ... 11 = B) and quality.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chord {
+    pub root: u8,
+    pub quality: Quality,
+}
+
+/// Musical style of a progression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Genre {
+    Pop = 0,
+    Jazz = 1,
+    Blues = 2,
+}
+
+/// A sequence of encoded chords in a key (0 = C ... 11 = B) and genre.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChordProgression {
+    pub chords: Vec<u8>,
+    pub key: u8,
+    pub genre: u8,
+}
+
+/// A ranked next-chord suggestion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Suggestion {
+    pub chord: Chord,
+    /// Network output for this chord, between 0 and 1
+    pub score: f64,
+}
+
+/// Neural network that suggests the next chord of a progression.
+#[derive(Debug)]
+pub struct MusicPredictor {
+    network: NeuralNetwork,
+    context: usize,
+}
+
+impl Quality {
+    const ALL: [Quality; 4] = [
+        Quality::Major,
+        Quality::Minor,
+        Quality::Dominant7,
+        Quality::Diminished,
+    ];
+
+    fn suffix(&self) -> &'static str {
+        match self {
+            Quality::Major => "",
+            Quality::Minor => "m",
+            Quality::Dominant7 => "7",
+            Quality::Diminished => "dim",
+        }
+    }
+}
+
+impl Chord {
+    /// Creates a chord.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root` is not below 12.
+    pub fn new(root: u8, quality: Quality) -> Self {
+        if root >= 12 {
+            panic!("Root must be a pitch class below 12");
+        }
+
+        Chord { root, quality }
+    }
+
+    /// Parses a chord name such as `C`, `F#m`, `Bb7` or `Bdim`.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        let name = name.trim();
+        let mut chars = name.chars();
+        let letter = chars.next().ok_or_else(|| "Empty chord name".to_string())?;
+        let natural = match letter {
+            'C' => 0,
+            'D' => 2,
+            'E' => 4,
+            'F' => 5,
+            'G' => 7,
+            'A' => 9,
+            'B' => 11,
+            _ => return Err(format!("Unknown chord {:?}", name)),
+        };
+
+        let rest = chars.as_str();
+        let (root, suffix) = if let Some(suffix) = rest.strip_prefix('#') {
+            ((natural + 1) % 12, suffix)
+        } else if let Some(suffix) = rest.strip_prefix('b') {
+            ((natural + 11) % 12, suffix)
+        } else {
+            (natural, rest)
+        };
+
+        let quality = match suffix {
+            "" | "maj" => Quality::Major,
+            "m" | "min" => Quality::Minor,
+            "7" => Quality::Dominant7,
+            "dim" | "°" => Quality::Diminished,
+            _ => return Err(format!("Unknown chord {:?}", name)),
+        };
+
+        Ok(Chord::new(root, quality))
+    }
+
+    /// The one-byte encoding, `quality × 12 + root`.
+    pub fn encode(&self) -> u8 {
+        let quality = Quality::ALL
+            .iter()
+            .position(|q| *q == self.quality)
+            .unwrap() as u8;
+        quality * 12 + self.root
+    }
+
+    /// Decodes a chord code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `code` is not below [`CHORD_COUNT`].
+    pub fn decode(code: u8) -> Self {
+        if code as usize >= CHORD_COUNT {
+            panic!("Unknown chord code {:#04x}", code);
+        }
+
+        Chord::new(code % 12, Quality::ALL[(code / 12) as usize])
+    }
+
+    /// Moves the root by `semitones`, up if positive.
+    pub fn transpose(&self, semitones: i32) -> Self {
+        Chord::new(
+            (self.root as i32 + semitones).rem_euclid(12) as u8,
+            self.quality,
+        )
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            NOTE_NAMES[self.root as usize],
+            self.quality.suffix()
+        )
+    }
+}
+
+impl Genre {
+    const ALL: [Genre; 3] = [Genre::Pop, Genre::Jazz, Genre::Blues];
+
+    /// The genre with the given code, if there is one.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Genre::ALL.get(code as usize).copied()
+    }
+}
+
+impl ChordProgression {
+    /// Creates a progression from chords.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not below 12.
+    pub fn new(chords: &[Chord], key: u8, genre: Genre) -> Self {
+        if key >= 12 {
+            panic!("Key must be a pitch class below 12");
+        }
+
+        ChordProgression {
+            chords: chords.iter().map(Chord::encode).collect(),
+            key,
+            genre: genre as u8,
+        }
+    }
+
+    /// Parses chord names separated by spaces or dashes, e.g. `"C G Am F"`.
+    pub fn parse(text: &str, key: u8, genre: Genre) -> Result<Self, String> {
+        let chords = split(text)
+            .map(Chord::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ChordProgression::new(&chords, key, genre))
+    }
+
+    /// Parses roman numerals relative to `key`, e.g. `"I-V-vi-IV"` or `"ii V7 I"`.
+    pub fn from_roman(text: &str, key: u8, genre: Genre) -> Result<Self, String> {
+        let chords = split(text)
+            .map(|numeral| parse_roman(numeral).map(|chord| chord.transpose(key as i32)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ChordProgression::new(&chords, key, genre))
+    }
+
+    /// The decoded chords.
+    pub fn to_chords(&self) -> Vec<Chord> {
+        self.chords
+            .iter()
+            .map(|code| Chord::decode(*code))
+            .collect()
+    }
+
+    /// The chord names, e.g. `["C", "G", "Am", "F"]`.
+    pub fn names(&self) -> Vec<String> {
+        self.to_chords().iter().map(Chord::to_string).collect()
+    }
+
+    /// The genre, if the code is known.
+    pub fn genre(&self) -> Option<Genre> {
+        Genre::from_code(self.genre)
+    }
+
+    /// The same progression moved by `semitones`, key included.
+    pub fn transpose(&self, semitones: i32) -> Self {
+        ChordProgression {
+            chords: self
+                .to_chords()
+                .iter()
+                .map(|chord| chord.transpose(semitones).encode())
+                .collect(),
+            key: (self.key as i32 + semitones).rem_euclid(12) as u8,
+            genre: self.genre,
+        }
+    }
+
+    /// The same progression moved to `key`.
+    pub fn to_key(&self, key: u8) -> Self {
+        self.transpose(key as i32 - self.key as i32)
+    }
+
+    /// The progression in all twelve keys, starting with C.
+    pub fn transpositions(&self) -> Vec<Self> {
+        (0..12).map(|key| self.to_key(key)).collect()
+    }
+
+    /// Checks that every chord code and the genre code are known.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(code) = self
+            .chords
+            .iter()
+            .find(|code| **code as usize >= CHORD_COUNT)
+        {
+            return Err(format!("Unknown chord code {:#04x}", code));
+        }
+        if self.genre().is_none() {
+            return Err(format!("Unknown genre code {}", self.genre));
+        }
+
+        Ok(())
+    }
+
+    /// The first `length` chords, in the same key and genre.
+    pub fn prefix(&self, length: usize) -> Self {
+        ChordProgression {
+            chords: self.chords[..length.min(self.chords.len())].to_vec(),
+            key: self.key,
+            genre: self.genre,
+        }
+    }
+}
+
+/// Builds next-chord training data from looping progressions.
+///
+/// Every progression is transposed to C. Each sample is the one-hot encoding of `context`
+/// consecutive chords followed by the genre, and its target is the one-hot encoding of the
+/// chord after them. Returns the input as `[samples × features]` and the targets in the
+/// `[outputs × samples]` layout of [`NeuralNetwork::train`], or an error if a progression is
+/// empty or fails [`ChordProgression::validate`].
+///
+/// # Panics
+///
+/// Panics if `context` is zero.
+pub fn training_data(
+    progressions: &[ChordProgression],
+    context: usize,
+) -> Result<(Matrix, Matrix), String> {
+    if context == 0 {
+        panic!("Context must be greater than zero");
+    }
+
+    let mut inputs = Vec::new();
+    let mut targets = Vec::new();
+
+    for progression in progressions {
+        if progression.chords.is_empty() {
+            return Err("Progression must not be empty".to_string());
+        }
+        progression.validate()?;
+
+        let normalized = progression.to_key(0);
+        let length = normalized.chords.len();
+        for start in 0..length {
+            let previous: Vec<u8> = (0..context)
+                .map(|offset| normalized.chords[(start + offset) % length])
+                .collect();
+            inputs.push(features(&previous, progression.genre));
+            targets.push(one_hot(
+                normalized.chords[(start + context) % length],
+                CHORD_COUNT,
+            ));
+        }
+    }
+
+    Ok((Matrix::new(inputs), Matrix::new(targets).transpose()))
+}
+
+impl MusicPredictor {
+    /// Creates an untrained predictor that looks at the last `context` chords.
+    pub fn new(context: usize, hidden_size: usize, seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        let inputs = context * CHORD_COUNT + Genre::ALL.len();
+        let layers = vec![
+            random_layer(hidden_size, inputs, &mut rng),
+            random_layer(CHORD_COUNT, hidden_size, &mut rng),
+        ];
+
+        MusicPredictor {
+            network: NeuralNetwork::new(layers).with_learning_rate(2.0),
+            context,
+        }
+    }
+
+    /// Number of previous chords the predictor looks at.
+    pub fn context(&self) -> usize {
+        self.context
+    }
+
+    /// Trains on `progressions` for `epochs` full-batch steps and returns the final loss.
+    ///
+    /// Fails without training if [`training_data`] rejects the progressions.
+    pub fn train(&self, progressions: &[ChordProgression], epochs: usize) -> Result<f64, String> {
+        let (input, targets) = training_data(progressions, self.context)?;
+        let mut loss = f64::NAN;
+
+        for _ in 0..epochs {
+            loss = self.network.train(input.clone(), targets.clone()).loss;
+        }
+
+        Ok(loss)
+    }
+
+    /// Every chord ranked by how likely it is to follow `progression`, best first.
+    ///
+    /// Only the last `context` chords of the progression are used.
+    pub fn predict(&self, progression: &ChordProgression) -> Result<Vec<Suggestion>, String> {
+        progression.validate()?;
+        if progression.chords.len() < self.context {
+            return Err(format!(
+                "Expected at least {} chords, found {}",
+                self.context,
+                progression.chords.len()
+            ));
+        }
+
+        let normalized = progression.to_key(0);
+        let previous = &normalized.chords[normalized.chords.len() - self.context..];
+        let output = self
+            .network
+            .predict(Matrix::new(vec![features(previous, progression.genre)]));
+
+        let mut suggestions: Vec<Suggestion> = output.data[0]
+            .iter()
+            .enumerate()
+            .map(|(code, score)| Suggestion {
+                chord: Chord::decode(code as u8).transpose(progression.key as i32),
+                score: *score,
+            })
+            .collect();
+        suggestions.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+        Ok(suggestions)
+    }
+
+    /// The `count` best suggestions for the chord after `progression`.
+    pub fn suggest(
+        &self,
+        progression: &ChordProgression,
+        count: usize,
+    ) -> Result<Vec<Suggestion>, String> {
+        let mut suggestions = self.predict(progression)?;
+        suggestions.truncate(count);
+        Ok(suggestions)
+    }
+}
+
+fn split(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| c == '-' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+}
+
+fn parse_roman(numeral: &str) -> Result<Chord, String> {
+    let unknown = || format!("Unknown roman numeral {:?}", numeral);
+
+    let (shift, rest) = if let Some(rest) = numeral.strip_prefix('b') {
+        (-1, rest)
+    } else if let Some(rest) = numeral.strip_prefix('#') {
+        (1, rest)
+    } else {
+        (0, numeral)
+    };
+
+    let end = rest
+        .find(|c: char| !matches!(c, 'I' | 'V' | 'i' | 'v'))
+        .unwrap_or(rest.len());
+    let (letters, suffix) = rest.split_at(end);
+
+    let degree = match letters.to_uppercase().as_str() {
+        "I" => 0,
+        "II" => 1,
+        "III" => 2,
+        "IV" => 3,
+        "V" => 4,
+        "VI" => 5,
+        "VII" => 6,
+        _ => return Err(unknown()),
+    };
+    let upper = letters.chars().all(|c| c.is_ascii_uppercase());
+    let lower = letters.chars().all(|c| c.is_ascii_lowercase());
+
+    let quality = match (suffix, upper, lower) {
+        ("", true, _) => Quality::Major,
+        ("", _, true) => Quality::Minor,
+        ("7", true, _) => Quality::Dominant7,
+        ("°" | "dim", _, true) => Quality::Diminished,
+        _ => return Err(unknown()),
+    };
+
+    Ok(Chord::new(MAJOR_SCALE[degree], quality).transpose(shift))
+}
+
+fn features(previous: &[u8], genre: u8) -> Vec<f64> {
+    let mut features: Vec<f64> = previous
+        .iter()
+        .flat_map(|code| one_hot(*code, CHORD_COUNT))
+        .collect();
+    features.extend(one_hot(genre, Genre::ALL.len()));
+    features
+}
+
+fn one_hot(index: u8, size: usize) -> Vec<f64> {
+    let mut row = vec![0.0; size];
+    row[index as usize] = 1.0;
+    row
+}
+
+fn random_layer(outputs: usize, inputs: usize, rng: &mut Rng) -> Layer {
+    let limit = 1.0 / (inputs as f64).sqrt();
+    Layer::new(Matrix::new(
+        (0..outputs)
+            .map(|_| (0..inputs).map(|_| rng.range(-limit, limit)).collect())
+            .collect(),
+    ))
+}
+
+/// Trains a predictor on a few classic progressions and prints its suggestions
+pub fn test() {
+    let progressions = vec![
+        ChordProgression::from_roman(POP, 0, Genre::Pop).unwrap(),
+        ChordProgression::from_roman(JAZZ_TURNAROUND, 0, Genre::Jazz).unwrap(),
+        ChordProgression::from_roman(TWELVE_BAR_BLUES, 0, Genre::Blues).unwrap(),
+    ];
+
+    let predictor = MusicPredictor::new(2, 32, 42);
+    let loss = predictor.train(&progressions, 3000).unwrap();
+    println!("Final loss: {:.4}", loss);
+    println!();
+
+    let prompts = [
+        ("C G", Genre::Pop, 0),
+        ("Am D", Genre::Jazz, 7),
+        ("E7 E7", Genre::Blues, 4),
+    ];
+    for (prompt, genre, key) in prompts {
+        let progression = ChordProgression::parse(prompt, key, genre).unwrap();
+        let suggestions = predictor.suggest(&progression, 3).unwrap();
+        let ranked: Vec<String> = suggestions
+            .iter()
+            .map(|s| format!("{} ({:.2})", s.chord, s.score))
+            .collect();
+        println!(
+            "{:?} in {} after {}: {}",
+            genre,
+            NOTE_NAMES[key as usize],
+            prompt,
+            ranked.join(", ")
+        );
+    }
+}
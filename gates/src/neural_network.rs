@@ -50,11 +50,24 @@
 //! ```
 
 use crate::layer::Layer;
-use aspirina_core::calc::Calc;
+use crate::regularization::Regularization;
 use aspirina_core::matrix::Matrix;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
+/// Summary of a single call to [`NeuralNetwork::train`].
+///
+/// All values describe the batch as seen *before* the weights were updated.
+#[derive(Debug, Clone)]
+pub struct TrainingStep {
+    /// Network output for the batch, shape `[batch_size, output_features]`
+    pub output: Matrix,
+    /// Loss for the batch, as computed by [`NeuralNetwork::loss`]
+    pub loss: f64,
+    /// Euclidean norm of the gradient over every trainable parameter
+    pub gradient_norm: f64,
+}
+
 /// A feedforward neural network with backpropagation training capabilities.
 ///
 /// The `NeuralNetwork` struct represents a multi-layer neural network that can learn
@@ -85,11 +98,24 @@ use std::rc::Rc;
 /// # Fields
 ///
 /// - `layers`: Vector of neural network layers wrapped in smart pointers
+/// - `regularization`: L1/L2 penalties applied to the weights on every update
+/// - `learning_rate`: Step size multiplying every parameter update
+/// - `training`: Whether layers such as dropout behave as in training or inference
 #[derive(Debug, Clone)]
 pub struct NeuralNetwork {
     /// The neural network layers, each containing weights and optional forwarded results.
     /// Uses `Rc<RefCell<>>` for shared ownership and interior mutability during training.
     layers: Vec<Rc<RefCell<Layer>>>,
+
+    /// Weight penalties folded into the loss and every weight update.
+    regularization: Regularization,
+
+    /// Step size applied to every parameter update. Uses `Cell` so schedules can change it
+    /// between training steps without exclusive access to the network.
+    learning_rate: Cell<f64>,
+
+    /// `true` in training mode (the default), `false` in evaluation mode.
+    training: Cell<bool>,
 }
 
 impl NeuralNetwork {
@@ -143,9 +169,113 @@ impl NeuralNetwork {
                 .into_iter()
                 .map(|layer| Rc::new(RefCell::new(layer)))
                 .collect(),
+            regularization: Regularization::none(),
+            learning_rate: Cell::new(1.0),
+            training: Cell::new(true),
         }
     }
 
+    /// Sets the L1/L2 penalties applied to the weights during training.
+    ///
+    /// The penalty is added to [`loss`](Self::loss) and its gradient is subtracted from every
+    /// weight update, shrinking weights that the data does not support.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aspirina_gates::neural_network::NeuralNetwork;
+    /// # use aspirina_gates::layer::Layer;
+    /// # use aspirina_gates::regularization::Regularization;
+    /// # use aspirina_core::matrix::Matrix;
+    /// let network = NeuralNetwork::new(vec![Layer::new(Matrix::new(vec![vec![0.5, 0.5]]))])
+    ///     .with_regularization(Regularization::l2(0.001));
+    /// ```
+    pub fn with_regularization(mut self, regularization: Regularization) -> Self {
+        self.regularization = regularization;
+        self
+    }
+
+    /// The penalties applied to the weights during training.
+    pub fn regularization(&self) -> Regularization {
+        self.regularization
+    }
+
+    /// Sets the initial learning rate (defaults to `1.0`).
+    ///
+    /// Every parameter update is scaled by the learning rate. Smaller values make training
+    /// slower but steadier; [`LrSchedule`](crate::lr_schedule::LrSchedule)s change it over time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aspirina_gates::neural_network::NeuralNetwork;
+    /// # use aspirina_gates::layer::Layer;
+    /// # use aspirina_core::matrix::Matrix;
+    /// let network = NeuralNetwork::new(vec![Layer::new(Matrix::new(vec![vec![0.5, 0.5]]))])
+    ///     .with_learning_rate(0.5);
+    ///
+    /// assert_eq!(network.learning_rate(), 0.5);
+    /// ```
+    pub fn with_learning_rate(self, learning_rate: f64) -> Self {
+        self.learning_rate.set(learning_rate);
+        self
+    }
+
+    /// The current learning rate.
+    pub fn learning_rate(&self) -> f64 {
+        self.learning_rate.get()
+    }
+
+    /// Changes the learning rate used by subsequent training steps.
+    pub fn set_learning_rate(&self, learning_rate: f64) {
+        self.learning_rate.set(learning_rate);
+    }
+
+    /// Switches the network to evaluation mode.
+    ///
+    /// In evaluation mode, layers that only make sense while learning (such as dropout)
+    /// pass their input straight through during [`train`](Self::train), [`loss`](Self::loss)
+    /// and [`gradients`](Self::gradients). [`predict`](Self::predict) always runs in
+    /// inference mode, whatever the mode.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aspirina_gates::neural_network::NeuralNetwork;
+    /// # use aspirina_gates::layer::Layer;
+    /// # use aspirina_core::matrix::Matrix;
+    /// let network = NeuralNetwork::new(vec![
+    ///     Layer::new(Matrix::new(vec![vec![0.1, 0.2], vec![0.3, 0.4]])),
+    ///     Layer::dropout(0.5, 7),
+    ///     Layer::new(Matrix::new(vec![vec![0.5, 0.6]])),
+    /// ]);
+    ///
+    /// let input = Matrix::new(vec![vec![1.0, 0.0]]);
+    /// let targets = Matrix::new(vec![vec![1.0]]);
+    ///
+    /// network.eval();
+    /// assert_eq!(
+    ///     network.loss(input.clone(), targets.clone()),
+    ///     network.loss(input, targets)
+    /// );
+    ///
+    /// network.train_mode();
+    /// assert!(network.is_training());
+    /// ```
+    pub fn eval(&self) {
+        self.training.set(false);
+    }
+
+    /// Switches the network back to training mode (the default).
+    pub fn train_mode(&self) {
+        self.training.set(true);
+    }
+
+    /// Returns `true` while the network is in training mode.
+    pub fn is_training(&self) -> bool {
+        self.training.get()
+    }
+
     /// Trains the neural network using a single training batch.
     ///
     /// This method performs one complete training iteration using the backpropagation
@@ -172,6 +302,11 @@ impl NeuralNetwork {
     ///   - Each column represents an output feature  
     ///   - Shape: `[batch_size, output_features]`
     ///
+    /// # Returns
+    ///
+    /// A [`TrainingStep`] describing the batch *before* the update: the network's output,
+    /// its loss and the norm of the gradient that was applied.
+    ///
     /// # Training Strategy
     ///
     /// For effective training, call this method repeatedly with:
@@ -206,9 +341,165 @@ impl NeuralNetwork {
     ///     network.train(input.clone(), targets.clone());
     /// }
     /// ```
-    pub fn train(&self, input: Matrix, targets: Matrix) {
-        let forwarded = self.forward_propagation(input.clone());
-        self.back_propagation(forwarded, input, targets);
+    pub fn train(&self, input: Matrix, targets: Matrix) -> TrainingStep {
+        let forwarded = self.forward_propagation(input.clone(), self.is_training());
+        let output = forwarded.last().unwrap().clone();
+        let loss = self.loss_for_output(&output, &targets);
+
+        let adjustments = self.back_propagation(forwarded, input, targets);
+        let gradient_norm = adjustments
+            .iter()
+            .flatten()
+            .flat_map(|adjustment| adjustment.data.iter().flatten())
+            .map(|value| value * value)
+            .sum::<f64>()
+            .sqrt();
+
+        for (layer, adjustment) in self.layers.iter().zip(adjustments) {
+            self.adjust(layer.clone(), adjustment);
+        }
+
+        TrainingStep {
+            output,
+            loss,
+            gradient_norm,
+        }
+    }
+
+    /// Computes the loss minimised by [`train`](Self::train) for the given batch.
+    ///
+    /// The loss is half the sum of squared errors, `½ Σ (target - output)²`, plus the
+    /// regularization penalty on the weights. This is the function whose gradient the
+    /// backpropagation update follows.
+    ///
+    /// # Parameters
+    ///
+    /// * `input` - Input data matrix, shape `[batch_size, input_features]`
+    /// * `targets` - Target matrix in the same layout accepted by `train`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aspirina_gates::neural_network::NeuralNetwork;
+    /// # use aspirina_gates::layer::Layer;
+    /// # use aspirina_core::matrix::Matrix;
+    /// let network = NeuralNetwork::new(vec![Layer::new(Matrix::new(vec![vec![0.0, 0.0]]))]);
+    ///
+    /// // Untrained output is sigmoid(0) = 0.5, so the loss is ½ · 0.5²
+    /// let loss = network.loss(
+    ///     Matrix::new(vec![vec![1.0, 1.0]]),
+    ///     Matrix::new(vec![vec![1.0]]),
+    /// );
+    /// assert_eq!(loss, 0.125);
+    /// ```
+    pub fn loss(&self, input: Matrix, targets: Matrix) -> f64 {
+        let forwarded = self.forward_propagation(input, self.is_training());
+        self.loss_for_output(forwarded.last().unwrap(), &targets)
+    }
+
+    fn loss_for_output(&self, output: &Matrix, targets: &Matrix) -> f64 {
+        let error = targets.transpose() - output.clone();
+
+        let squared_error = 0.5
+            * error
+                .data
+                .iter()
+                .flat_map(|row| row.iter())
+                .map(|value| value * value)
+                .sum::<f64>();
+
+        let penalty: f64 = self
+            .layers
+            .iter()
+            .filter_map(|layer| {
+                let layer = layer.borrow();
+                layer
+                    .weights()
+                    .map(|weights| self.regularization.penalty(weights))
+            })
+            .sum();
+
+        squared_error + penalty
+    }
+
+    /// Computes the gradient of [`loss`](Self::loss) with respect to every trainable parameter.
+    ///
+    /// The gradients are computed with the same backward pass used by `train`, but the
+    /// parameters are left untouched. The result mirrors [`parameters`](Self::parameters):
+    /// one entry per layer, each holding one matrix per parameter of that layer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aspirina_gates::neural_network::NeuralNetwork;
+    /// # use aspirina_gates::layer::Layer;
+    /// # use aspirina_core::matrix::Matrix;
+    /// let network = NeuralNetwork::new(vec![
+    ///     Layer::new(Matrix::new(vec![vec![0.1, 0.2], vec![0.3, 0.4]])),
+    ///     Layer::new(Matrix::new(vec![vec![0.5, 0.6]])),
+    /// ]);
+    ///
+    /// let input = Matrix::new(vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+    /// let targets = Matrix::new(vec![vec![1.0, 1.0]]);
+    /// let gradients = network.gradients(input, targets);
+    ///
+    /// assert_eq!(gradients.len(), 2);
+    /// assert_eq!(gradients[0][0].data.len(), 2);
+    /// assert_eq!(gradients[1][0].data[0].len(), 2);
+    /// ```
+    pub fn gradients(&self, input: Matrix, targets: Matrix) -> Vec<Vec<Matrix>> {
+        let forwarded = self.forward_propagation(input.clone(), self.is_training());
+
+        self.back_propagation(forwarded, input, targets)
+            .into_iter()
+            .map(|adjustments| {
+                adjustments
+                    .into_iter()
+                    .map(|adjustment| adjustment.scale(-1.0))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns a copy of every layer's trainable parameters, ordered from input to output.
+    ///
+    /// See [`Layer::parameters`] for the parameters of each layer kind.
+    pub fn parameters(&self) -> Vec<Vec<Matrix>> {
+        self.layers
+            .iter()
+            .map(|layer| layer.borrow().parameters())
+            .collect()
+    }
+
+    /// Returns copies of every layer, including state that is not a trainable parameter, such as
+    /// batch normalization running statistics and dropout random state.
+    pub(crate) fn snapshot(&self) -> Vec<Layer> {
+        self.layers
+            .iter()
+            .map(|layer| layer.borrow().clone())
+            .collect()
+    }
+
+    /// Restores every layer from a [`snapshot`](Self::snapshot) of this network.
+    pub(crate) fn restore(&self, snapshot: Vec<Layer>) {
+        for (layer, saved) in self.layers.iter().zip(snapshot) {
+            *layer.borrow_mut() = saved;
+        }
+    }
+
+    /// Replaces every layer's trainable parameters, ordered from input to output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of entries does not match the number of layers.
+    pub fn set_parameters(&self, parameters: Vec<Vec<Matrix>>) {
+        if parameters.len() != self.layers.len() {
+            panic!("Expected one parameter entry per layer");
+        }
+
+        for (layer, layer_parameters) in self.layers.iter().zip(parameters) {
+            layer.borrow_mut().set_parameters(layer_parameters);
+        }
     }
 
     /// Makes predictions on new input data using the trained neural network.
@@ -216,6 +507,10 @@ impl NeuralNetwork {
     /// This method performs forward propagation through all network layers without
     /// updating weights. It's used for inference after the network has been trained.
     ///
+    /// Predictions always run in inference mode, even while the network is in training
+    /// mode: dropout passes its input through and batch normalization uses its running
+    /// statistics without updating them.
+    ///
     /// # Process
     ///
     /// 1. **Forward Pass**: Input flows through each layer sequentially
@@ -278,57 +573,68 @@ impl NeuralNetwork {
     /// }
     /// ```
     pub fn predict(&self, input: Matrix) -> Matrix {
-        self.forward_propagation(input).last().unwrap().clone()
+        self.forward_propagation(input, false)
+            .last()
+            .unwrap()
+            .clone()
     }
 
-    fn forward_propagation(&self, input: Matrix) -> Vec<Matrix> {
+    fn forward_propagation(&self, input: Matrix, training: bool) -> Vec<Matrix> {
         let mut forwarded = Vec::new();
         let mut input = input;
 
         for layer in &self.layers {
-            self.apply_activation(input.clone(), layer.clone());
-            input = layer.borrow().forwarded.clone().unwrap();
+            input = layer.borrow_mut().forward(&input, training);
             forwarded.push(input.clone());
         }
 
         forwarded
     }
 
-    fn back_propagation(&self, forwarded: Vec<Matrix>, input: Matrix, targets: Matrix) {
+    fn back_propagation(
+        &self,
+        forwarded: Vec<Matrix>,
+        input: Matrix,
+        targets: Matrix,
+    ) -> Vec<Vec<Matrix>> {
         let mut error = targets.transpose() - forwarded.last().unwrap().clone();
+        let mut adjustments = Vec::with_capacity(self.layers.len());
 
         for (idx, layer) in self.layers.iter().enumerate().rev() {
             let input_to_layer = if idx == 0 {
-                input.clone()
+                &input
             } else {
-                forwarded[idx - 1].clone()
+                &forwarded[idx - 1]
             };
 
-            let delta = Matrix::naive_multiply(forwarded[idx].clone().derivative(), error.clone());
+            let layer = layer.borrow();
+            let (previous_error, mut layer_adjustments) = layer.backward(input_to_layer, error);
 
-            if idx > 0 {
-                error = delta.clone() * layer.borrow().matrix.clone();
+            if let Some(weights) = layer.weights() {
+                if !self.regularization.is_none() {
+                    layer_adjustments[0] =
+                        layer_adjustments[0].clone() - self.regularization.gradient(weights);
+                }
             }
 
-            self.adjust(input_to_layer, layer.clone(), delta);
+            error = previous_error;
+            adjustments.push(layer_adjustments);
         }
+
+        adjustments.reverse();
+        adjustments
     }
 
-    fn apply_activation(&self, input: Matrix, layer: Rc<RefCell<Layer>>) {
+    fn adjust(&self, layer: Rc<RefCell<Layer>>, adjustments: Vec<Matrix>) {
         let mut layer_borrow = layer.borrow_mut();
 
-        layer_borrow.forwarded = Some(Matrix::new(
-            (input * layer_borrow.matrix.transpose())
-                .data
-                .iter()
-                .map(|row| row.iter().map(Calc::sigmoid).collect())
-                .collect(),
-        ))
-    }
+        let parameters = layer_borrow
+            .parameters()
+            .into_iter()
+            .zip(adjustments)
+            .map(|(parameter, adjustment)| parameter + adjustment.scale(self.learning_rate()))
+            .collect();
 
-    fn adjust(&self, input: Matrix, layer: Rc<RefCell<Layer>>, delta: Matrix) {
-        let adjustment = input.transpose() * delta.clone();
-        let mut layer_borrow = layer.borrow_mut();
-        layer_borrow.matrix = layer_borrow.matrix.clone() + adjustment.transpose()
+        layer_borrow.set_parameters(parameters);
     }
 }
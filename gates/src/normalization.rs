@@ -0,0 +1,318 @@
+//! Normalization Layers
+//!
+//! Stacks of sigmoid layers drift into the flat regions of the sigmoid, where the derivative
+//! is nearly zero and learning stalls. Normalization layers rescale activations to zero mean and
+//! unit variance, then apply a learned scale (`gamma`) and shift (`beta`) so the network can
+//! still choose the range it needs.
+//!
+//! # Layer Kinds
+//!
+//! - **Batch normalization**: normalizes each feature (column) across the samples in a batch.
+//!   While training it keeps a running mean and variance, which replace the batch statistics in
+//!   evaluation mode so single-sample predictions are stable.
+//! - **Layer normalization**: normalizes each sample (row) across its features. It behaves the
+//!   same in training and evaluation, and does not depend on the batch size.
+//!
+//! `gamma` and `beta` are trainable parameters and are updated by the same step as dense weights.
+
+use aspirina_core::matrix::Matrix;
+
+const EPSILON: f64 = 1e-5;
+
+/// Values saved by the forward pass for use in the backward pass.
+#[derive(Debug, Clone)]
+struct NormalizationCache {
+    normalized: Matrix,
+    inverse_std: Vec<f64>,
+    batch_statistics: bool,
+}
+
+/// Batch normalization over the features of a `[batch_size, features]` matrix.
+#[derive(Debug, Clone)]
+pub struct BatchNorm {
+    /// Learned scale, shape `1 × features`
+    pub gamma: Matrix,
+    /// Learned shift, shape `1 × features`
+    pub beta: Matrix,
+    /// Exponential moving average of the batch means
+    pub running_mean: Vec<f64>,
+    /// Exponential moving average of the batch variances
+    pub running_variance: Vec<f64>,
+    /// Weight given to the newest batch when updating the running statistics
+    pub momentum: f64,
+    cache: Option<NormalizationCache>,
+}
+
+impl BatchNorm {
+    /// Creates a batch normalization layer for `features` inputs with `gamma = 1`, `beta = 0`.
+    pub fn new(features: usize) -> Self {
+        Self {
+            gamma: Matrix::new(vec![vec![1.0; features]]),
+            beta: Matrix::new(vec![vec![0.0; features]]),
+            running_mean: vec![0.0; features],
+            running_variance: vec![1.0; features],
+            momentum: 0.1,
+            cache: None,
+        }
+    }
+
+    pub(crate) fn forward(&mut self, input: &Matrix, training: bool) -> Matrix {
+        let features = input.data[0].len();
+
+        let (mean, variance) = if training {
+            let (mean, variance) = column_statistics(input);
+
+            for j in 0..features {
+                self.running_mean[j] =
+                    (1.0 - self.momentum) * self.running_mean[j] + self.momentum * mean[j];
+                self.running_variance[j] =
+                    (1.0 - self.momentum) * self.running_variance[j] + self.momentum * variance[j];
+            }
+
+            (mean, variance)
+        } else {
+            (self.running_mean.clone(), self.running_variance.clone())
+        };
+
+        let inverse_std: Vec<f64> = variance
+            .iter()
+            .map(|v| 1.0 / (v + EPSILON).sqrt())
+            .collect();
+
+        let normalized = Matrix::new(
+            input
+                .data
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .enumerate()
+                        .map(|(j, x)| (x - mean[j]) * inverse_std[j])
+                        .collect()
+                })
+                .collect(),
+        );
+
+        let output = affine(&normalized, &self.gamma, &self.beta);
+
+        self.cache = Some(NormalizationCache {
+            normalized,
+            inverse_std,
+            batch_statistics: training,
+        });
+
+        output
+    }
+
+    pub(crate) fn backward(&self, error: Matrix) -> (Matrix, Vec<Matrix>) {
+        let cache = self.cache.as_ref().unwrap();
+        let (gamma_adjustment, beta_adjustment) = affine_adjustments(&error, &cache.normalized);
+
+        let batch_size = error.data.len() as f64;
+        let features = error.data[0].len();
+        let normalized_error = scale_columns(&error, &self.gamma);
+
+        let previous_error = if cache.batch_statistics {
+            let mut sum_error = vec![0.0; features];
+            let mut sum_error_normalized = vec![0.0; features];
+
+            for (error_row, normalized_row) in normalized_error
+                .data
+                .iter()
+                .zip(cache.normalized.data.iter())
+            {
+                for j in 0..features {
+                    sum_error[j] += error_row[j];
+                    sum_error_normalized[j] += error_row[j] * normalized_row[j];
+                }
+            }
+
+            Matrix::new(
+                normalized_error
+                    .data
+                    .iter()
+                    .zip(cache.normalized.data.iter())
+                    .map(|(error_row, normalized_row)| {
+                        (0..features)
+                            .map(|j| {
+                                cache.inverse_std[j] / batch_size
+                                    * (batch_size * error_row[j]
+                                        - sum_error[j]
+                                        - normalized_row[j] * sum_error_normalized[j])
+                            })
+                            .collect()
+                    })
+                    .collect(),
+            )
+        } else {
+            Matrix::new(
+                normalized_error
+                    .data
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .enumerate()
+                            .map(|(j, e)| e * cache.inverse_std[j])
+                            .collect()
+                    })
+                    .collect(),
+            )
+        };
+
+        (previous_error, vec![gamma_adjustment, beta_adjustment])
+    }
+}
+
+/// Layer normalization over the features of each sample.
+#[derive(Debug, Clone)]
+pub struct LayerNorm {
+    /// Learned scale, shape `1 × features`
+    pub gamma: Matrix,
+    /// Learned shift, shape `1 × features`
+    pub beta: Matrix,
+    cache: Option<NormalizationCache>,
+}
+
+impl LayerNorm {
+    /// Creates a layer normalization layer for `features` inputs with `gamma = 1`, `beta = 0`.
+    pub fn new(features: usize) -> Self {
+        Self {
+            gamma: Matrix::new(vec![vec![1.0; features]]),
+            beta: Matrix::new(vec![vec![0.0; features]]),
+            cache: None,
+        }
+    }
+
+    pub(crate) fn forward(&mut self, input: &Matrix) -> Matrix {
+        let mut inverse_std = Vec::with_capacity(input.data.len());
+
+        let normalized = Matrix::new(
+            input
+                .data
+                .iter()
+                .map(|row| {
+                    let count = row.len() as f64;
+                    let mean = row.iter().sum::<f64>() / count;
+                    let variance = row.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count;
+                    let row_inverse_std = 1.0 / (variance + EPSILON).sqrt();
+                    inverse_std.push(row_inverse_std);
+
+                    row.iter().map(|x| (x - mean) * row_inverse_std).collect()
+                })
+                .collect(),
+        );
+
+        let output = affine(&normalized, &self.gamma, &self.beta);
+
+        self.cache = Some(NormalizationCache {
+            normalized,
+            inverse_std,
+            batch_statistics: true,
+        });
+
+        output
+    }
+
+    pub(crate) fn backward(&self, error: Matrix) -> (Matrix, Vec<Matrix>) {
+        let cache = self.cache.as_ref().unwrap();
+        let (gamma_adjustment, beta_adjustment) = affine_adjustments(&error, &cache.normalized);
+        let normalized_error = scale_columns(&error, &self.gamma);
+
+        let previous_error = Matrix::new(
+            normalized_error
+                .data
+                .iter()
+                .zip(cache.normalized.data.iter())
+                .zip(cache.inverse_std.iter())
+                .map(|((error_row, normalized_row), inverse_std)| {
+                    let count = error_row.len() as f64;
+                    let sum_error: f64 = error_row.iter().sum();
+                    let sum_error_normalized: f64 = error_row
+                        .iter()
+                        .zip(normalized_row.iter())
+                        .map(|(e, n)| e * n)
+                        .sum();
+
+                    error_row
+                        .iter()
+                        .zip(normalized_row.iter())
+                        .map(|(e, n)| {
+                            inverse_std / count * (count * e - sum_error - n * sum_error_normalized)
+                        })
+                        .collect()
+                })
+                .collect(),
+        );
+
+        (previous_error, vec![gamma_adjustment, beta_adjustment])
+    }
+}
+
+/// Per-column mean and (biased) variance of a `[batch_size, features]` matrix.
+fn column_statistics(input: &Matrix) -> (Vec<f64>, Vec<f64>) {
+    let count = input.data.len() as f64;
+    let features = input.data[0].len();
+
+    let mut mean = vec![0.0; features];
+    for row in &input.data {
+        for (j, x) in row.iter().enumerate() {
+            mean[j] += x / count;
+        }
+    }
+
+    let mut variance = vec![0.0; features];
+    for row in &input.data {
+        for (j, x) in row.iter().enumerate() {
+            variance[j] += (x - mean[j]).powi(2) / count;
+        }
+    }
+
+    (mean, variance)
+}
+
+/// Computes `gamma · normalized + beta`, broadcasting the `1 × features` parameters over rows.
+fn affine(normalized: &Matrix, gamma: &Matrix, beta: &Matrix) -> Matrix {
+    Matrix::new(
+        normalized
+            .data
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, x)| gamma.data[0][j] * x + beta.data[0][j])
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+/// Multiplies every row of `matrix` element-wise by the `1 × features` row vector `scale`.
+fn scale_columns(matrix: &Matrix, scale: &Matrix) -> Matrix {
+    Matrix::new(
+        matrix
+            .data
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, x)| x * scale.data[0][j])
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+/// Adjustments for `gamma` and `beta` given the error flowing into the affine step.
+fn affine_adjustments(error: &Matrix, normalized: &Matrix) -> (Matrix, Matrix) {
+    let features = error.data[0].len();
+    let mut gamma = vec![0.0; features];
+    let mut beta = vec![0.0; features];
+
+    for (error_row, normalized_row) in error.data.iter().zip(normalized.data.iter()) {
+        for j in 0..features {
+            gamma[j] += error_row[j] * normalized_row[j];
+            beta[j] += error_row[j];
+        }
+    }
+
+    (Matrix::new(vec![gamma]), Matrix::new(vec![beta]))
+}
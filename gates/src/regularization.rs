@@ -0,0 +1,99 @@
+//! Weight Regularization
+//!
+//! Regularization discourages large weights by adding a penalty to the training loss. The
+//! penalty's gradient is folded into every weight update, so weights that are not needed to
+//! fit the data decay towards zero instead of memorising noise.
+//!
+//! # Penalties
+//!
+//! - **L1** (`λ₁ Σ |w|`): pushes weights towards exactly zero, producing sparse layers
+//! - **L2** (`½ λ₂ Σ w²`): shrinks every weight in proportion to its size (weight decay)
+//!
+//! Both penalties can be combined. Only dense weight matrices are penalised; layers without
+//! weights, such as dropout, are left alone.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::regularization::Regularization;
+//! # use aspirina_core::matrix::Matrix;
+//! let regularization = Regularization::l2(0.01);
+//! let weights = Matrix::new(vec![vec![1.0, -2.0]]);
+//!
+//! assert_eq!(regularization.penalty(&weights), 0.025);
+//! assert_eq!(regularization.gradient(&weights).data, vec![vec![0.01, -0.02]]);
+//! ```
+
+use aspirina_core::matrix::Matrix;
+
+/// L1 and L2 penalty strengths applied to a network's weights.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Regularization {
+    /// Strength of the L1 penalty `λ₁ Σ |w|`
+    pub l1: f64,
+    /// Strength of the L2 penalty `½ λ₂ Σ w²`
+    pub l2: f64,
+}
+
+impl Regularization {
+    /// No regularization.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// L1 penalty only.
+    pub fn l1(strength: f64) -> Self {
+        Self {
+            l1: strength,
+            l2: 0.0,
+        }
+    }
+
+    /// L2 penalty only (weight decay).
+    pub fn l2(strength: f64) -> Self {
+        Self {
+            l1: 0.0,
+            l2: strength,
+        }
+    }
+
+    /// Combined L1 and L2 penalties.
+    pub fn elastic_net(l1: f64, l2: f64) -> Self {
+        Self { l1, l2 }
+    }
+
+    /// Returns `true` when neither penalty is active.
+    pub fn is_none(&self) -> bool {
+        self.l1 == 0.0 && self.l2 == 0.0
+    }
+
+    /// Penalty added to the loss for the given weights.
+    pub fn penalty(&self, weights: &Matrix) -> f64 {
+        weights
+            .data
+            .iter()
+            .flat_map(|row| row.iter())
+            .map(|w| self.l1 * w.abs() + 0.5 * self.l2 * w * w)
+            .sum()
+    }
+
+    /// Gradient of [`penalty`](Self::penalty) with respect to each weight.
+    ///
+    /// The L1 term uses a subgradient of zero for weights that are exactly zero.
+    pub fn gradient(&self, weights: &Matrix) -> Matrix {
+        Matrix::new(
+            weights
+                .data
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|w| {
+                            let sign = if *w == 0.0 { 0.0 } else { w.signum() };
+                            self.l1 * sign + self.l2 * w
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,283 @@
+//! Training Loop
+//!
+//! [`NeuralNetwork::train`] performs a single update. The [`Trainer`] wraps it in a loop over
+//! epochs, either on one full batch or on the mini-batches of a
+//! [`DataLoader`](crate::dataset::DataLoader), and takes care of the bookkeeping around each
+//! update: it asks the learning-rate schedule for the step size, reports the validation loss
+//! back to it after every epoch, notifies [`Callback`]s and records the metrics of every epoch
+//! in a [`History`].
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_gates::trainer::Trainer;
+//! # use aspirina_gates::lr_schedule::ExponentialDecay;
+//! # use aspirina_gates::neural_network::NeuralNetwork;
+//! # use aspirina_gates::layer::Layer;
+//! # use aspirina_core::matrix::Matrix;
+//! let network = NeuralNetwork::new(vec![
+//!     Layer::new(Matrix::new(vec![vec![0.1, 0.2], vec![0.3, 0.4]])),
+//!     Layer::new(Matrix::new(vec![vec![0.5, 0.6]])),
+//! ]);
+//!
+//! let input = Matrix::new(vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.0], vec![1.0, 1.0]]);
+//! let targets = Matrix::new(vec![vec![0.0, 1.0, 1.0, 1.0]]);
+//!
+//! let mut trainer = Trainer::new(100).with_schedule(ExponentialDecay::new(1.0, 0.99));
+//! let history = trainer.fit(&network, input, targets);
+//!
+//! assert!((network.learning_rate() - 0.99f64.powi(99)).abs() < 1e-12);
+//! assert!(history.losses().last().unwrap() < history.losses().first().unwrap());
+//! ```
+
+use crate::callbacks::{Callback, CallbackAction};
+use crate::dataset::DataLoader;
+use crate::lr_schedule::LrSchedule;
+use crate::metrics::ConfusionMatrix;
+use crate::neural_network::NeuralNetwork;
+use aspirina_core::matrix::Matrix;
+
+/// Metrics recorded at the end of one epoch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EpochMetrics {
+    /// Zero-based epoch index
+    pub epoch: usize,
+    /// Total number of epochs planned for the run
+    pub epochs: usize,
+    /// Training loss, as reported by [`NeuralNetwork::train`]
+    pub loss: f64,
+    /// Fraction of training outputs on the correct side of 0.5
+    pub accuracy: f64,
+    /// Norm of the gradient applied during the epoch
+    pub gradient_norm: f64,
+    /// Learning rate used during the epoch, or by its last batch when scheduled per step
+    pub learning_rate: f64,
+    /// Loss on the validation data, when configured
+    pub validation_loss: Option<f64>,
+}
+
+/// Metrics for every epoch of a training run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct History {
+    /// One entry per completed epoch
+    pub epochs: Vec<EpochMetrics>,
+    /// Epoch (zero-based) at which a callback stopped training early
+    pub stopped_at: Option<usize>,
+}
+
+impl History {
+    /// Training loss per epoch.
+    pub fn losses(&self) -> Vec<f64> {
+        self.epochs.iter().map(|metrics| metrics.loss).collect()
+    }
+
+    /// Training accuracy per epoch.
+    pub fn accuracies(&self) -> Vec<f64> {
+        self.epochs.iter().map(|metrics| metrics.accuracy).collect()
+    }
+
+    /// Gradient norm per epoch.
+    pub fn gradient_norms(&self) -> Vec<f64> {
+        self.epochs
+            .iter()
+            .map(|metrics| metrics.gradient_norm)
+            .collect()
+    }
+
+    /// Learning rate per epoch.
+    pub fn learning_rates(&self) -> Vec<f64> {
+        self.epochs
+            .iter()
+            .map(|metrics| metrics.learning_rate)
+            .collect()
+    }
+
+    /// Validation loss per epoch, for runs with validation data.
+    pub fn validation_losses(&self) -> Vec<f64> {
+        self.epochs
+            .iter()
+            .filter_map(|metrics| metrics.validation_loss)
+            .collect()
+    }
+}
+
+/// Runs training for a fixed number of epochs, on a full batch or on mini-batches.
+pub struct Trainer {
+    epochs: usize,
+    schedule: Option<Box<dyn LrSchedule>>,
+    schedule_per_step: bool,
+    validation: Option<(Matrix, Matrix)>,
+}
+
+impl Trainer {
+    /// Creates a trainer that runs `epochs` training steps with the network's own learning rate.
+    pub fn new(epochs: usize) -> Self {
+        Self {
+            epochs,
+            schedule: None,
+            schedule_per_step: false,
+            validation: None,
+        }
+    }
+
+    /// Sets the learning-rate schedule consulted before every epoch.
+    pub fn with_schedule(mut self, schedule: impl LrSchedule + 'static) -> Self {
+        self.schedule = Some(Box::new(schedule));
+        self
+    }
+
+    /// Consults the schedule before every batch instead of every epoch.
+    ///
+    /// The schedule's step then counts batches across the whole run, so the learning rate
+    /// can change within an epoch of [`fit_loader`](Self::fit_loader). The validation loss is
+    /// still reported once per epoch.
+    pub fn with_schedule_per_step(mut self) -> Self {
+        self.schedule_per_step = true;
+        self
+    }
+
+    /// Sets the data used to compute the validation loss reported to the schedule.
+    ///
+    /// Without validation data, the schedule is given the training loss instead.
+    pub fn with_validation(mut self, input: Matrix, targets: Matrix) -> Self {
+        self.validation = Some((input, targets));
+        self
+    }
+
+    /// Trains `network` on the given batch and returns the metrics of every epoch.
+    ///
+    /// Before each epoch the schedule (if any) sets the network's learning rate. After each
+    /// epoch the validation loss is computed in evaluation mode and passed to the schedule.
+    pub fn fit(&mut self, network: &NeuralNetwork, input: Matrix, targets: Matrix) -> History {
+        self.fit_with_callbacks(network, input, targets, &mut [])
+    }
+
+    /// Same as [`fit`](Self::fit), notifying `callbacks` along the way.
+    ///
+    /// Training ends early when any callback returns [`CallbackAction::Stop`]; the epoch is
+    /// recorded in [`History::stopped_at`].
+    pub fn fit_with_callbacks(
+        &mut self,
+        network: &NeuralNetwork,
+        input: Matrix,
+        targets: Matrix,
+        callbacks: &mut [&mut dyn Callback],
+    ) -> History {
+        self.run(
+            network,
+            || std::iter::once((input.clone(), targets.clone())),
+            callbacks,
+        )
+    }
+
+    /// Trains `network` on the mini-batches produced by `loader`, one pass over the data per
+    /// epoch.
+    ///
+    /// The schedule is consulted once per epoch, so every batch of an epoch uses the same
+    /// learning rate, unless [`with_schedule_per_step`](Self::with_schedule_per_step) is set.
+    /// The epoch loss is the sum of the batch losses, the gradient norm is the
+    /// mean over batches, and the accuracy counts every training output of the epoch.
+    pub fn fit_loader(
+        &mut self,
+        network: &NeuralNetwork,
+        loader: &mut DataLoader,
+        callbacks: &mut [&mut dyn Callback],
+    ) -> History {
+        self.run(network, || loader.batches(), callbacks)
+    }
+
+    fn run<I>(
+        &mut self,
+        network: &NeuralNetwork,
+        mut batches: impl FnMut() -> I,
+        callbacks: &mut [&mut dyn Callback],
+    ) -> History
+    where
+        I: Iterator<Item = (Matrix, Matrix)>,
+    {
+        let mut history = History::default();
+        let mut global_step = 0;
+
+        for epoch in 0..self.epochs {
+            for callback in callbacks.iter_mut() {
+                callback.on_epoch_start(epoch, network);
+            }
+
+            if let Some(schedule) = self.schedule.as_mut().filter(|_| !self.schedule_per_step) {
+                network.set_learning_rate(schedule.learning_rate(epoch));
+            }
+
+            let mut loss = 0.0;
+            let mut gradient_norm = 0.0;
+            let mut confusion = ConfusionMatrix::default();
+            let mut batch_count = 0;
+
+            for (batch, (input, targets)) in batches().enumerate() {
+                if let Some(schedule) = self.schedule.as_mut().filter(|_| self.schedule_per_step) {
+                    network.set_learning_rate(schedule.learning_rate(global_step));
+                }
+                global_step += 1;
+
+                let step = network.train(input, targets.clone());
+
+                for callback in callbacks.iter_mut() {
+                    callback.on_batch_end(epoch, batch, &step);
+                }
+
+                confusion += ConfusionMatrix::new(&step.output, &targets.transpose(), 0.5);
+                loss += step.loss;
+                gradient_norm += step.gradient_norm;
+                batch_count += 1;
+            }
+
+            let validation_loss = self
+                .validation
+                .as_ref()
+                .map(|(input, targets)| validation_loss(network, input.clone(), targets.clone()));
+
+            if let Some(schedule) = self.schedule.as_mut() {
+                schedule.observe(validation_loss.unwrap_or(loss));
+            }
+
+            let metrics = EpochMetrics {
+                epoch,
+                epochs: self.epochs,
+                loss,
+                accuracy: confusion.accuracy(),
+                gradient_norm: gradient_norm / batch_count.max(1) as f64,
+                learning_rate: network.learning_rate(),
+                validation_loss,
+            };
+
+            let mut stop = false;
+            for callback in callbacks.iter_mut() {
+                if callback.on_epoch_end(&metrics, network) == CallbackAction::Stop {
+                    stop = true;
+                }
+            }
+
+            history.epochs.push(metrics);
+
+            if stop {
+                history.stopped_at = Some(epoch);
+                break;
+            }
+        }
+
+        history
+    }
+}
+
+/// Computes the loss in evaluation mode, restoring the previous mode afterwards.
+fn validation_loss(network: &NeuralNetwork, input: Matrix, targets: Matrix) -> f64 {
+    let training = network.is_training();
+    network.eval();
+
+    let loss = network.loss(input, targets);
+
+    if training {
+        network.train_mode();
+    }
+
+    loss
+}
@@ -1,5 +1,8 @@
+use crate::callbacks::ProgressLogger;
 use crate::layer::Layer;
+use crate::metrics;
 use crate::neural_network::NeuralNetwork;
+use crate::trainer::Trainer;
 use aspirina_core::matrix::Matrix;
 
 pub fn run() {
@@ -18,13 +21,12 @@ pub fn run() {
     let targets = Matrix::new(vec![vec![0.0, 1.0, 1.0, 1.0]]);
 
     // Training loop with progress
-    let epochs = 10_000;
-    for idx in 0..epochs {
-        if idx % 2000 == 0 || idx == epochs - 1 {
-            println!("Epoch: {}/{}", idx + 1, epochs);
-        }
-        network.train(input.clone(), targets.clone());
-    }
+    Trainer::new(10_000).fit_with_callbacks(
+        &network,
+        input.clone(),
+        targets.clone(),
+        &mut [&mut ProgressLogger::new(2000)],
+    );
 
     println!("\n=== Training Complete ===");
     println!("Testing OR gate logic:");
@@ -63,6 +65,9 @@ pub fn run() {
             println!("  ✗ Needs more training");
         }
     }
+
+    let accuracy = metrics::accuracy(&network.predict(input), &targets.transpose(), 0.5);
+    println!("\nAccuracy: {:.0}%", accuracy * 100.0);
 }
 
 fn create_network() -> NeuralNetwork {
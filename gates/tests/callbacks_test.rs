@@ -0,0 +1,148 @@
+use aspirina_core::matrix::Matrix;
+use aspirina_gates::callbacks::{
+    BestModelCheckpoint, Callback, CallbackAction, CsvLogger, StopOnNaN,
+};
+use aspirina_gates::neural_network::{NeuralNetwork, TrainingStep};
+use aspirina_gates::trainer::{EpochMetrics, Trainer};
+
+mod common;
+
+use common::{xor_data, xor_network};
+
+#[derive(Default)]
+struct EventCounter {
+    epoch_starts: usize,
+    batch_ends: usize,
+    epoch_ends: usize,
+    stop_after: Option<usize>,
+}
+
+impl Callback for EventCounter {
+    fn on_epoch_start(&mut self, _epoch: usize, _network: &NeuralNetwork) {
+        self.epoch_starts += 1;
+    }
+
+    fn on_batch_end(&mut self, _epoch: usize, _batch: usize, _step: &TrainingStep) {
+        self.batch_ends += 1;
+    }
+
+    fn on_epoch_end(&mut self, metrics: &EpochMetrics, _network: &NeuralNetwork) -> CallbackAction {
+        self.epoch_ends += 1;
+        match self.stop_after {
+            Some(epoch) if metrics.epoch >= epoch => CallbackAction::Stop,
+            _ => CallbackAction::Continue,
+        }
+    }
+}
+
+#[test]
+fn history_records_metrics_for_every_epoch() {
+    let (input, targets) = xor_data();
+
+    let history = Trainer::new(3_000).fit(&xor_network(vec![]), input, targets);
+
+    assert_eq!(history.epochs.len(), 3_000);
+    assert_eq!(history.stopped_at, None);
+    assert!(history.losses().last().unwrap() < history.losses().first().unwrap());
+    assert_eq!(*history.accuracies().last().unwrap(), 1.0);
+    assert!(history.gradient_norms().iter().all(|norm| *norm > 0.0));
+    assert!(history.learning_rates().iter().all(|lr| *lr == 1.0));
+    assert!(history.validation_losses().is_empty());
+}
+
+#[test]
+fn history_records_validation_loss_when_configured() {
+    let (input, targets) = xor_data();
+
+    let history = Trainer::new(10)
+        .with_validation(input.clone(), targets.clone())
+        .fit(&xor_network(vec![]), input, targets);
+
+    assert_eq!(history.validation_losses().len(), 10);
+}
+
+#[test]
+fn callbacks_receive_every_event_and_can_stop() {
+    let (input, targets) = xor_data();
+    let mut counter = EventCounter {
+        stop_after: Some(4),
+        ..Default::default()
+    };
+
+    let history = Trainer::new(100).fit_with_callbacks(
+        &xor_network(vec![]),
+        input,
+        targets,
+        &mut [&mut counter],
+    );
+
+    assert_eq!(counter.epoch_starts, 5);
+    assert_eq!(counter.batch_ends, 5);
+    assert_eq!(counter.epoch_ends, 5);
+    assert_eq!(history.epochs.len(), 5);
+    assert_eq!(history.stopped_at, Some(4));
+}
+
+#[test]
+fn stop_on_nan_halts_training() {
+    let network = xor_network(vec![]);
+    let input = Matrix::new(vec![vec![f64::NAN, 0.0]]);
+    let targets = Matrix::new(vec![vec![1.0]]);
+    let mut stop_on_nan = StopOnNaN::new();
+
+    let history =
+        Trainer::new(100).fit_with_callbacks(&network, input, targets, &mut [&mut stop_on_nan]);
+
+    assert_eq!(stop_on_nan.stopped_at(), Some(0));
+    assert_eq!(history.stopped_at, Some(0));
+    assert_eq!(history.epochs.len(), 1);
+}
+
+#[test]
+fn checkpoint_keeps_and_restores_best_parameters() {
+    let (input, targets) = xor_data();
+    let network = xor_network(vec![]);
+    let mut checkpoint = BestModelCheckpoint::new();
+
+    let history = Trainer::new(500).fit_with_callbacks(
+        &network,
+        input.clone(),
+        targets.clone(),
+        &mut [&mut checkpoint],
+    );
+
+    let losses = history.losses();
+    let best_epoch = (0..losses.len())
+        .min_by(|&a, &b| losses[a].partial_cmp(&losses[b]).unwrap())
+        .unwrap();
+    assert_eq!(checkpoint.best_epoch(), Some(best_epoch));
+    assert_eq!(checkpoint.best_loss(), Some(losses[best_epoch]));
+
+    let saved = checkpoint.parameters().unwrap().clone();
+    network.set_parameters(xor_network(vec![]).parameters());
+    assert!(checkpoint.restore(&network));
+    assert_eq!(network.parameters()[0][0].data, saved[0][0].data);
+}
+
+#[test]
+fn csv_logger_writes_header_and_one_row_per_epoch() {
+    let (input, targets) = xor_data();
+    let path = std::env::temp_dir().join("aspirina_csv_logger_test.csv");
+    let mut logger = CsvLogger::create(&path).unwrap();
+
+    Trainer::new(5).fit_with_callbacks(&xor_network(vec![]), input, targets, &mut [&mut logger]);
+    drop(logger);
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = contents.lines().collect();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        lines[0],
+        "epoch,loss,accuracy,gradient_norm,learning_rate,validation_loss"
+    );
+    assert_eq!(lines.len(), 6);
+    assert!(lines[1].starts_with("0,"));
+    assert!(lines[5].starts_with("4,"));
+    assert!(lines[1].ends_with(','));
+}
@@ -0,0 +1,234 @@
+use aspirina_gates::computer::alu::{ALUOperation, ALU};
+use aspirina_gates::computer::circuit::Circuit;
+use aspirina_gates::computer::compiled::Mode;
+use aspirina_gates::computer::full_adder::FullAdder;
+use aspirina_gates::computer::gates::GateType;
+use aspirina_gates::computer::half_adder::HalfAdder;
+use aspirina_gates::computer::registry::GateRegistry;
+use aspirina_gates::computer::truth_table::TruthTable;
+
+const OPERATIONS: [ALUOperation; 5] = [
+    ALUOperation::Add,
+    ALUOperation::Subtract,
+    ALUOperation::And,
+    ALUOperation::Or,
+    ALUOperation::Xor,
+];
+
+fn nibble(value: u8) -> Vec<bool> {
+    (0..4).map(|bit| value & (1 << bit) != 0).collect()
+}
+
+fn value(bits: &[bool]) -> u8 {
+    bits.iter()
+        .enumerate()
+        .filter(|(_, bit)| **bit)
+        .map(|(index, _)| 1 << index)
+        .sum()
+}
+
+#[test]
+fn half_adder_netlist_matches_the_half_adder() {
+    let registry = GateRegistry::new();
+    let netlist = Circuit::half_adder().build(&registry).unwrap();
+    let half_adder = HalfAdder::with_registry(&registry);
+
+    assert_eq!(netlist.inputs(), &["a", "b"]);
+    assert_eq!(netlist.outputs(), &["sum", "carry"]);
+    for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+        let result = half_adder.compute(a, b);
+        assert_eq!(netlist.compute(&[a, b]), vec![result.sum, result.carry]);
+    }
+
+    assert_eq!(
+        netlist.truth_tables().unwrap(),
+        vec![
+            TruthTable::of(&GateType::XOR, 2),
+            TruthTable::of(&GateType::AND, 2)
+        ]
+    );
+}
+
+#[test]
+fn full_adder_netlists_match_both_full_adders() {
+    let registry = GateRegistry::new();
+
+    for (circuit, full_adder) in [
+        (Circuit::full_adder(), FullAdder::with_registry(&registry)),
+        (
+            Circuit::wide_full_adder(),
+            FullAdder::wide_with_registry(&registry),
+        ),
+    ] {
+        let netlist = circuit.build(&registry).unwrap();
+        for row in 0..8 {
+            let (a, b, carry) = (row & 4 != 0, row & 2 != 0, row & 1 != 0);
+            let result = full_adder.compute(a, b, carry);
+            assert_eq!(
+                netlist.compute(&[a, b, carry]),
+                vec![result.sum, result.carry]
+            );
+        }
+    }
+
+    assert_eq!(Circuit::full_adder().gate_count(), 5);
+    assert_eq!(Circuit::wide_full_adder().gate_count(), 2);
+}
+
+#[test]
+fn ripple_adder_adds_every_pair_of_nibbles() {
+    let registry = GateRegistry::new();
+    let adder = Circuit::ripple_adder(&Circuit::wide_full_adder(), 4)
+        .build(&registry)
+        .unwrap();
+
+    for a in 0..16u8 {
+        for b in 0..16u8 {
+            let mut inputs = nibble(a);
+            inputs.extend(nibble(b));
+            inputs.push(false);
+
+            let outputs = adder.compute(&inputs);
+            assert_eq!(value(&outputs[..4]), (a + b) & 0x0F);
+            assert_eq!(outputs[4], a + b > 15);
+        }
+    }
+}
+
+#[test]
+fn alu_netlist_matches_the_alu() {
+    let registry = GateRegistry::new();
+    let alu = ALU::with_registry(&registry);
+    let netlist = Circuit::alu(&Circuit::full_adder())
+        .build(&registry)
+        .unwrap();
+    assert_eq!(netlist.inputs().len(), 13);
+    assert_eq!(
+        netlist.truth_tables().unwrap_err(),
+        "ALU: truth tables need between 1 and 8 inputs, found 13"
+    );
+
+    for (index, operation) in OPERATIONS.iter().enumerate() {
+        for a in 0..16 {
+            for b in 0..16 {
+                let mut inputs = nibble(a);
+                inputs.extend(nibble(b));
+                inputs.extend((0..5).map(|select| select == index));
+
+                let outputs = netlist.compute(&inputs);
+                let expected = alu.compute(a, b, operation.clone());
+                assert_eq!(value(&outputs[..4]), expected.result, "{:?}", operation);
+                assert_eq!(outputs[4], expected.carry, "{} {:?} {}", a, operation, b);
+                assert_eq!(outputs[5], expected.zero);
+            }
+        }
+    }
+}
+
+#[test]
+fn truth_tables_need_at_least_one_input() {
+    let constant = Circuit::new("Constant", &[], &["out"])
+        .with_gate(GateType::NOT, &["false"], "out")
+        .build(&GateRegistry::new())
+        .unwrap();
+
+    assert_eq!(constant.compute(&[]), vec![true]);
+    assert_eq!(
+        constant.truth_tables().unwrap_err(),
+        "Constant: truth tables need between 1 and 8 inputs, found 0"
+    );
+}
+
+#[test]
+fn components_can_be_compiled() {
+    let registry = GateRegistry::new();
+    let netlist = Circuit::full_adder().build(&registry).unwrap();
+    let compiled = netlist.compiled().unwrap();
+
+    assert_eq!(netlist.mode(), Mode::Neural);
+    assert_eq!(compiled.mode(), Mode::Compiled);
+    assert_eq!(
+        compiled.truth_tables().unwrap(),
+        netlist.truth_tables().unwrap()
+    );
+}
+
+#[test]
+fn parts_are_evaluated_in_topological_order() {
+    // Parts listed from output back to input
+    let circuit = Circuit::new("Chain", &["a"], &["out"])
+        .with_gate(GateType::NOT, &["mid"], "out")
+        .with_gate(GateType::NOT, &["a"], "mid");
+
+    assert_eq!(circuit.order().unwrap(), vec![1, 0]);
+    let component = circuit.build(&GateRegistry::new()).unwrap();
+    assert_eq!(component.compute(&[true]), vec![true]);
+}
+
+#[test]
+fn constant_wires_are_available() {
+    let circuit = Circuit::new("Constants", &["a"], &["high", "low"])
+        .with_gate(GateType::OR, &["a", "true"], "high")
+        .with_gate(GateType::AND, &["a", "false"], "low");
+    let component = circuit.build(&GateRegistry::new()).unwrap();
+
+    assert_eq!(component.compute(&[false]), vec![true, false]);
+    assert_eq!(component.compute(&[true]), vec![true, false]);
+}
+
+#[test]
+fn combinational_cycles_are_rejected() {
+    let latch = Circuit::new("Latch", &["s", "r"], &["q"])
+        .with_gate(GateType::NOR, &["r", "qn"], "q")
+        .with_gate(GateType::NOR, &["s", "q"], "qn");
+
+    assert_eq!(
+        latch.order().unwrap_err(),
+        "Latch: combinational cycle through wires q, qn"
+    );
+    assert!(latch.build(&GateRegistry::new()).is_err());
+}
+
+#[test]
+fn malformed_netlists_are_rejected() {
+    let undriven =
+        Circuit::new("Undriven", &["a"], &["out"]).with_gate(GateType::AND, &["a", "b"], "out");
+    assert_eq!(
+        undriven.order().unwrap_err(),
+        "Undriven: wire b is never driven"
+    );
+
+    let twice = Circuit::new("Twice", &["a"], &["a"]).with_gate(GateType::NOT, &["a"], "a");
+    assert_eq!(
+        twice.order().unwrap_err(),
+        "Twice: wire a is driven more than once"
+    );
+
+    let arity =
+        Circuit::new("Arity", &["a", "b"], &["out"]).with_gate(GateType::NOT, &["a", "b"], "out");
+    assert_eq!(
+        arity.order().unwrap_err(),
+        "Arity: NOT gate cannot take 2 input(s)"
+    );
+
+    let ports = Circuit::new("Ports", &["a"], &["sum"]).with_circuit(
+        &Circuit::half_adder(),
+        &["a"],
+        &["sum"],
+    );
+    assert_eq!(
+        ports.order().unwrap_err(),
+        "Ports: HalfAdder expects 2 input(s) and 2 output(s), got 1 and 1"
+    );
+}
+
+#[test]
+fn sub_circuit_errors_surface_when_building() {
+    let broken = Circuit::new("Broken", &["a"], &["out"]);
+    let outer = Circuit::new("Outer", &["x"], &["y"]).with_circuit(&broken, &["x"], &["y"]);
+
+    assert_eq!(
+        outer.build(&GateRegistry::new()).unwrap_err(),
+        "Broken: wire out is never driven"
+    );
+}
@@ -0,0 +1,31 @@
+use aspirina_core::matrix::Matrix;
+use aspirina_gates::layer::Layer;
+use aspirina_gates::neural_network::NeuralNetwork;
+
+/// The 2-4-1 network the training tests start from, with `hidden` layers (such as dropout
+/// or normalization) inserted between its two dense layers
+pub fn xor_network(hidden: Vec<Layer>) -> NeuralNetwork {
+    let mut layers = vec![Layer::new(Matrix::new(vec![
+        vec![0.5, 0.5],
+        vec![-0.3, -0.3],
+        vec![0.8, -0.8],
+        vec![-0.6, 0.6],
+    ]))];
+    layers.extend(hidden);
+    layers.push(Layer::new(Matrix::new(vec![vec![0.9, -0.7, 1.2, -0.4]])));
+
+    NeuralNetwork::new(layers)
+}
+
+/// The XOR truth table as (input, targets)
+pub fn xor_data() -> (Matrix, Matrix) {
+    (
+        Matrix::new(vec![
+            vec![0.0, 0.0],
+            vec![0.0, 1.0],
+            vec![1.0, 0.0],
+            vec![1.0, 1.0],
+        ]),
+        Matrix::new(vec![vec![0.0, 1.0, 1.0, 0.0]]),
+    )
+}
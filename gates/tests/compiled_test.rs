@@ -0,0 +1,127 @@
+use aspirina_gates::computer::alu::{ALUOperation, ALU};
+use aspirina_gates::computer::compiled::Mode;
+use aspirina_gates::computer::full_adder::FullAdder;
+use aspirina_gates::computer::gates::{GateType, LogicGate};
+use aspirina_gates::computer::half_adder::HalfAdder;
+use aspirina_gates::computer::registry::GateRegistry;
+use aspirina_gates::computer::truth_table::TruthTable;
+
+const OPERATIONS: [ALUOperation; 5] = [
+    ALUOperation::Add,
+    ALUOperation::Subtract,
+    ALUOperation::And,
+    ALUOperation::Or,
+    ALUOperation::Xor,
+];
+
+#[test]
+fn trained_gates_compile_to_their_truth_tables() {
+    let registry = GateRegistry::new();
+
+    for gate_type in GateType::ALL {
+        let inputs = gate_type.default_inputs();
+        let table = registry.get(gate_type.clone(), inputs).compile().unwrap();
+        assert_eq!(table, TruthTable::of(&gate_type, inputs), "{}", gate_type);
+    }
+}
+
+#[test]
+fn untrained_gates_do_not_compile() {
+    let gate = LogicGate::new(GateType::XOR, 2);
+
+    assert_eq!(
+        gate.compile().unwrap_err(),
+        "XOR gate does not reproduce its truth table"
+    );
+    assert!(gate.to_threshold_circuit().is_err());
+}
+
+#[test]
+fn threshold_circuits_reproduce_every_gate() {
+    let registry = GateRegistry::new();
+    let gates = GateType::ALL
+        .iter()
+        .map(|gate_type| (gate_type.clone(), gate_type.default_inputs()))
+        .chain([(GateType::NOR, 4), (GateType::THRESHOLD(2), 3)]);
+
+    for (gate_type, inputs) in gates {
+        let circuit = registry
+            .get(gate_type.clone(), inputs)
+            .to_threshold_circuit()
+            .unwrap();
+
+        assert_eq!(circuit.inputs(), inputs);
+        assert_eq!(circuit.layers.last().unwrap().len(), 1);
+        assert_eq!(
+            circuit.truth_table(),
+            TruthTable::of(&gate_type, inputs),
+            "{}\n{}",
+            gate_type,
+            circuit
+        );
+    }
+}
+
+#[test]
+fn compiled_adders_match_neural_adders() {
+    let registry = GateRegistry::new();
+    let half_adder = HalfAdder::with_registry(&registry);
+    let full_adders = [
+        FullAdder::with_registry(&registry),
+        FullAdder::wide_with_registry(&registry),
+    ];
+
+    let compiled = half_adder.compiled().unwrap();
+    assert_eq!(half_adder.mode(), Mode::Neural);
+    assert_eq!(compiled.mode(), Mode::Compiled);
+    for a in [false, true] {
+        for b in [false, true] {
+            assert_eq!(compiled.compute(a, b), half_adder.compute(a, b));
+        }
+    }
+
+    for full_adder in &full_adders {
+        let compiled = full_adder.compiled().unwrap();
+        assert_eq!(compiled.mode(), Mode::Compiled);
+        for row in 0..8 {
+            let (a, b, carry) = (row & 4 != 0, row & 2 != 0, row & 1 != 0);
+            assert_eq!(
+                compiled.compute(a, b, carry),
+                full_adder.compute(a, b, carry)
+            );
+        }
+    }
+}
+
+#[test]
+fn compiled_alu_matches_neural_alu_on_every_operation() {
+    let registry = GateRegistry::new();
+
+    for neural in [
+        ALU::with_registry(&registry),
+        ALU::wide_with_registry(&registry),
+    ] {
+        let compiled = neural.compiled().unwrap();
+        assert_eq!(neural.mode(), Mode::Neural);
+        assert_eq!(compiled.mode(), Mode::Compiled);
+
+        for operation in OPERATIONS {
+            for a in 0..16 {
+                for b in 0..16 {
+                    assert_eq!(
+                        compiled.compute(a, b, operation.clone()),
+                        neural.compute(a, b, operation.clone()),
+                        "{} {:?} {}",
+                        a,
+                        operation,
+                        b
+                    );
+                }
+            }
+        }
+
+        // Compiling twice keeps the same tables
+        let recompiled = compiled.compiled().unwrap();
+        assert_eq!(recompiled.compute(9, 8, ALUOperation::Add).result, 1);
+    }
+}
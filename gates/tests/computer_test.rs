@@ -0,0 +1,283 @@
+use aspirina_core::matrix::Matrix;
+use aspirina_gates::computer::alu::{ALUOperation, ALU};
+use aspirina_gates::computer::full_adder::FullAdder;
+use aspirina_gates::computer::gates::{GateType, LogicGate};
+use aspirina_gates::metrics::{accuracy, ConfusionMatrix};
+
+type BitwiseOperation = (ALUOperation, fn(u8, u8) -> u8);
+
+fn gate_outputs(gate: &LogicGate) -> Matrix {
+    Matrix::new(
+        gate.truth_table()
+            .into_iter()
+            .map(|(inputs, _)| vec![gate.compute(inputs)])
+            .collect(),
+    )
+}
+
+fn bits(values: &[u8]) -> Matrix {
+    Matrix::new(
+        values
+            .iter()
+            .map(|value| (0..4).map(|bit| ((value >> bit) & 1) as f64).collect())
+            .collect(),
+    )
+}
+
+#[test]
+fn trained_gates_reach_full_accuracy() {
+    for gate_type in GateType::ALL {
+        let gate = LogicGate::new(gate_type.clone(), gate_type.default_inputs());
+        gate.train(10_000);
+
+        let targets = Matrix::new(gate.truth_table().iter().map(|(_, t)| vec![*t]).collect());
+        let confusion = ConfusionMatrix::new(&gate_outputs(&gate), &targets, 0.5);
+
+        assert_eq!(confusion.accuracy(), 1.0, "{:?} gate", gate_type);
+        assert_eq!(confusion.f1_score(), 1.0, "{:?} gate", gate_type);
+    }
+}
+
+#[test]
+fn truth_tables_match_boolean_operations() {
+    let cases = [
+        (GateType::AND, vec![0.0, 0.0, 0.0, 1.0]),
+        (GateType::OR, vec![0.0, 1.0, 1.0, 1.0]),
+        (GateType::XOR, vec![0.0, 1.0, 1.0, 0.0]),
+        (GateType::NOT, vec![1.0, 0.0]),
+        (GateType::NAND, vec![1.0, 1.0, 1.0, 0.0]),
+        (GateType::NOR, vec![1.0, 0.0, 0.0, 0.0]),
+        (GateType::XNOR, vec![1.0, 0.0, 0.0, 1.0]),
+        (GateType::BUFFER, vec![0.0, 1.0]),
+    ];
+
+    for (gate_type, expected) in cases {
+        let inputs = gate_type.default_inputs();
+        let table = gate_type.truth_table(inputs);
+        let outputs: Vec<f64> = table.iter().map(|(_, output)| *output).collect();
+
+        assert_eq!(outputs, expected, "{:?} gate", gate_type);
+        assert!(table.iter().all(|(row, _)| row.len() == inputs));
+    }
+    assert_eq!(GateType::NOT.truth_table(1)[0].0, vec![0.0]);
+    assert_eq!(GateType::XOR.truth_table(2)[2].0, vec![1.0, 0.0]);
+}
+
+#[test]
+fn wide_truth_tables_count_high_inputs() {
+    let outputs = |gate_type: GateType, inputs: usize| -> Vec<f64> {
+        gate_type
+            .truth_table(inputs)
+            .into_iter()
+            .map(|(_, output)| output)
+            .collect()
+    };
+
+    // rows 000, 001, 010, 011, 100, 101, 110, 111
+    assert_eq!(
+        outputs(GateType::AND, 3),
+        vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0]
+    );
+    assert_eq!(
+        outputs(GateType::XOR, 3),
+        vec![0.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0]
+    );
+    assert_eq!(
+        outputs(GateType::PARITY, 3),
+        vec![0.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0]
+    );
+    assert_eq!(
+        outputs(GateType::MAJORITY, 3),
+        vec![0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 1.0]
+    );
+    assert_eq!(
+        outputs(GateType::THRESHOLD(2), 3),
+        outputs(GateType::MAJORITY, 3)
+    );
+    assert_eq!(outputs(GateType::PARITY, 2), outputs(GateType::XOR, 2));
+    assert_eq!(GateType::OR.truth_table(8).len(), 256);
+}
+
+#[test]
+fn wide_gates_train_on_the_full_truth_table() {
+    let cases = [
+        (GateType::AND, 3),
+        (GateType::OR, 3),
+        (GateType::XOR, 3),
+        (GateType::NAND, 3),
+        (GateType::NOR, 3),
+        (GateType::XNOR, 3),
+        (GateType::THRESHOLD(1), 3),
+        (GateType::MAJORITY, 4),
+        (GateType::PARITY, 4),
+    ];
+
+    for (gate_type, inputs) in cases {
+        let gate = LogicGate::new(gate_type.clone(), inputs);
+        gate.train(10_000);
+
+        let targets = Matrix::new(gate.truth_table().iter().map(|(_, t)| vec![*t]).collect());
+        assert_eq!(
+            accuracy(&gate_outputs(&gate), &targets, 0.5),
+            1.0,
+            "{:?} gate with {} inputs",
+            gate_type,
+            inputs
+        );
+    }
+}
+
+#[test]
+fn gate_arity_is_validated() {
+    assert!(GateType::NOT.supports(1));
+    assert!(!GateType::NOT.supports(2));
+    assert!(!GateType::AND.supports(1));
+    assert!(GateType::AND.supports(8));
+    assert!(!GateType::AND.supports(9));
+    assert!(!GateType::THRESHOLD(4).supports(3));
+    assert!(!GateType::THRESHOLD(0).supports(3));
+}
+
+#[test]
+#[should_panic(expected = "OR gate cannot take 9 input(s)")]
+fn gates_wider_than_the_maximum_are_rejected() {
+    LogicGate::new(GateType::OR, 9);
+}
+
+#[test]
+#[should_panic(expected = "NOT gate expects 1 input(s), got 2")]
+fn not_gate_takes_a_single_input() {
+    LogicGate::new(GateType::NOT, 1).compute(vec![1.0, 0.0]);
+}
+
+#[test]
+fn wide_full_adder_matches_binary_addition() {
+    let adder = FullAdder::wide();
+
+    for row in 0..8u8 {
+        let (a, b, carry_in) = (row & 4 != 0, row & 2 != 0, row & 1 != 0);
+        let total = a as u8 + b as u8 + carry_in as u8;
+        let result = adder.compute(a, b, carry_in);
+
+        assert_eq!(result.sum, total & 1 == 1, "{:03b}", row);
+        assert_eq!(result.carry, total >= 2, "{:03b}", row);
+    }
+}
+
+#[test]
+fn wide_alu_matches_gate_chain_alu() {
+    let alu = ALU::wide();
+
+    for a in 0..16u8 {
+        for b in 0..16u8 {
+            let sum = alu.compute(a, b, ALUOperation::Add);
+            assert_eq!(sum.result, (a + b) & 0x0F, "{} + {}", a, b);
+            assert_eq!(sum.carry, a + b > 0x0F, "{} + {}", a, b);
+            assert_eq!(sum.zero, (a + b) & 0x0F == 0, "{} + {}", a, b);
+        }
+    }
+    assert_eq!(alu.compute(9, 9, ALUOperation::Subtract).result, 0);
+    assert!(alu.compute(9, 9, ALUOperation::Subtract).zero);
+    assert!(!alu.compute(12, 5, ALUOperation::And).zero);
+}
+
+#[test]
+fn alu_bitwise_operations_are_exact() {
+    let alu = ALU::new();
+    let pairs: Vec<(u8, u8)> = (0..16).flat_map(|a| (0..16).map(move |b| (a, b))).collect();
+
+    let operations: [BitwiseOperation; 4] = [
+        (ALUOperation::Add, |a, b| (a + b) & 0x0F),
+        (ALUOperation::And, |a, b| a & b),
+        (ALUOperation::Or, |a, b| a | b),
+        (ALUOperation::Xor, |a, b| a ^ b),
+    ];
+
+    for (operation, expected) in operations {
+        let results: Vec<u8> = pairs
+            .iter()
+            .map(|&(a, b)| alu.compute(a, b, operation.clone()).result)
+            .collect();
+        let targets: Vec<u8> = pairs.iter().map(|&(a, b)| expected(a, b)).collect();
+
+        assert_eq!(
+            accuracy(&bits(&results), &bits(&targets), 0.5),
+            1.0,
+            "{:?}",
+            operation
+        );
+    }
+}
+
+#[test]
+fn verify_reports_every_row_and_the_worst_margin() {
+    let gate = LogicGate::new(GateType::XOR, 2);
+    gate.train(10_000);
+
+    let report = gate.verify();
+
+    assert_eq!(report.rows.len(), 4);
+    assert!(report.passed);
+    assert!(report.meets(0.4));
+    for (row, (inputs, expected)) in report.rows.iter().zip(gate.truth_table()) {
+        assert_eq!(row.inputs, inputs);
+        assert_eq!(row.expected, expected);
+        assert_eq!(row.output, gate.compute(inputs));
+        assert!(row.margin >= report.margin);
+    }
+    assert!(report.to_string().ends_with("PASS"));
+}
+
+#[test]
+fn untrained_gates_fail_verification() {
+    let report = LogicGate::new(GateType::XOR, 2).verify();
+
+    assert!(!report.passed);
+    assert!(report.margin <= 0.0);
+    assert!(report.rows.iter().any(|row| row.margin <= 0.0));
+    assert!(report.to_string().ends_with("FAIL"));
+}
+
+#[test]
+fn training_retries_until_the_margin_holds() {
+    let gate = LogicGate::train_verified(GateType::NAND, 2, 5_000, 0.4, 3).unwrap();
+    assert!(gate.verify().margin > 0.4);
+
+    let error = LogicGate::train_verified(GateType::XOR, 2, 10, 0.4, 2).unwrap_err();
+    assert!(
+        error.starts_with("XOR gate with 2 input(s) failed verification after 2 attempt(s)"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn seeds_perturb_the_starting_weights() {
+    let weights = |gate: LogicGate| gate.parameters()[0][0].data.clone();
+
+    let default = weights(LogicGate::new(GateType::AND, 2));
+    let first = weights(LogicGate::with_seed(GateType::AND, 2, 1));
+
+    assert_eq!(first, weights(LogicGate::with_seed(GateType::AND, 2, 1)));
+    assert_ne!(first, weights(LogicGate::with_seed(GateType::AND, 2, 2)));
+    for (row, default_row) in first.iter().zip(default.iter()) {
+        for (w, d) in row.iter().zip(default_row.iter()) {
+            assert!((w - d).abs() <= 0.5);
+        }
+    }
+}
+
+#[test]
+fn noise_sweep_degrades_with_noise() {
+    let gate = LogicGate::new(GateType::AND, 2);
+    gate.train(10_000);
+
+    let sweep = gate.noise_sweep(&[0.0, 0.1, 1.0], 50, 7);
+
+    assert_eq!(sweep.len(), 3);
+    assert_eq!(sweep[0].std_dev, 0.0);
+    assert_eq!(sweep[0].accuracy, 1.0);
+    assert!(sweep[1].accuracy > 0.9);
+    assert!(sweep[2].accuracy < sweep[1].accuracy);
+    assert_eq!(gate.noise_sweep(&[1.0], 50, 7), vec![sweep[2]]);
+}
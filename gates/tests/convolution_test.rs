@@ -0,0 +1,240 @@
+use aspirina_core::calc::Calc;
+use aspirina_core::matrix::Matrix;
+use aspirina_core::random::Rng;
+use aspirina_gates::convolution::{Convolution, Pooling, PoolingKind};
+use aspirina_gates::gradient_check::check_gradients;
+use aspirina_gates::layer::Layer;
+use aspirina_gates::neural_network::NeuralNetwork;
+use aspirina_gates::regularization::Regularization;
+
+/// Segments a-g of each digit, rendered on a 5×3 grid:
+///
+/// ```text
+/// aaa
+/// f b
+/// ggg
+/// e c
+/// ddd
+/// ```
+const DIGIT_SEGMENTS: [&str; 10] = [
+    "abcdef", "bc", "abdeg", "abcdg", "bcfg", "acdfg", "acdefg", "abc", "abcdefg", "abcdfg",
+];
+
+fn seven_segment(segments: &str) -> Vec<f64> {
+    let mut pixels = vec![0.0; 15];
+    let mut light = |cells: &[usize]| {
+        for cell in cells {
+            pixels[*cell] = 1.0;
+        }
+    };
+
+    for segment in segments.chars() {
+        match segment {
+            'a' => light(&[0, 1, 2]),
+            'b' => light(&[2, 5, 8]),
+            'c' => light(&[8, 11, 14]),
+            'd' => light(&[12, 13, 14]),
+            'e' => light(&[6, 9, 12]),
+            'f' => light(&[0, 3, 6]),
+            'g' => light(&[6, 7, 8]),
+            _ => unreachable!(),
+        }
+    }
+
+    pixels
+}
+
+fn random_dense(outputs: usize, inputs: usize, seed: u64) -> Layer {
+    let mut rng = Rng::new(seed);
+    Layer::new(Matrix::new(
+        (0..outputs)
+            .map(|_| (0..inputs).map(|_| rng.range(-0.5, 0.5)).collect())
+            .collect(),
+    ))
+}
+
+fn argmax(row: &[f64]) -> usize {
+    (0..row.len())
+        .max_by(|a, b| row[*a].total_cmp(&row[*b]))
+        .unwrap()
+}
+
+#[test]
+fn conv1d_slides_kernel_with_stride_and_padding() {
+    let mut conv = Convolution::conv1d(1, 4, 1, 3, 0)
+        .with_padding(1)
+        .with_stride(2);
+    conv.kernels = Matrix::new(vec![vec![1.0, 2.0, 3.0]]);
+    conv.bias = Matrix::new(vec![vec![0.5]]);
+    assert_eq!(conv.output_shape(), (1, 1, 2));
+
+    let network = NeuralNetwork::new(vec![Layer::convolution(conv)]);
+    let output = network.predict(Matrix::new(vec![vec![1.0, 2.0, 3.0, 4.0]]));
+
+    // Padded input [0, 1, 2, 3, 4, 0]; windows start at 0 and 2.
+    let expected = [0.5 + 2.0 + 6.0, 0.5 + 2.0 + 6.0 + 12.0];
+    for (value, pre_activation) in output.data[0].iter().zip(expected.iter()) {
+        assert!((value - Calc::sigmoid(pre_activation)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn conv2d_combines_input_channels() {
+    let mut conv = Convolution::conv2d(2, (2, 2), 1, (2, 2), 0);
+    conv.kernels = Matrix::new(vec![vec![1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, 0.0]]);
+    assert_eq!(conv.output_shape(), (1, 1, 1));
+
+    let network = NeuralNetwork::new(vec![Layer::convolution(conv)]);
+    let output = network.predict(Matrix::new(vec![vec![
+        1.0, 2.0, 3.0, 4.0, // channel 0
+        0.5, 0.0, 0.0, 0.0, // channel 1
+    ]]));
+
+    assert!((output.data[0][0] - Calc::sigmoid(&4.5)).abs() < 1e-12);
+}
+
+#[test]
+fn output_shapes_follow_stride_and_padding() {
+    assert_eq!(Convolution::conv1d(2, 10, 3, 3, 0).output_size(), 3 * 8);
+    assert_eq!(
+        Convolution::conv2d(1, (6, 6), 2, (3, 3), 0)
+            .with_stride(2)
+            .output_shape(),
+        (2, 2, 2)
+    );
+    assert_eq!(
+        Convolution::conv2d(1, (5, 3), 4, (3, 3), 0)
+            .with_padding(1)
+            .output_shape(),
+        (4, 5, 3)
+    );
+    assert_eq!(Pooling::max_1d(3, 7, 2).output_shape(), (3, 1, 3));
+    assert_eq!(
+        Pooling::average_2d(1, (4, 4), (2, 2))
+            .with_stride(1)
+            .output_shape(),
+        (1, 3, 3)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Kernel is larger than the padded input")]
+fn convolution_rejects_oversized_kernel() {
+    Convolution::conv2d(1, (2, 2), 1, (3, 3), 0);
+}
+
+#[test]
+#[should_panic(expected = "Incompatible dimensions")]
+fn convolution_rejects_wrong_input_width() {
+    let network = NeuralNetwork::new(vec![Layer::convolution(Convolution::conv1d(1, 4, 1, 2, 0))]);
+    network.predict(Matrix::new(vec![vec![1.0, 2.0, 3.0]]));
+}
+
+#[test]
+fn pooling_keeps_maximum_or_average_per_channel() {
+    let input = Matrix::new(vec![vec![
+        1.0, 5.0, 2.0, 0.0, // channel 0, 2×2
+        -1.0, -3.0, 4.0, 8.0, // channel 1, 2×2
+    ]]);
+
+    let max = Pooling::max_2d(2, (2, 2), (2, 2));
+    assert_eq!(max.kind(), PoolingKind::Max);
+    let max = NeuralNetwork::new(vec![Layer::pooling(max)]);
+    assert_eq!(max.predict(input.clone()).data, vec![vec![5.0, 8.0]]);
+
+    let average = NeuralNetwork::new(vec![Layer::pooling(Pooling::average_2d(2, (2, 2), (2, 2)))]);
+    assert_eq!(average.predict(input).data, vec![vec![2.0, 2.0]]);
+
+    let pool_1d = NeuralNetwork::new(vec![Layer::pooling(Pooling::max_1d(1, 5, 2))]);
+    assert_eq!(
+        pool_1d
+            .predict(Matrix::new(vec![vec![1.0, 3.0, 2.0, 0.0, 9.0]]))
+            .data,
+        vec![vec![3.0, 2.0]]
+    );
+}
+
+#[test]
+fn flatten_passes_values_through() {
+    let network = NeuralNetwork::new(vec![Layer::flatten()]);
+    let input = Matrix::new(vec![vec![0.1, 0.2], vec![0.3, 0.4]]);
+
+    assert_eq!(network.predict(input.clone()).data, input.data);
+    assert!(Layer::flatten().parameters().is_empty());
+}
+
+#[test]
+fn conv1d_network_gradients_match_finite_differences() {
+    let network = NeuralNetwork::new(vec![
+        Layer::convolution(
+            Convolution::conv1d(2, 6, 3, 3, 1)
+                .with_padding(1)
+                .with_stride(2),
+        ),
+        Layer::pooling(Pooling::average_1d(3, 3, 2).with_stride(1)),
+        Layer::flatten(),
+        random_dense(1, 6, 2),
+    ]);
+    let mut rng = Rng::new(3);
+    let input = Matrix::new(
+        (0..3)
+            .map(|_| (0..12).map(|_| rng.range(-1.0, 1.0)).collect())
+            .collect(),
+    );
+    let targets = Matrix::new(vec![vec![0.0, 1.0, 1.0]]);
+
+    let report = check_gradients(&network, input, targets, 1e-5);
+
+    assert!(report.passed(1e-6), "{:?}", report);
+}
+
+#[test]
+fn conv2d_network_gradients_match_finite_differences() {
+    let network = NeuralNetwork::new(vec![
+        Layer::convolution(Convolution::conv2d(1, (5, 4), 2, (3, 2), 4).with_padding(1)),
+        Layer::pooling(Pooling::max_2d(2, (5, 5), (2, 2))),
+        Layer::flatten(),
+        random_dense(2, 8, 5),
+    ])
+    .with_regularization(Regularization::l2(0.01));
+    let mut rng = Rng::new(6);
+    let input = Matrix::new(
+        (0..2)
+            .map(|_| (0..20).map(|_| rng.range(0.0, 1.0)).collect())
+            .collect(),
+    );
+    let targets = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+
+    let report = check_gradients(&network, input, targets, 1e-5);
+
+    assert!(report.passed(1e-6), "{:?}", report);
+}
+
+#[test]
+fn learns_seven_segment_digits() {
+    let network = NeuralNetwork::new(vec![
+        Layer::convolution(Convolution::conv2d(1, (5, 3), 6, (3, 3), 11)),
+        Layer::flatten(),
+        random_dense(10, 18, 12),
+    ]);
+
+    let input = Matrix::new(DIGIT_SEGMENTS.iter().map(|s| seven_segment(s)).collect());
+    let targets = Matrix::new(
+        (0..10)
+            .map(|digit| {
+                (0..10)
+                    .map(|i| if i == digit { 1.0 } else { 0.0 })
+                    .collect()
+            })
+            .collect(),
+    );
+
+    for _ in 0..3_000 {
+        network.train(input.clone(), targets.clone());
+    }
+
+    let output = network.predict(input);
+    for (digit, row) in output.data.iter().enumerate() {
+        assert_eq!(argmax(row), digit, "digit {}: {:?}", digit, row);
+    }
+}
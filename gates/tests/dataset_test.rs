@@ -0,0 +1,244 @@
+use aspirina_core::matrix::Matrix;
+use aspirina_gates::dataset::{
+    split, CsvDataset, DataLoader, Dataset, InMemoryDataset, Scaler, Scaling,
+};
+use aspirina_gates::layer::Layer;
+use aspirina_gates::lr_schedule::{ExponentialDecay, LrSchedule};
+use aspirina_gates::neural_network::NeuralNetwork;
+use aspirina_gates::trainer::Trainer;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn numbered_dataset(samples: usize) -> InMemoryDataset {
+    InMemoryDataset::new(
+        (0..samples)
+            .map(|i| vec![i as f64, 2.0 * i as f64])
+            .collect(),
+        (0..samples).map(|i| vec![i as f64]).collect(),
+    )
+}
+
+#[test]
+fn in_memory_dataset_from_training_matrices() {
+    let input = Matrix::new(vec![vec![0.0, 1.0], vec![1.0, 0.0], vec![1.0, 1.0]]);
+    let targets = Matrix::new(vec![vec![1.0, 1.0, 0.0]]);
+
+    let dataset = InMemoryDataset::from_matrices(&input, &targets);
+
+    assert_eq!(dataset.len(), 3);
+    assert_eq!(dataset.get(2), (vec![1.0, 1.0], vec![0.0]));
+}
+
+#[test]
+#[should_panic(expected = "Expected one target per input sample")]
+fn in_memory_dataset_rejects_mismatched_lengths() {
+    InMemoryDataset::new(vec![vec![0.0]], vec![]);
+}
+
+#[test]
+fn csv_dataset_parses_header_and_targets() {
+    let dataset = CsvDataset::parse("a, b, y\n1, 2, 0\n\n3, 4, 1\n", 1).unwrap();
+
+    assert_eq!(dataset.header().unwrap(), ["a", "b", "y"]);
+    assert_eq!(dataset.len(), 2);
+    assert_eq!(dataset.get(1), (vec![3.0, 4.0], vec![1.0]));
+}
+
+#[test]
+fn csv_dataset_without_header_and_multiple_targets() {
+    let dataset = CsvDataset::parse("1,0,0,1\n0,1,1,0", 2).unwrap();
+
+    assert!(dataset.header().is_none());
+    assert_eq!(dataset.get(0), (vec![1.0, 0.0], vec![0.0, 1.0]));
+}
+
+#[test]
+fn csv_dataset_reports_bad_lines() {
+    assert_eq!(
+        CsvDataset::parse("1,2,3\n4,5\n", 1).unwrap_err(),
+        "Line 2: expected 3 columns, found 2"
+    );
+    assert_eq!(
+        CsvDataset::parse("x,y\n1,2\nfoo,3\n", 1).unwrap_err(),
+        "Line 3: expected numeric values"
+    );
+    assert!(CsvDataset::parse("1,2\n", 2).is_err());
+    assert_eq!(
+        CsvDataset::parse("1,abc,0\n2,3,1\n", 1).unwrap_err(),
+        "Line 1: expected numeric values"
+    );
+}
+
+#[test]
+fn csv_dataset_loads_from_file() {
+    let path = std::env::temp_dir().join("aspirina_dataset_test.csv");
+    std::fs::write(&path, "x,y\n0.5,1\n").unwrap();
+
+    let dataset = CsvDataset::load(&path, 1).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(dataset.get(0), (vec![0.5], vec![1.0]));
+    assert!(CsvDataset::load(&path, 1).is_err());
+}
+
+#[test]
+fn split_is_seeded_and_covers_every_sample() {
+    let dataset = numbered_dataset(20);
+
+    let a = split(&dataset, 0.2, 0.1, 3);
+    let b = split(&dataset, 0.2, 0.1, 3);
+    let c = split(&dataset, 0.2, 0.1, 4);
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_eq!(a.train.len(), 14);
+    assert_eq!(a.validation.len(), 4);
+    assert_eq!(a.test.len(), 2);
+
+    let mut seen: Vec<f64> = [&a.train, &a.validation, &a.test]
+        .iter()
+        .flat_map(|set| set.targets().iter().map(|t| t[0]))
+        .collect();
+    seen.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    assert_eq!(seen, (0..20).map(|i| i as f64).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic(expected = "sum to less than 1")]
+fn split_rejects_fractions_without_training_data() {
+    split(&numbered_dataset(4), 0.5, 0.5, 0);
+}
+
+#[test]
+fn min_max_scaler_maps_training_range_to_unit_interval() {
+    let train = InMemoryDataset::new(
+        vec![vec![2.0, 5.0], vec![4.0, 5.0], vec![6.0, 5.0]],
+        vec![vec![0.0]; 3],
+    );
+
+    let scaler = Scaler::min_max(&train);
+    let scaled = scaler.transform(&train);
+
+    assert_eq!(scaler.scaling(), Scaling::MinMax);
+    assert_eq!(scaled.inputs()[0], vec![0.0, 0.0]);
+    assert_eq!(scaled.inputs()[1], vec![0.5, 0.0]);
+    assert_eq!(scaled.inputs()[2], vec![1.0, 0.0]);
+    assert_eq!(scaler.transform_sample(&[8.0, 6.0]), vec![1.5, 1.0]);
+}
+
+#[test]
+fn standard_scaler_uses_training_statistics_only() {
+    let dataset = numbered_dataset(10);
+    let splits = split(&dataset, 0.3, 0.0, 1);
+
+    let scaler = Scaler::standard(&splits.train);
+    let train = scaler.transform(&splits.train);
+
+    for feature in 0..2 {
+        let column: Vec<f64> = train.inputs().iter().map(|row| row[feature]).collect();
+        let mean = column.iter().sum::<f64>() / column.len() as f64;
+        let variance = column.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / column.len() as f64;
+
+        assert!(mean.abs() < 1e-12);
+        assert!((variance - 1.0).abs() < 1e-12);
+    }
+
+    let validation = scaler.transform(&splits.validation);
+    let matrix = scaler.transform_input(&Matrix::new(splits.validation.inputs().to_vec()));
+    assert_eq!(matrix.data, validation.inputs());
+    assert_eq!(validation.targets(), splits.validation.targets());
+}
+
+#[test]
+fn data_loader_yields_batches_in_training_layout() {
+    let dataset = numbered_dataset(5);
+    let mut loader = DataLoader::new(&dataset, 2);
+
+    let batches: Vec<(Matrix, Matrix)> = loader.batches().collect();
+
+    assert_eq!(loader.len(), 3);
+    assert_eq!(batches.len(), 3);
+    assert_eq!(batches[0].0.data, vec![vec![0.0, 0.0], vec![1.0, 2.0]]);
+    assert_eq!(batches[0].1.data, vec![vec![0.0, 1.0]]);
+    assert_eq!(batches[2].0.data, vec![vec![4.0, 8.0]]);
+}
+
+#[test]
+fn shuffled_loader_is_reproducible_and_reshuffles_each_epoch() {
+    let dataset = numbered_dataset(16);
+    let order = |loader: &mut DataLoader| -> Vec<f64> {
+        loader
+            .batches()
+            .flat_map(|(_, targets)| targets.data[0].clone())
+            .collect()
+    };
+
+    let mut a = DataLoader::new(&dataset, 4).with_shuffle(9);
+    let mut b = DataLoader::new(&dataset, 4).with_shuffle(9);
+
+    let first = order(&mut a);
+    let second = order(&mut a);
+
+    assert_eq!(first, order(&mut b));
+    assert_ne!(first, second);
+
+    let mut sorted = first.clone();
+    sorted.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    assert_eq!(sorted, (0..16).map(|i| i as f64).collect::<Vec<_>>());
+}
+
+#[test]
+fn trainer_fits_mini_batches_from_a_loader() {
+    let dataset = InMemoryDataset::new(
+        vec![
+            vec![0.0, 0.0],
+            vec![0.0, 1.0],
+            vec![1.0, 0.0],
+            vec![1.0, 1.0],
+        ],
+        vec![vec![0.0], vec![1.0], vec![1.0], vec![1.0]],
+    );
+    let network = NeuralNetwork::new(vec![Layer::new(Matrix::new(vec![vec![0.1, 0.2]]))]);
+    let mut loader = DataLoader::new(&dataset, 2).with_shuffle(1);
+
+    let history = Trainer::new(200).fit_loader(&network, &mut loader, &mut []);
+
+    assert_eq!(history.epochs.len(), 200);
+    assert!(history.losses().last().unwrap() < history.losses().first().unwrap());
+}
+
+/// Records every step it is asked about, halving the rate each step
+struct RecordingSchedule(Rc<RefCell<Vec<usize>>>);
+
+impl LrSchedule for RecordingSchedule {
+    fn learning_rate(&mut self, step: usize) -> f64 {
+        self.0.borrow_mut().push(step);
+        0.5f64.powi(step as i32)
+    }
+}
+
+#[test]
+fn schedule_per_step_changes_the_rate_within_an_epoch() {
+    let dataset = numbered_dataset(4);
+    let network = NeuralNetwork::new(vec![Layer::new(Matrix::new(vec![vec![0.1, 0.2]]))]);
+    let mut loader = DataLoader::new(&dataset, 1);
+    let steps = Rc::new(RefCell::new(Vec::new()));
+
+    let history = Trainer::new(2)
+        .with_schedule(RecordingSchedule(steps.clone()))
+        .with_schedule_per_step()
+        .fit_loader(&network, &mut loader, &mut []);
+
+    // One rate per batch, counted across epochs; each epoch ends on its last batch's rate
+    assert_eq!(*steps.borrow(), (0..8).collect::<Vec<_>>());
+    assert_eq!(
+        history.learning_rates(),
+        vec![0.5f64.powi(3), 0.5f64.powi(7)]
+    );
+
+    // Scheduled per epoch, every batch of the epoch shares one rate
+    let per_epoch = Trainer::new(2)
+        .with_schedule(ExponentialDecay::new(1.0, 0.5))
+        .fit_loader(&network, &mut loader, &mut []);
+    assert_eq!(per_epoch.learning_rates(), vec![1.0, 0.5]);
+}
@@ -0,0 +1,90 @@
+use aspirina_core::matrix::Matrix;
+use aspirina_gates::layer::Layer;
+use aspirina_gates::neural_network::NeuralNetwork;
+
+mod common;
+
+use common::{xor_data, xor_network};
+
+#[test]
+fn dropout_is_identity_in_eval_mode() {
+    let with_dropout = xor_network(vec![Layer::dropout(0.5, 1)]);
+    let without_dropout = xor_network(vec![]);
+    let input = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+
+    with_dropout.eval();
+
+    assert_eq!(
+        with_dropout.predict(input.clone()).data,
+        without_dropout.predict(input).data
+    );
+}
+
+#[test]
+fn dropout_zeroes_and_rescales_in_training_mode() {
+    let network = NeuralNetwork::new(vec![Layer::dropout(0.25, 3)]);
+    let input = Matrix::new(vec![vec![1.0; 1_000]]);
+    let targets = Matrix::new(vec![vec![1.0]; 1_000]);
+
+    let output = network.train(input, targets).output;
+    let values = &output.data[0];
+
+    let dropped = values.iter().filter(|&&v| v == 0.0).count();
+    assert!(values
+        .iter()
+        .all(|&v| v == 0.0 || (v - 1.0 / 0.75).abs() < 1e-12));
+    assert!((200..300).contains(&dropped), "dropped {}", dropped);
+}
+
+#[test]
+fn same_seed_gives_same_masks() {
+    let a = xor_network(vec![Layer::dropout(0.5, 11)]);
+    let b = xor_network(vec![Layer::dropout(0.5, 11)]);
+    let input = Matrix::new(vec![vec![1.0, 1.0], vec![0.0, 1.0]]);
+    let targets = Matrix::new(vec![vec![1.0, 0.0]]);
+
+    for _ in 0..5 {
+        assert_eq!(
+            a.train(input.clone(), targets.clone()).output.data,
+            b.train(input.clone(), targets.clone()).output.data
+        );
+    }
+}
+
+#[test]
+fn predict_skips_dropout_in_training_mode() {
+    let network = xor_network(vec![Layer::dropout(0.5, 13)]);
+    let input = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+
+    assert!(network.is_training());
+    assert_eq!(
+        network.predict(input.clone()).data,
+        network.predict(input.clone()).data
+    );
+    assert_eq!(
+        network.predict(input.clone()).data,
+        xor_network(vec![]).predict(input).data
+    );
+}
+
+#[test]
+fn training_with_dropout_still_learns_xor() {
+    let network = xor_network(vec![Layer::dropout(0.1, 5)]);
+    let (input, targets) = xor_data();
+
+    for _ in 0..10_000 {
+        network.train(input.clone(), targets.clone());
+    }
+
+    network.eval();
+    let output = network.predict(input);
+    for (row, expected) in output.data.iter().zip([0.0, 1.0, 1.0, 0.0]) {
+        assert!((row[0] - expected).abs() < 0.5);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Dropout rate must be in [0, 1)")]
+fn dropout_rejects_rate_of_one() {
+    Layer::dropout(1.0, 0);
+}
@@ -0,0 +1,92 @@
+use aspirina_core::matrix::Matrix;
+use aspirina_gates::gradient_check::check_gradients;
+use aspirina_gates::layer::Layer;
+use aspirina_gates::neural_network::NeuralNetwork;
+
+mod common;
+
+use common::{xor_data, xor_network};
+
+fn and_network() -> NeuralNetwork {
+    NeuralNetwork::new(vec![
+        Layer::new(Matrix::new(vec![
+            vec![0.8, 0.8],
+            vec![0.6, 0.6],
+            vec![-0.3, -0.3],
+        ])),
+        Layer::new(Matrix::new(vec![vec![1.2, 0.8, -0.5]])),
+    ])
+}
+
+#[test]
+fn xor_gradients_match_finite_differences() {
+    let (input, targets) = xor_data();
+
+    let report = check_gradients(&xor_network(vec![]), input, targets, 1e-5);
+
+    assert_eq!(report.layers.len(), 2);
+    assert!(report.passed(1e-6), "{:?}", report);
+}
+
+#[test]
+fn and_gradients_match_finite_differences() {
+    let targets = Matrix::new(vec![vec![0.0, 0.0, 0.0, 1.0]]);
+
+    let report = check_gradients(&and_network(), xor_data().0, targets, 1e-5);
+
+    assert_eq!(report.layers.len(), 2);
+    assert!(report.passed(1e-6), "{:?}", report);
+}
+
+#[test]
+fn gradients_match_after_partial_training() {
+    let network = xor_network(vec![]);
+    let (input, targets) = xor_data();
+
+    for _ in 0..500 {
+        network.train(input.clone(), targets.clone());
+    }
+
+    let report = check_gradients(&network, input, targets, 1e-5);
+
+    assert!(report.passed(1e-6), "{:?}", report);
+}
+
+#[test]
+fn check_restores_original_weights() {
+    let network = and_network();
+    let before = network.parameters();
+    let targets = Matrix::new(vec![vec![0.0, 0.0, 0.0, 1.0]]);
+
+    check_gradients(&network, xor_data().0, targets, 1e-5);
+
+    let after = network.parameters();
+    for (before, after) in before.iter().flatten().zip(after.iter().flatten()) {
+        assert_eq!(before.data, after.data);
+    }
+}
+
+#[test]
+fn stepping_against_gradient_reduces_loss() {
+    let network = xor_network(vec![]);
+    let (input, targets) = xor_data();
+    let gradients = network.gradients(input.clone(), targets.clone());
+
+    let loss_before = network.loss(input.clone(), targets.clone());
+    let stepped: Vec<Vec<Matrix>> = network
+        .parameters()
+        .into_iter()
+        .zip(gradients)
+        .map(|(layer, layer_gradients)| {
+            layer
+                .into_iter()
+                .zip(layer_gradients)
+                .map(|(weights, gradient)| weights - gradient.scale(0.1))
+                .collect()
+        })
+        .collect();
+    network.set_parameters(stepped);
+    let loss_after = network.loss(input, targets);
+
+    assert!(loss_after < loss_before);
+}
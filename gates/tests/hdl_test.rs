@@ -0,0 +1,226 @@
+use aspirina_gates::computer::circuit::Circuit;
+use aspirina_gates::computer::full_adder::FullAdder;
+use aspirina_gates::computer::hdl::Library;
+use aspirina_gates::computer::registry::GateRegistry;
+
+const ADDERS: &str = "
+// Adds three bits
+CHIP FullAdder {
+    IN a, b, c;
+    OUT sum, carry;
+
+    PARTS:
+    HalfAdder(a=a, b=b, sum=s1, carry=c1);
+    HalfAdder(a=s1, b=c, sum=sum, carry=c2);
+    Or(a=c1, b=c2, out=carry);
+}
+
+/* Defined after its first use */
+CHIP HalfAdder {
+    IN a, b;
+    OUT sum, carry;
+
+    PARTS:
+    Xor(a=a, b=b, out=sum);
+    And(a=a, b=b, out=carry);
+}
+";
+
+fn error(source: &str) -> String {
+    Library::parse(source).unwrap_err()
+}
+
+#[test]
+fn chips_elaborate_to_neural_circuits() {
+    let registry = GateRegistry::new();
+    let library = Library::parse(ADDERS).unwrap();
+    assert_eq!(library.names(), vec!["FullAdder", "HalfAdder"]);
+
+    let circuit = library.circuit("FullAdder").unwrap();
+    assert_eq!(circuit.inputs(), &["a", "b", "c"]);
+    assert_eq!(circuit.gate_count(), 5);
+
+    let chip = library.build("FullAdder", &registry).unwrap();
+    let full_adder = FullAdder::with_registry(&registry);
+    for row in 0..8 {
+        let (a, b, c) = (row & 4 != 0, row & 2 != 0, row & 1 != 0);
+        let result = full_adder.compute(a, b, c);
+        assert_eq!(chip.compute(&[a, b, c]), vec![result.sum, result.carry]);
+    }
+
+    // Same gates, same truth tables as the hand-written netlist
+    let netlist = Circuit::full_adder().build(&registry).unwrap();
+    assert_eq!(
+        chip.truth_tables().unwrap(),
+        netlist.truth_tables().unwrap()
+    );
+}
+
+#[test]
+fn wide_gates_and_constants_are_built_in() {
+    let library = Library::parse(
+        "CHIP Vote {
+            IN x, y, z;
+            OUT agree, odd, high;
+            PARTS:
+            Majority(a=x, b=y, c=z, out=agree);
+            Parity(a=x, b=y, c=z, out=odd);
+            Buffer(in=true, out=high);
+        }",
+    )
+    .unwrap();
+    let vote = library.build("Vote", &GateRegistry::new()).unwrap();
+
+    assert_eq!(vote.compute(&[true, true, false]), vec![true, false, true]);
+    assert_eq!(vote.compute(&[true, false, false]), vec![false, true, true]);
+}
+
+#[test]
+fn chips_can_use_library_circuits_and_skip_outputs() {
+    let mut library = Library::new().with_circuit(Circuit::half_adder());
+    library
+        .add_source(
+            "CHIP Carry { IN a, b; OUT out; PARTS:
+                 HalfAdder(a=a, b=b, carry=out); }",
+        )
+        .unwrap();
+
+    let carry = library.build("Carry", &GateRegistry::new()).unwrap();
+    assert_eq!(carry.compute(&[true, true]), vec![true]);
+    assert_eq!(carry.compute(&[true, false]), vec![false]);
+}
+
+#[test]
+fn failed_sources_leave_the_library_unchanged() {
+    let mut library = Library::new();
+    let source = |part: &str| {
+        format!(
+            "CHIP A {{ IN a; OUT b; PARTS: Not(in=a, out=b); }}
+             CHIP B {{ IN a; OUT b; PARTS: {}(a=a, b=b); }}",
+            part
+        )
+    };
+
+    assert!(library
+        .add_source(&source("Bogus"))
+        .unwrap_err()
+        .ends_with("Unknown chip or gate \"Bogus\""));
+    assert!(library.names().is_empty());
+
+    // The corrected source goes in whole, with no chip already defined
+    library.add_source(&source("A")).unwrap();
+    assert_eq!(library.names(), ["A", "B"]);
+    let b = library.build("B", &GateRegistry::new()).unwrap();
+    assert_eq!(b.compute(&[true]), vec![false]);
+}
+
+#[test]
+fn libraries_load_from_files() {
+    let path = std::env::temp_dir().join("aspirina_hdl_test.hdl");
+    std::fs::write(&path, ADDERS).unwrap();
+
+    let library = Library::load(&path).unwrap();
+    assert!(library.circuit("HalfAdder").is_some());
+
+    std::fs::remove_file(&path).unwrap();
+    assert!(Library::load(&path).unwrap_err().starts_with("Cannot read"));
+}
+
+#[test]
+fn syntax_errors_report_line_and_column() {
+    assert_eq!(
+        error("CHIP A {\n  IN a, b\n  OUT c;"),
+        "Line 3, column 3: Expected ';', found \"OUT\""
+    );
+    assert_eq!(
+        error("CHIP A { IN a; OUT b; PARTS:\n  Not(in=a, out=b)\n}"),
+        "Line 3, column 1: Expected ';', found '}'"
+    );
+    assert_eq!(
+        error("CHIP A { IN a; OUT b; PARTS: Not(in=a out=b); }"),
+        "Line 1, column 39: Expected ')', found \"out\""
+    );
+    assert_eq!(
+        error("CHIP A { IN a; OUT b; PARTS:"),
+        "Line 1, column 29: Expected a name, found end of file"
+    );
+    assert_eq!(
+        error("CHIP A {\n  IN a[4];"),
+        "Line 2, column 7: Unexpected character '['"
+    );
+    assert_eq!(
+        error("/* never closed"),
+        "Line 1, column 1: Unterminated comment"
+    );
+    assert_eq!(
+        error("CHIP A { IN a; PARTS: }"),
+        "Line 1, column 16: Expected OUT, found \"PARTS\""
+    );
+}
+
+#[test]
+fn elaboration_errors_report_line_and_column() {
+    assert_eq!(
+        error("CHIP A { IN a; OUT b; PARTS:\n    Foo(in=a, out=b); }"),
+        "Line 2, column 5: Unknown chip or gate \"Foo\""
+    );
+    assert_eq!(
+        error("CHIP A { IN a; OUT b; PARTS:\n    Not(in=a, x=a, out=b); }"),
+        "Line 2, column 15: Not has no pin x"
+    );
+    assert_eq!(
+        error("CHIP A { IN a; OUT b; PARTS:\n    And(a=a, out=b); }"),
+        "Line 2, column 5: AND gate cannot take 1 input(s)"
+    );
+    assert_eq!(
+        error("CHIP A { IN a; OUT b; PARTS:\n    Or(a=a, b=a); }"),
+        "Line 2, column 5: Or is missing pin out"
+    );
+    assert_eq!(
+        error("CHIP A { IN a; OUT b; PARTS:\n    Not(in=a, in=a, out=b); }"),
+        "Line 2, column 15: Pin in is connected more than once"
+    );
+    assert_eq!(
+        error("CHIP A { IN a; OUT b; PARTS: Not(in=c, out=b); }"),
+        "Line 1, column 34: A: wire c is never driven"
+    );
+    assert_eq!(
+        error("CHIP A { IN a; OUT b, c; PARTS: Not(in=a, out=b); }"),
+        "Line 1, column 23: A: wire c is never driven"
+    );
+    assert_eq!(
+        error("CHIP A { IN a; OUT b; PARTS:\n    Not(in=a, out=b);\n    Buffer(in=a, out=b); }"),
+        "Line 3, column 18: A: wire b is driven more than once"
+    );
+    assert_eq!(
+        error("CHIP A { IN a, a; OUT b; PARTS: Not(in=a, out=b); }"),
+        "Line 1, column 16: A: wire a is driven more than once"
+    );
+    assert_eq!(
+        error("CHIP A { IN a; OUT b; PARTS: Not(in=a, out=b); }\nCHIP A { IN a; OUT b; PARTS: }"),
+        "Line 2, column 1: Chip A is already defined"
+    );
+}
+
+#[test]
+fn recursive_and_cyclic_chips_are_rejected() {
+    assert_eq!(
+        error("CHIP Loop { IN a; OUT b; PARTS: Loop(a=a, b=b); }"),
+        "Line 1, column 1: Chip Loop contains itself"
+    );
+    assert_eq!(
+        error(
+            "CHIP Latch {\n    IN s, r;\n    OUT q;\n    PARTS:\n    \
+             Nor(a=r, b=qn, out=q);\n    Nor(a=s, b=q, out=qn);\n}"
+        ),
+        "Line 5, column 5: Latch: combinational cycle through wires q, qn"
+    );
+    // The cycle starts at the second part; the first only depends on it
+    assert_eq!(
+        error(
+            "CHIP Ring { IN a; OUT b; PARTS:\n    Not(in=x, out=b);\n    \
+             And(a=a, b=y, out=x);\n    Buffer(in=x, out=y); }"
+        ),
+        "Line 3, column 5: Ring: combinational cycle through wires b, x, y"
+    );
+}
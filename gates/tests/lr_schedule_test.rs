@@ -0,0 +1,160 @@
+use aspirina_core::matrix::Matrix;
+use aspirina_gates::layer::Layer;
+use aspirina_gates::lr_schedule::{
+    ConstantLr, CosineAnnealingWarmRestarts, ExponentialDecay, LinearWarmup, LrSchedule,
+    ReduceOnPlateau, StepDecay,
+};
+use aspirina_gates::neural_network::NeuralNetwork;
+use aspirina_gates::trainer::Trainer;
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-12,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+fn curve(schedule: &mut dyn LrSchedule, steps: usize) -> Vec<f64> {
+    (0..steps)
+        .map(|step| schedule.learning_rate(step))
+        .collect()
+}
+
+#[test]
+fn constant_never_changes() {
+    let mut schedule = ConstantLr::new(0.3);
+
+    assert!(curve(&mut schedule, 50).iter().all(|&lr| lr == 0.3));
+}
+
+#[test]
+fn step_decay_drops_every_step_size() {
+    let mut schedule = StepDecay::new(0.8, 3, 0.5);
+
+    assert_eq!(
+        curve(&mut schedule, 8),
+        vec![0.8, 0.8, 0.8, 0.4, 0.4, 0.4, 0.2, 0.2]
+    );
+}
+
+#[test]
+fn exponential_decay_multiplies_every_step() {
+    let mut schedule = ExponentialDecay::new(2.0, 0.9);
+
+    assert_close(schedule.learning_rate(0), 2.0);
+    assert_close(schedule.learning_rate(1), 1.8);
+    assert_close(schedule.learning_rate(5), 2.0 * 0.9f64.powi(5));
+}
+
+#[test]
+fn cosine_annealing_follows_half_cosine_within_a_cycle() {
+    let mut schedule = CosineAnnealingWarmRestarts::new(1.0, 0.0, 4, 1);
+
+    assert_close(schedule.learning_rate(0), 1.0);
+    assert_close(
+        schedule.learning_rate(1),
+        0.5 * (1.0 + (std::f64::consts::PI / 4.0).cos()),
+    );
+    assert_close(schedule.learning_rate(2), 0.5);
+    assert_close(schedule.learning_rate(4), 1.0); // warm restart
+}
+
+#[test]
+fn cosine_restart_periods_grow_by_multiplier() {
+    let mut schedule = CosineAnnealingWarmRestarts::new(0.5, 0.1, 2, 2);
+    let lrs = curve(&mut schedule, 14);
+
+    // Cycles of length 2, 4 and 8 start at steps 0, 2 and 6
+    let restarts: Vec<usize> = (0..lrs.len()).filter(|&i| lrs[i] == 0.5).collect();
+    assert_eq!(restarts, vec![0, 2, 6]);
+    assert!(lrs.iter().all(|&lr| (0.1..=0.5).contains(&lr)));
+    assert_close(lrs[4], 0.3); // halfway through the 4-step cycle
+}
+
+#[test]
+fn linear_warmup_ramps_then_defers() {
+    let mut schedule = LinearWarmup::new(4, StepDecay::new(1.0, 2, 0.1));
+
+    assert_close(schedule.learning_rate(0), 0.25);
+    assert_close(schedule.learning_rate(1), 0.5);
+    assert_close(schedule.learning_rate(3), 1.0);
+    assert_close(schedule.learning_rate(4), 1.0);
+    assert_close(schedule.learning_rate(6), 0.1);
+}
+
+#[test]
+fn reduce_on_plateau_waits_for_patience() {
+    let mut schedule = ReduceOnPlateau::new(1.0, 0.5, 2, 0.2);
+
+    for loss in [1.0, 0.9, 0.8] {
+        schedule.observe(loss);
+    }
+    assert_close(schedule.learning_rate(0), 1.0);
+
+    // Two stale epochs are tolerated, the third triggers a reduction
+    schedule.observe(0.8);
+    schedule.observe(0.85);
+    assert_close(schedule.learning_rate(0), 1.0);
+    schedule.observe(0.81);
+    assert_close(schedule.learning_rate(0), 0.5);
+
+    for _ in 0..20 {
+        schedule.observe(1.0);
+    }
+    assert_close(schedule.learning_rate(0), 0.2);
+}
+
+#[test]
+fn reduce_on_plateau_threshold_ignores_tiny_improvements() {
+    let mut schedule = ReduceOnPlateau::new(1.0, 0.1, 0, 0.0).with_threshold(0.1);
+
+    schedule.observe(1.0);
+    schedule.observe(0.95);
+
+    assert_close(schedule.learning_rate(0), 0.1);
+}
+
+#[test]
+fn trainer_applies_schedule_every_epoch() {
+    let network = NeuralNetwork::new(vec![Layer::new(Matrix::new(vec![vec![0.5, 0.5]]))]);
+    let input = Matrix::new(vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+    let targets = Matrix::new(vec![vec![1.0, 1.0]]);
+
+    let mut trainer = Trainer::new(7).with_schedule(StepDecay::new(1.0, 3, 0.5));
+    trainer.fit(&network, input, targets);
+
+    // The last epoch (index 6) ran with 1.0 · 0.5²
+    assert_close(network.learning_rate(), 0.25);
+}
+
+#[test]
+fn zero_learning_rate_freezes_weights() {
+    let network = NeuralNetwork::new(vec![Layer::new(Matrix::new(vec![vec![0.5, 0.5]]))])
+        .with_learning_rate(0.0);
+    let input = Matrix::new(vec![vec![0.0, 1.0]]);
+    let targets = Matrix::new(vec![vec![1.0]]);
+
+    Trainer::new(10).fit(&network, input, targets);
+
+    assert_eq!(network.parameters()[0][0].data, vec![vec![0.5, 0.5]]);
+}
+
+#[test]
+fn trainer_feeds_validation_loss_to_plateau_schedule() {
+    let network = NeuralNetwork::new(vec![Layer::new(Matrix::new(vec![vec![0.5, 0.5]]))]);
+    let input = Matrix::new(vec![vec![0.0, 1.0]]);
+    let targets = Matrix::new(vec![vec![1.0]]);
+
+    // Sigmoid outputs never reach the validation target of 2, so the loss plateaus
+    let mut trainer = Trainer::new(50)
+        .with_schedule(ReduceOnPlateau::new(1.0, 0.5, 3, 0.01).with_threshold(0.01))
+        .with_validation(
+            Matrix::new(vec![vec![1.0, 0.0]]),
+            Matrix::new(vec![vec![2.0]]),
+        );
+    trainer.fit(&network, input, targets);
+
+    assert!(network.learning_rate() < 1.0);
+}
@@ -0,0 +1,130 @@
+use aspirina_core::matrix::Matrix;
+use aspirina_gates::metrics::{
+    accuracy, f1_score, mean_squared_error, precision, r_squared, recall, roc_auc, ConfusionMatrix,
+};
+
+fn column(values: &[f64]) -> Matrix {
+    Matrix::new(values.iter().map(|v| vec![*v]).collect())
+}
+
+#[test]
+fn confusion_matrix_counts_each_outcome() {
+    let predictions = column(&[0.9, 0.8, 0.3, 0.1, 0.6]);
+    let targets = column(&[1.0, 0.0, 1.0, 0.0, 1.0]);
+
+    let confusion = ConfusionMatrix::new(&predictions, &targets, 0.5);
+
+    assert_eq!(
+        confusion,
+        ConfusionMatrix {
+            true_positives: 2,
+            false_positives: 1,
+            true_negatives: 1,
+            false_negatives: 1,
+        }
+    );
+    assert_eq!(confusion.total(), 5);
+}
+
+#[test]
+fn classification_scores_follow_from_counts() {
+    let predictions = column(&[0.9, 0.8, 0.3, 0.1, 0.6]);
+    let targets = column(&[1.0, 0.0, 1.0, 0.0, 1.0]);
+
+    assert_eq!(accuracy(&predictions, &targets, 0.5), 0.6);
+    assert_eq!(precision(&predictions, &targets, 0.5), 2.0 / 3.0);
+    assert_eq!(recall(&predictions, &targets, 0.5), 2.0 / 3.0);
+    assert!((f1_score(&predictions, &targets, 0.5) - 2.0 / 3.0).abs() < 1e-12);
+}
+
+#[test]
+fn threshold_changes_the_decision() {
+    let predictions = column(&[0.7, 0.4]);
+    let targets = column(&[1.0, 0.0]);
+
+    assert_eq!(accuracy(&predictions, &targets, 0.5), 1.0);
+    assert_eq!(accuracy(&predictions, &targets, 0.8), 0.5);
+    assert_eq!(accuracy(&predictions, &targets, 0.3), 0.5);
+}
+
+#[test]
+fn scores_without_positives_are_zero() {
+    let predictions = column(&[0.1, 0.2]);
+    let targets = column(&[0.0, 0.0]);
+
+    let confusion = ConfusionMatrix::new(&predictions, &targets, 0.5);
+
+    assert_eq!(confusion.precision(), 0.0);
+    assert_eq!(confusion.recall(), 0.0);
+    assert_eq!(confusion.f1_score(), 0.0);
+    assert_eq!(confusion.accuracy(), 1.0);
+}
+
+#[test]
+fn confusion_matrices_can_be_accumulated() {
+    let mut total = ConfusionMatrix::new(&column(&[0.9]), &column(&[1.0]), 0.5);
+    total += ConfusionMatrix::new(&column(&[0.9]), &column(&[0.0]), 0.5);
+
+    assert_eq!(total.true_positives, 1);
+    assert_eq!(total.false_positives, 1);
+    assert_eq!(total.accuracy(), 0.5);
+}
+
+#[test]
+fn metrics_compare_every_element_of_wide_outputs() {
+    let predictions = Matrix::new(vec![vec![0.9, 0.1], vec![0.2, 0.4]]);
+    let targets = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+
+    assert_eq!(accuracy(&predictions, &targets, 0.5), 0.75);
+}
+
+#[test]
+#[should_panic(expected = "Incompatible dimensions")]
+fn metrics_reject_mismatched_shapes() {
+    accuracy(
+        &column(&[0.1, 0.2]),
+        &Matrix::new(vec![vec![0.0, 1.0]]),
+        0.5,
+    );
+}
+
+#[test]
+fn roc_auc_measures_ranking() {
+    let targets = column(&[0.0, 0.0, 1.0, 1.0]);
+
+    assert_eq!(roc_auc(&column(&[0.1, 0.2, 0.8, 0.9]), &targets), Some(1.0));
+    assert_eq!(roc_auc(&column(&[0.9, 0.8, 0.2, 0.1]), &targets), Some(0.0));
+    assert_eq!(roc_auc(&column(&[0.5, 0.5, 0.5, 0.5]), &targets), Some(0.5));
+    assert_eq!(
+        roc_auc(&column(&[0.1, 0.4, 0.35, 0.8]), &targets),
+        Some(0.75)
+    );
+}
+
+#[test]
+fn roc_auc_is_undefined_for_a_single_class() {
+    assert_eq!(roc_auc(&column(&[0.1, 0.9]), &column(&[1.0, 1.0])), None);
+}
+
+#[test]
+fn regression_metrics() {
+    let targets = column(&[1.0, 2.0, 3.0, 4.0]);
+
+    assert_eq!(mean_squared_error(&targets, &targets), 0.0);
+    assert_eq!(r_squared(&targets, &targets), 1.0);
+
+    let predictions = column(&[1.5, 2.0, 2.5, 4.0]);
+    assert_eq!(mean_squared_error(&predictions, &targets), 0.125);
+    assert_eq!(r_squared(&predictions, &targets), 0.9);
+
+    let mean = column(&[2.5, 2.5, 2.5, 2.5]);
+    assert_eq!(r_squared(&mean, &targets), 0.0);
+}
+
+#[test]
+fn r_squared_with_constant_targets() {
+    let targets = column(&[1.0, 1.0]);
+
+    assert_eq!(r_squared(&targets, &targets), 1.0);
+    assert_eq!(r_squared(&column(&[0.0, 1.0]), &targets), 0.0);
+}
@@ -0,0 +1,170 @@
+use aspirina_gates::music::{
+    training_data, Chord, ChordProgression, Genre, MusicPredictor, Quality, CHORD_COUNT,
+    JAZZ_TURNAROUND, POP, TWELVE_BAR_BLUES,
+};
+
+fn roots(progression: &ChordProgression) -> Vec<u8> {
+    progression.to_chords().iter().map(|c| c.root).collect()
+}
+
+#[test]
+fn chords_encode_quality_times_twelve_plus_root() {
+    assert_eq!(Chord::parse("C").unwrap().encode(), 0x00);
+    assert_eq!(Chord::parse("C#").unwrap().encode(), 0x01);
+    assert_eq!(Chord::parse("D").unwrap().encode(), 0x02);
+    assert_eq!(Chord::parse("Am").unwrap().encode(), 0x15);
+    assert_eq!(Chord::parse("G7").unwrap().encode(), 0x1F);
+    assert_eq!(Chord::parse("Bdim").unwrap().encode(), 0x2F);
+
+    for code in 0..CHORD_COUNT as u8 {
+        assert_eq!(Chord::decode(code).encode(), code);
+    }
+}
+
+#[test]
+fn chord_names_round_trip_with_sharps() {
+    assert_eq!(
+        Chord::parse("Bb7").unwrap(),
+        Chord::new(10, Quality::Dominant7)
+    );
+    assert_eq!(Chord::parse("Bb7").unwrap().to_string(), "A#7");
+    assert_eq!(Chord::parse("F#m").unwrap().to_string(), "F#m");
+    assert!(Chord::parse("H").is_err());
+    assert!(Chord::parse("Cx").is_err());
+}
+
+#[test]
+fn roman_numerals_follow_the_major_scale() {
+    let pop = ChordProgression::from_roman(POP, 0, Genre::Pop).unwrap();
+    assert_eq!(roots(&pop), vec![0, 7, 9, 5]);
+    assert_eq!(pop.names(), vec!["C", "G", "Am", "F"]);
+
+    let jazz = ChordProgression::from_roman(JAZZ_TURNAROUND, 0, Genre::Jazz).unwrap();
+    assert_eq!(roots(&jazz), vec![2, 7, 0]);
+    assert_eq!(jazz.genre(), Some(Genre::Jazz));
+
+    let cadence = ChordProgression::from_roman("ii V7 I", 0, Genre::Jazz).unwrap();
+    assert_eq!(cadence.names(), vec!["Dm", "G7", "C"]);
+    // Sevenths are dominant, so only major numerals take the suffix
+    assert_eq!(
+        ChordProgression::from_roman("ii7 V7 I", 0, Genre::Jazz).unwrap_err(),
+        "Unknown roman numeral \"ii7\""
+    );
+
+    let flats = ChordProgression::from_roman("bVII viio vii° #IV", 0, Genre::Pop);
+    assert!(flats.is_err());
+    let flats = ChordProgression::from_roman("bVII vii° #IV", 0, Genre::Pop).unwrap();
+    assert_eq!(flats.names(), vec!["A#", "Bdim", "F#"]);
+}
+
+#[test]
+fn twelve_bar_blues_uses_dominant_sevenths() {
+    let blues = ChordProgression::from_roman(TWELVE_BAR_BLUES, 9, Genre::Blues).unwrap();
+
+    assert_eq!(blues.chords.len(), 12);
+    assert_eq!(
+        blues.names(),
+        vec!["A7", "A7", "A7", "A7", "D7", "D7", "A7", "A7", "E7", "D7", "A7", "E7"]
+    );
+}
+
+#[test]
+fn transposition_moves_key_and_chords() {
+    let pop = ChordProgression::from_roman(POP, 0, Genre::Pop).unwrap();
+
+    let in_g = pop.transpose(7);
+    assert_eq!(in_g.key, 7);
+    assert_eq!(
+        in_g,
+        ChordProgression::from_roman(POP, 7, Genre::Pop).unwrap()
+    );
+    assert_eq!(in_g.transpose(-7), pop);
+
+    let keys = pop.transpositions();
+    assert_eq!(keys.len(), 12);
+    assert_eq!(keys[2].names(), vec!["D", "A", "Bm", "G"]);
+}
+
+#[test]
+fn training_data_wraps_around_each_progression() {
+    let jazz = ChordProgression::parse("Em-A-D", 2, Genre::Jazz).unwrap();
+
+    let (input, targets) = training_data(&[jazz], 2).unwrap();
+
+    assert_eq!(input.data.len(), 3);
+    assert_eq!(input.data[0].len(), 2 * CHORD_COUNT + 3);
+    assert_eq!(targets.data.len(), CHORD_COUNT);
+    assert_eq!(targets.data[0].len(), 3);
+
+    // Dm, G → C after transposing to C
+    assert_eq!(input.data[0][0x0E], 1.0);
+    assert_eq!(input.data[0][CHORD_COUNT + 0x07], 1.0);
+    assert_eq!(input.data[0][2 * CHORD_COUNT + Genre::Jazz as usize], 1.0);
+    assert_eq!(targets.data[0x00][0], 1.0);
+    // G, C → Dm: the last chord is followed by the first
+    assert_eq!(targets.data[0x0E][1], 1.0);
+    // C, Dm → G
+    assert_eq!(targets.data[0x07][2], 1.0);
+}
+
+#[test]
+fn predictor_ranks_the_learned_chord_first_in_any_key() {
+    let progressions = vec![
+        ChordProgression::from_roman(POP, 0, Genre::Pop).unwrap(),
+        ChordProgression::from_roman(JAZZ_TURNAROUND, 0, Genre::Jazz).unwrap(),
+    ];
+    let predictor = MusicPredictor::new(2, 16, 42);
+
+    let loss = predictor.train(&progressions, 2000).unwrap();
+    assert!(loss < 0.05, "loss {}", loss);
+
+    let prompt = ChordProgression::parse("Am D", 7, Genre::Jazz).unwrap();
+    let suggestions = predictor.suggest(&prompt, 3).unwrap();
+    assert_eq!(suggestions.len(), 3);
+    assert_eq!(suggestions[0].chord.to_string(), "G");
+    assert!(suggestions[0].score > suggestions[1].score);
+
+    let prompt = ChordProgression::parse("C G", 0, Genre::Pop).unwrap();
+    assert_eq!(
+        predictor.suggest(&prompt, 1).unwrap()[0].chord.to_string(),
+        "Am"
+    );
+}
+
+#[test]
+fn predictor_needs_enough_context() {
+    let predictor = MusicPredictor::new(2, 8, 1);
+    let prompt = ChordProgression::parse("C", 0, Genre::Pop).unwrap();
+
+    assert_eq!(
+        predictor.predict(&prompt).unwrap_err(),
+        "Expected at least 2 chords, found 1"
+    );
+}
+
+#[test]
+fn unknown_codes_are_rejected_instead_of_panicking() {
+    let predictor = MusicPredictor::new(2, 8, 1);
+    let mut bad_genre = ChordProgression::parse("C G", 0, Genre::Pop).unwrap();
+    bad_genre.genre = 3;
+    let mut bad_chord = ChordProgression::parse("C G", 0, Genre::Pop).unwrap();
+    bad_chord.chords[1] = CHORD_COUNT as u8;
+
+    assert_eq!(
+        predictor.predict(&bad_genre).unwrap_err(),
+        "Unknown genre code 3"
+    );
+    assert_eq!(
+        predictor.predict(&bad_chord).unwrap_err(),
+        "Unknown chord code 0x30"
+    );
+    assert_eq!(
+        training_data(&[bad_chord.clone()], 2).unwrap_err(),
+        "Unknown chord code 0x30"
+    );
+    assert!(predictor.train(&[bad_genre], 10).is_err());
+    assert_eq!(
+        training_data(&[bad_chord.prefix(0)], 2).unwrap_err(),
+        "Progression must not be empty"
+    );
+}
@@ -20,3 +20,16 @@ fn create_neural_network() {
     assert_eq!(result.data.len(), 1);
     assert_eq!(result.data[0].len(), 1);
 }
+
+#[test]
+fn network_starts_in_training_mode_and_switches() {
+    let network = NeuralNetwork::new(vec![Layer::new(Matrix::new(vec![vec![0.5, 0.5]]))]);
+
+    assert!(network.is_training());
+
+    network.eval();
+    assert!(!network.is_training());
+
+    network.train_mode();
+    assert!(network.is_training());
+}
@@ -0,0 +1,201 @@
+use aspirina_core::matrix::Matrix;
+use aspirina_gates::gradient_check::check_gradients;
+use aspirina_gates::layer::{Layer, LayerKind};
+use aspirina_gates::neural_network::NeuralNetwork;
+
+mod common;
+
+use common::{xor_data, xor_network};
+
+fn column_mean_and_variance(matrix: &Matrix, column: usize) -> (f64, f64) {
+    let values: Vec<f64> = matrix.data.iter().map(|row| row[column]).collect();
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    (mean, variance)
+}
+
+#[test]
+fn batch_norm_normalizes_each_feature_over_the_batch() {
+    let network = NeuralNetwork::new(vec![Layer::batch_norm(2)]);
+    let input = Matrix::new(vec![vec![1.0, 10.0], vec![3.0, 20.0], vec![5.0, 60.0]]);
+    let targets = Matrix::new(vec![vec![0.0; 3]; 2]);
+
+    let output = network.train(input, targets).output;
+
+    for column in 0..2 {
+        let (mean, variance) = column_mean_and_variance(&output, column);
+        assert!(mean.abs() < 1e-9);
+        assert!((variance - 1.0).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn layer_norm_normalizes_each_sample_over_its_features() {
+    let network = NeuralNetwork::new(vec![Layer::layer_norm(3)]);
+    let input = Matrix::new(vec![vec![1.0, 2.0, 3.0], vec![-4.0, 0.0, 10.0]]);
+
+    let output = network.predict(input);
+
+    for row in &output.data {
+        let mean = row.iter().sum::<f64>() / 3.0;
+        let variance = row.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / 3.0;
+        assert!(mean.abs() < 1e-9);
+        assert!((variance - 1.0).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn batch_norm_uses_running_statistics_in_eval_mode() {
+    let network = NeuralNetwork::new(vec![Layer::batch_norm(1)]);
+    let input = Matrix::new(vec![vec![2.0], vec![4.0], vec![6.0], vec![8.0]]);
+    let targets = Matrix::new(vec![vec![0.0; 4]]);
+
+    // Training-mode forwards update the running statistics
+    for _ in 0..200 {
+        network.loss(input.clone(), targets.clone());
+    }
+
+    network.eval();
+    let output = network.predict(Matrix::new(vec![vec![5.0]]));
+
+    // Running mean converges to 5, so the batch mean maps to zero in eval mode
+    assert!(output.data[0][0].abs() < 1e-3);
+}
+
+#[test]
+fn batch_norm_gradients_match_finite_differences() {
+    let (input, targets) = xor_data();
+
+    let report = check_gradients(
+        &xor_network(vec![Layer::batch_norm(4)]),
+        input,
+        targets,
+        1e-5,
+    );
+
+    assert_eq!(report.layers.len(), 3);
+    assert!(report.passed(1e-5), "{:?}", report);
+}
+
+#[test]
+fn layer_norm_gradients_match_finite_differences() {
+    let (input, targets) = xor_data();
+
+    let report = check_gradients(
+        &xor_network(vec![Layer::layer_norm(4)]),
+        input,
+        targets,
+        1e-5,
+    );
+
+    assert_eq!(report.layers.len(), 3);
+    assert!(report.passed(1e-5), "{:?}", report);
+}
+
+#[test]
+fn batch_norm_gradients_match_in_eval_mode() {
+    let (input, targets) = xor_data();
+    let network = xor_network(vec![Layer::batch_norm(4)]);
+    network.loss(input.clone(), targets.clone());
+    network.eval();
+
+    let report = check_gradients(&network, input, targets, 1e-5);
+
+    assert!(report.passed(1e-5), "{:?}", report);
+}
+
+#[test]
+fn gradient_check_keeps_running_statistics() {
+    let (input, targets) = xor_data();
+    let network = xor_network(vec![Layer::batch_norm(4)]);
+    for _ in 0..20 {
+        network.train(input.clone(), targets.clone());
+    }
+
+    // Eval-mode predictions depend on the running mean and variance only
+    network.eval();
+    let before = network.predict(input.clone());
+    network.train_mode();
+
+    check_gradients(&network, input.clone(), targets, 1e-5);
+
+    network.eval();
+    assert_eq!(network.predict(input).data, before.data);
+}
+
+#[test]
+fn predicting_a_single_row_uses_running_statistics() {
+    let (input, targets) = xor_data();
+    let network = xor_network(vec![Layer::batch_norm(4)]);
+    for _ in 0..20 {
+        network.train(input.clone(), targets.clone());
+    }
+    let batch = network.predict(input.clone());
+
+    // A lone row normalized over its own batch would collapse to beta
+    let row = network.predict(Matrix::new(vec![input.data[1].clone()]));
+
+    assert_eq!(row.data[0], batch.data[1]);
+    assert_eq!(network.predict(input).data, batch.data);
+
+    let normalization = NeuralNetwork::new(vec![Layer::batch_norm(2)]);
+    let output = normalization.predict(Matrix::new(vec![vec![3.0, -2.0]]));
+    assert!((output.data[0][0] - 3.0).abs() < 1e-3);
+    assert!((output.data[0][1] + 2.0).abs() < 1e-3);
+}
+
+#[test]
+fn training_updates_scale_and_shift() {
+    let (input, targets) = xor_data();
+    let network = xor_network(vec![Layer::layer_norm(4)]);
+    let before = network.parameters()[1].clone();
+
+    for _ in 0..100 {
+        network.train(input.clone(), targets.clone());
+    }
+
+    let after = network.parameters()[1].clone();
+    assert_ne!(before[0].data, after[0].data);
+    assert_ne!(before[1].data, after[1].data);
+}
+
+#[test]
+fn normalized_deep_stack_reduces_loss() {
+    let (input, targets) = xor_data();
+    let network = NeuralNetwork::new(vec![
+        Layer::new(Matrix::new(vec![
+            vec![0.5, 0.5],
+            vec![-0.3, -0.3],
+            vec![0.8, -0.8],
+            vec![-0.6, 0.6],
+        ])),
+        Layer::batch_norm(4),
+        Layer::new(Matrix::new(vec![
+            vec![0.2, -0.4, 0.6, -0.1],
+            vec![-0.5, 0.3, 0.1, 0.7],
+            vec![0.4, 0.4, -0.6, 0.2],
+        ])),
+        Layer::batch_norm(3),
+        Layer::new(Matrix::new(vec![vec![0.3, -0.2, 0.5]])),
+    ]);
+
+    let initial_loss = network.loss(input.clone(), targets.clone());
+    for _ in 0..2_000 {
+        network.train(input.clone(), targets.clone());
+    }
+
+    assert!(network.loss(input, targets) < initial_loss * 0.5);
+}
+
+#[test]
+fn normalization_layers_expose_scale_and_shift() {
+    let layer = Layer::batch_norm(3);
+
+    match &layer.kind {
+        LayerKind::BatchNorm(norm) => {
+            assert_eq!(norm.gamma.data, vec![vec![1.0, 1.0, 1.0]]);
+            assert_eq!(norm.beta.data, vec![vec![0.0, 0.0, 0.0]]);
+        }
+        _ => panic!("expected a batch normalization layer"),
+    }
+}
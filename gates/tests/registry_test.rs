@@ -0,0 +1,159 @@
+use aspirina_gates::computer::alu::{ALUOperation, ALU};
+use aspirina_gates::computer::cpu::SimpleCPU;
+use aspirina_gates::computer::gates::{GateType, LogicGate};
+use aspirina_gates::computer::registry::GateRegistry;
+use std::rc::Rc;
+
+fn outputs(gate: &LogicGate) -> Vec<f64> {
+    gate.truth_table()
+        .into_iter()
+        .map(|(inputs, _)| gate.compute(inputs))
+        .collect()
+}
+
+#[test]
+fn each_gate_is_trained_once_and_shared() {
+    let registry = GateRegistry::new();
+    assert!(registry.is_empty());
+
+    let first = registry.get(GateType::XOR, 2);
+    let second = registry.get(GateType::XOR, 2);
+
+    assert!(Rc::ptr_eq(&first, &second));
+    assert!(first.is_exact());
+    assert_eq!(registry.len(), 1);
+    assert!(registry.contains(&GateType::XOR, 2));
+    assert!(!registry.contains(&GateType::XOR, 3));
+}
+
+#[test]
+fn components_share_the_registry_gates() {
+    let registry = GateRegistry::new();
+
+    let alu = ALU::with_registry(&registry);
+    assert_eq!(registry.len(), 3); // AND, OR and XOR
+    assert_eq!(alu.compute(7, 6, ALUOperation::Add).result, 13);
+
+    let mut cpu = SimpleCPU::with_registry(&registry);
+    assert_eq!(registry.len(), 3);
+    cpu.load_program(&[0xA, 0x5, 0xB, 0xF]); // LDI 5, INC, HALT
+    cpu.run(10);
+    assert_eq!(cpu.registers.accumulator.read(), 6);
+
+    ALU::wide_with_registry(&registry);
+    assert_eq!(registry.len(), 6); // plus PARITY(3), MAJORITY(3) and NOR(4)
+}
+
+#[test]
+fn cloned_gates_are_independent() {
+    let registry = GateRegistry::new();
+    let shared = registry.get(GateType::AND, 2);
+    let before = outputs(&shared);
+
+    let copy = registry.gate(GateType::AND, 2);
+    assert_eq!(outputs(&copy), before);
+
+    copy.train(100);
+    assert_eq!(outputs(&shared), before);
+}
+
+#[test]
+fn registry_round_trips_through_text() {
+    let registry = GateRegistry::new();
+    let nor = registry.get(GateType::NOR, 2);
+    let majority = registry.get(GateType::MAJORITY, 3);
+
+    let restored = GateRegistry::parse(&registry.to_text()).unwrap();
+
+    assert_eq!(restored.len(), 2);
+    assert_eq!(outputs(&restored.get(GateType::NOR, 2)), outputs(&nor));
+    assert_eq!(
+        outputs(&restored.get(GateType::MAJORITY, 3)),
+        outputs(&majority)
+    );
+}
+
+#[test]
+fn later_runs_load_instead_of_training() {
+    let path = std::env::temp_dir().join("aspirina_gate_registry_test.txt");
+    let _ = std::fs::remove_file(&path);
+
+    let registry = GateRegistry::load_or_new(&path).unwrap();
+    assert!(registry.is_empty());
+    let trained = outputs(&registry.get(GateType::OR, 2));
+    registry.save(&path).unwrap();
+
+    // Zero epochs: a gate missing from the file would fail verification
+    let loaded = GateRegistry::load_or_new(&path).unwrap().with_epochs(0);
+    assert_eq!(outputs(&loaded.get(GateType::OR, 2)), trained);
+
+    std::fs::remove_file(&path).unwrap();
+    assert!(GateRegistry::load(&path)
+        .unwrap_err()
+        .starts_with("Cannot read"));
+}
+
+#[test]
+fn registry_files_are_checked() {
+    assert_eq!(
+        GateRegistry::parse("gate FOO 2").unwrap_err(),
+        "Line 1: Unknown gate type \"FOO\""
+    );
+    assert_eq!(
+        GateRegistry::parse("# header\nrow 1 2").unwrap_err(),
+        "Line 2: row outside a matrix"
+    );
+    assert_eq!(
+        GateRegistry::parse("gate NOT 2").unwrap_err(),
+        "Line 1: NOT gate cannot take 2 input(s)"
+    );
+    assert_eq!(
+        GateRegistry::parse("gate AND 2\nlayer\nmatrix\nrow 1 1").unwrap_err(),
+        "Line 1: weights do not match the AND gate's network"
+    );
+
+    // Untrained weights have the right shape but the wrong truth table
+    let untrained = LogicGate::new(GateType::XOR, 2);
+    let mut text = String::from("gate XOR 2\n");
+    for layer in untrained.parameters() {
+        text.push_str("layer\n");
+        for matrix in layer {
+            text.push_str("matrix\n");
+            for row in matrix.data {
+                let values: Vec<String> = row.iter().map(f64::to_string).collect();
+                text.push_str(&format!("row {}\n", values.join(" ")));
+            }
+        }
+    }
+    assert_eq!(
+        GateRegistry::parse(&text).unwrap_err(),
+        "Line 1: XOR gate does not reproduce its truth table"
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "XOR gate with 2 input(s) failed verification after 3 attempt(s) of 0 epochs"
+)]
+fn untrained_gates_fail_verification() {
+    GateRegistry::new().with_epochs(0).get(GateType::XOR, 2);
+}
+
+#[test]
+fn registry_margin_is_configurable() {
+    let registry = GateRegistry::new().with_margin(0.45).with_attempts(2);
+
+    assert!(registry.get(GateType::OR, 2).verify().margin > 0.45);
+}
+
+#[test]
+fn gate_types_parse_their_display_names() {
+    for gate_type in GateType::ALL {
+        assert_eq!(GateType::parse(&gate_type.to_string()).unwrap(), gate_type);
+    }
+    assert_eq!(
+        GateType::parse("THRESHOLD(3)").unwrap(),
+        GateType::THRESHOLD(3)
+    );
+    assert!(GateType::parse("THRESHOLD(x)").is_err());
+}
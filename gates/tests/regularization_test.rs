@@ -0,0 +1,81 @@
+use aspirina_core::matrix::Matrix;
+use aspirina_gates::gradient_check::check_gradients;
+use aspirina_gates::neural_network::NeuralNetwork;
+use aspirina_gates::regularization::Regularization;
+
+mod common;
+
+use common::{xor_data, xor_network};
+
+fn squared_weight_sum(network: &NeuralNetwork) -> f64 {
+    network
+        .parameters()
+        .iter()
+        .flatten()
+        .flat_map(|matrix| matrix.data.iter().flatten())
+        .map(|w| w * w)
+        .sum()
+}
+
+#[test]
+fn l1_penalty_and_gradient() {
+    let regularization = Regularization::l1(0.5);
+    let weights = Matrix::new(vec![vec![2.0, -1.0, 0.0]]);
+
+    assert_eq!(regularization.penalty(&weights), 1.5);
+    assert_eq!(
+        regularization.gradient(&weights).data,
+        vec![vec![0.5, -0.5, 0.0]]
+    );
+}
+
+#[test]
+fn elastic_net_combines_both_penalties() {
+    let regularization = Regularization::elastic_net(0.1, 0.2);
+    let weights = Matrix::new(vec![vec![-2.0]]);
+
+    assert!((regularization.penalty(&weights) - 0.6).abs() < 1e-12);
+    assert!((regularization.gradient(&weights).data[0][0] - (-0.5)).abs() < 1e-12);
+}
+
+#[test]
+fn loss_includes_penalty() {
+    let (input, targets) = xor_data();
+    let plain = xor_network(vec![]);
+    let regularized = xor_network(vec![]).with_regularization(Regularization::l2(0.1));
+
+    let penalty = 0.5 * 0.1 * squared_weight_sum(&plain);
+    let difference = regularized.loss(input.clone(), targets.clone()) - plain.loss(input, targets);
+
+    assert!((difference - penalty).abs() < 1e-12);
+}
+
+#[test]
+fn regularized_gradients_match_finite_differences() {
+    let (input, targets) = xor_data();
+
+    for regularization in [
+        Regularization::l1(0.01),
+        Regularization::l2(0.05),
+        Regularization::elastic_net(0.01, 0.05),
+    ] {
+        let network = xor_network(vec![]).with_regularization(regularization);
+        let report = check_gradients(&network, input.clone(), targets.clone(), 1e-5);
+
+        assert!(report.passed(1e-6), "{:?}: {:?}", regularization, report);
+    }
+}
+
+#[test]
+fn weight_decay_keeps_weights_smaller() {
+    let (input, targets) = xor_data();
+    let plain = xor_network(vec![]);
+    let decayed = xor_network(vec![]).with_regularization(Regularization::l2(0.01));
+
+    for _ in 0..2_000 {
+        plain.train(input.clone(), targets.clone());
+        decayed.train(input.clone(), targets.clone());
+    }
+
+    assert!(squared_weight_sum(&decayed) < squared_weight_sum(&plain));
+}
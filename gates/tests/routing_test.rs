@@ -0,0 +1,154 @@
+use aspirina_gates::computer::cpu::SimpleCPU;
+use aspirina_gates::computer::memory::Memory;
+use aspirina_gates::computer::registry::GateRegistry;
+use aspirina_gates::computer::routing::{Decoder, Demux, Mux16, Mux2, Mux4, PriorityEncoder};
+
+fn bits<const N: usize>(value: usize) -> [bool; N] {
+    std::array::from_fn(|bit| value & (1 << bit) != 0)
+}
+
+#[test]
+fn mux2_selects_every_row() {
+    let mux = Mux2::new();
+
+    for row in 0..8 {
+        let [a, b, select] = bits(row);
+        assert_eq!(mux.compute(a, b, select), if select { b } else { a });
+    }
+}
+
+#[test]
+fn mux4_selects_every_row() {
+    let mux = Mux4::new();
+
+    for inputs in 0..16 {
+        for select in 0..4 {
+            assert_eq!(
+                mux.compute(&bits(inputs), bits(select)),
+                inputs & (1 << select) != 0
+            );
+        }
+    }
+}
+
+#[test]
+fn mux16_selects_every_input() {
+    let mux = Mux16::new();
+
+    // Every select against each input alone high and alone low, then every input
+    // pattern against the lowest and highest select
+    for select in 0..16 {
+        for input in 0..16 {
+            let one_hot = 1 << input;
+            let one_cold = 0xFFFF ^ one_hot;
+            assert_eq!(mux.compute(&bits(one_hot), bits(select)), input == select);
+            assert_eq!(mux.compute(&bits(one_cold), bits(select)), input != select);
+        }
+    }
+    for inputs in (0..1 << 16).step_by(7) {
+        assert_eq!(mux.compute(&bits(inputs), bits(0)), inputs & 1 != 0);
+        assert_eq!(mux.compute(&bits(inputs), bits(15)), inputs & 0x8000 != 0);
+    }
+}
+
+#[test]
+fn decoder_raises_exactly_one_line() {
+    let decoder = Decoder::new();
+
+    for select in 0..16 {
+        let lines = decoder.compute(bits(select));
+        assert_eq!(lines, bits::<16>(1 << select), "select {}", select);
+    }
+}
+
+#[test]
+fn demux_routes_the_input_to_one_output() {
+    let demux = Demux::new();
+
+    for select in 0..16 {
+        assert_eq!(demux.compute(true, bits(select)), bits::<16>(1 << select));
+        assert_eq!(demux.compute(false, bits(select)), [false; 16]);
+    }
+}
+
+#[test]
+fn priority_encoder_finds_the_highest_line() {
+    let encoder = PriorityEncoder::new();
+    assert_eq!(encoder.compute(&[false; 16]), None);
+
+    for inputs in 1..1usize << 16 {
+        let highest = inputs.ilog2() as usize;
+        assert_eq!(
+            encoder.compute(&bits(inputs)),
+            Some(bits(highest)),
+            "inputs {:016b}",
+            inputs
+        );
+    }
+}
+
+#[test]
+fn routed_memory_reads_and_writes_every_cell() {
+    let registry = GateRegistry::new();
+    let mut memory = Memory::new().with_routing(&registry);
+    assert!(memory.is_routed());
+    assert!(!Memory::new().is_routed());
+
+    for value in 0..16 {
+        for address in 0..16 {
+            memory.write(address, (address + value) & 0x0F);
+        }
+        for address in 0..16 {
+            assert_eq!(memory.read(address), (address + value) & 0x0F);
+        }
+    }
+
+    memory.write(0x13, 0xFF); // address and value masked to 4 bits
+    assert_eq!(memory.read(0x3), 0xF);
+    assert_eq!(memory.dump()[3], 0xF);
+}
+
+#[test]
+fn routed_cpu_matches_the_plain_cpu() {
+    let registry = GateRegistry::new();
+    let programs: [(&[u8], u8); 2] = [
+        // LDI 3, DEC, JNZ 2, LDI 9, XOR E, STORE F, HALT; E = 5
+        (
+            &[
+                0xA, 0x3, 0xC, 0xE, 0x2, 0xA, 0x9, 0x7, 0xE, 0x2, 0xF, 0xF, 0, 0, 0x5,
+            ],
+            0x9 ^ 0x5,
+        ),
+        // NOP, LOAD D, ADD E, INC, SUB E, OR D, AND E, HALT; D = 3, E = 5
+        (
+            &[
+                0x0, 0x1, 0xD, 0x3, 0xE, 0xB, 0x4, 0xE, 0x6, 0xD, 0x5, 0xE, 0xF, 0x3, 0x5,
+            ],
+            ((3 + 5 + 1 - 5) | 3) & 5,
+        ),
+    ];
+
+    for (program, accumulator) in programs {
+        let mut results = Vec::new();
+        for mut cpu in [
+            SimpleCPU::with_registry(&registry),
+            SimpleCPU::with_registry(&registry).with_routing(&registry),
+        ] {
+            cpu.load_program(program);
+            cpu.run(20);
+
+            assert!(cpu.halted);
+            results.push((
+                cpu.registers.accumulator.read(),
+                cpu.memory.dump(),
+                cpu.cycle_count,
+            ));
+        }
+
+        assert_eq!(results[0].0, accumulator);
+        assert_eq!(results[0], results[1]);
+    }
+
+    assert!(SimpleCPU::new().with_routing(&registry).is_routed());
+    assert!(!SimpleCPU::with_registry(&registry).is_routed());
+}
@@ -0,0 +1,123 @@
+use aspirina_gates::computer::cpu::SimpleCPU;
+use aspirina_gates::computer::registers::{CPURegisters, Register, RegisterMode};
+use aspirina_gates::computer::registry::GateRegistry;
+use aspirina_gates::computer::sequential::{DFlipFlop, DLatch, NeuralRegister, SRLatch};
+
+#[test]
+fn sr_latch_sets_resets_and_holds() {
+    let mut latch = SRLatch::new();
+    assert!(!latch.q());
+    assert!(latch.q_bar());
+
+    assert!(latch.update(true, false));
+    assert!(latch.update(false, false)); // holds the set state
+    assert!(!latch.q_bar());
+
+    assert!(!latch.update(false, true));
+    assert!(!latch.update(false, false)); // holds the reset state
+    assert!(latch.q_bar());
+
+    // Both inputs high drive both outputs low
+    latch.update(true, true);
+    assert!(!latch.q() && !latch.q_bar());
+}
+
+#[test]
+fn d_latch_is_transparent_while_enabled() {
+    let mut latch = DLatch::new();
+
+    assert!(latch.update(true, true));
+    assert!(!latch.update(false, true));
+    assert!(latch.update(true, true));
+
+    // Disabled: D is ignored
+    assert!(latch.update(false, false));
+    assert!(latch.update(true, false));
+}
+
+#[test]
+fn flip_flop_changes_only_on_the_rising_edge() {
+    let registry = GateRegistry::new();
+    let mut flip_flop = DFlipFlop::with_registry(&registry);
+
+    // Clock low: D reaches the master but not Q
+    assert!(!flip_flop.clock(false, true));
+    assert!(!flip_flop.clock(false, true));
+
+    // Rising edge: the captured bit appears, later D changes are ignored
+    assert!(flip_flop.clock(true, true));
+    assert!(flip_flop.clock(true, false));
+    assert!(flip_flop.clock_level());
+
+    // Falling edge: Q still holds
+    assert!(flip_flop.clock(false, false));
+    assert!(!flip_flop.clock(true, true)); // the 0 captured while low
+
+    assert!(flip_flop.tick(true));
+    assert!(!flip_flop.tick(false));
+}
+
+#[test]
+fn neural_register_stores_every_value() {
+    let mut register = NeuralRegister::with_registry(&GateRegistry::new());
+    assert!(register.is_zero());
+
+    for value in 0..16 {
+        register.write(value);
+        assert_eq!(register.read(), value);
+    }
+    register.write(0xFF); // masked to 4 bits
+    assert_eq!(register.read(), 0xF);
+
+    register.increment();
+    assert_eq!(register.read(), 0);
+    register.decrement();
+    assert_eq!(register.read(), 0xF);
+    register.clear();
+    assert!(register.is_zero());
+    assert_eq!(register.ticks(), 20);
+}
+
+#[test]
+fn registers_delegate_to_either_mode() {
+    let registry = GateRegistry::new();
+
+    for mode in [RegisterMode::Plain, RegisterMode::Neural] {
+        let mut register = Register::with_mode(mode, &registry);
+        assert_eq!(register.mode(), mode);
+
+        register.write(0xE);
+        register.increment();
+        assert_eq!(register.read(), 0xF);
+        register.increment();
+        assert!(register.is_zero());
+        register.decrement();
+        assert_eq!(register.read(), 0xF);
+    }
+}
+
+#[test]
+fn cpu_runs_on_neural_registers() {
+    let registry = GateRegistry::new();
+    // LDI 5, ADD E, STORE F, HALT
+    let program = [0xA, 0x5, 0x3, 0xE, 0x2, 0xF, 0xF];
+
+    let mut results = Vec::new();
+    for registers in [CPURegisters::new(), CPURegisters::neural(&registry)] {
+        let mut cpu = SimpleCPU::with_registry(&registry).with_registers(registers);
+        cpu.load_program(&program);
+        cpu.memory.write(0xE, 3);
+        cpu.run(10);
+
+        assert!(cpu.halted);
+        results.push((
+            cpu.memory.read(0xF),
+            cpu.registers.program_counter.read(),
+            cpu.cycle_count,
+        ));
+    }
+
+    assert_eq!(results[0].0, 8);
+    assert_eq!(results[0], results[1]);
+    assert_eq!(CPURegisters::neural(&registry).mode(), RegisterMode::Neural);
+}
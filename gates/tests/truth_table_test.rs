@@ -0,0 +1,137 @@
+use aspirina_gates::computer::gates::{GateType, LogicGate};
+use aspirina_gates::computer::registry::GateRegistry;
+use aspirina_gates::computer::truth_table::TruthTable;
+
+fn outputs(gate: &LogicGate) -> Vec<bool> {
+    gate.truth_table()
+        .into_iter()
+        .map(|(inputs, _)| gate.compute(inputs) > 0.5)
+        .collect()
+}
+
+#[test]
+fn tables_parse_rows_in_any_order() {
+    let xor = TruthTable::parse("00:0 01:1 10:1 11:0").unwrap();
+    assert_eq!(xor.inputs(), 2);
+    assert_eq!(xor.outputs(), &[false, true, true, false]);
+    assert_eq!(xor, TruthTable::of(&GateType::XOR, 2));
+
+    let shuffled = TruthTable::parse("11:0, 10:1\n00:0, 01:1").unwrap();
+    assert_eq!(shuffled, xor);
+    assert_eq!(TruthTable::parse(&xor.to_string()).unwrap(), xor);
+    assert!(xor.evaluate(&[true, false]));
+}
+
+#[test]
+fn malformed_tables_are_rejected() {
+    assert_eq!(TruthTable::parse("").unwrap_err(), "Empty truth table");
+    assert_eq!(
+        TruthTable::parse("00:0 01:1 10:1").unwrap_err(),
+        "Missing row 11"
+    );
+    assert_eq!(
+        TruthTable::parse("00:0 01:1 01:0 11:1").unwrap_err(),
+        "Duplicate row 01"
+    );
+    assert_eq!(
+        TruthTable::parse("00:0 011:1").unwrap_err(),
+        "Entry \"011:1\" has 3 inputs, expected 2"
+    );
+    assert_eq!(
+        TruthTable::parse("0:0 1:2").unwrap_err(),
+        "Invalid entry \"1:2\""
+    );
+}
+
+#[test]
+fn expressions_take_variables_in_alphabetical_order() {
+    assert_eq!(
+        TruthTable::from_expression("(a | b) & !(a & b)").unwrap(),
+        TruthTable::of(&GateType::XOR, 2)
+    );
+    assert_eq!(
+        TruthTable::from_expression("a AND b OR c AND (a XOR b)").unwrap(),
+        TruthTable::of(&GateType::MAJORITY, 3)
+    );
+
+    // b & !a: high only for a = 0, b = 1
+    let table = TruthTable::from_expression("b * ~a").unwrap();
+    assert_eq!(table.to_string(), "00:0 01:1 10:0 11:0");
+}
+
+#[test]
+fn expressions_follow_operator_precedence() {
+    // NOT binds tightest, then AND, XOR and OR
+    assert_eq!(
+        TruthTable::from_expression("!a & b | c").unwrap(),
+        TruthTable::from_expression("((!a) & b) | c").unwrap()
+    );
+    assert_eq!(
+        TruthTable::from_expression("a ^ b & c").unwrap(),
+        TruthTable::from_expression("a ^ (b & c)").unwrap()
+    );
+    assert_eq!(
+        TruthTable::from_expression("a | 1").unwrap().output_bits(),
+        "11"
+    );
+}
+
+#[test]
+fn malformed_expressions_are_rejected() {
+    assert_eq!(
+        TruthTable::from_expression("a % b").unwrap_err(),
+        "Unexpected character '%' at column 3"
+    );
+    assert_eq!(
+        TruthTable::from_expression("(a & b").unwrap_err(),
+        "Expected ')'"
+    );
+    assert_eq!(
+        TruthTable::from_expression("a &").unwrap_err(),
+        "Unexpected end of expression"
+    );
+    assert_eq!(
+        TruthTable::from_expression("1 | 0").unwrap_err(),
+        "Expression has no variables"
+    );
+    assert_eq!(
+        TruthTable::from_expression("a b c d e f g h i").unwrap_err(),
+        "Expression has 9 variables, at most 8 are supported"
+    );
+}
+
+#[test]
+fn gates_are_synthesized_from_truth_tables() {
+    let implication = TruthTable::parse("00:1 01:1 10:0 11:1").unwrap();
+    let gate = LogicGate::from_truth_table(implication.clone()).unwrap();
+
+    assert_eq!(gate.gate_type(), &GateType::CUSTOM(implication.clone()));
+    assert!(gate.is_exact());
+    assert_eq!(outputs(&gate), implication.outputs());
+}
+
+#[test]
+fn wider_custom_cells_train_exactly() {
+    // A 2:1 multiplexer: b when s is low, c when s is high
+    let mux = TruthTable::from_expression("!s & b | s & c").unwrap();
+    let gate = LogicGate::from_truth_table(mux.clone()).unwrap();
+    assert_eq!(outputs(&gate), mux.outputs());
+
+    let constant = TruthTable::from_expression("a & !a").unwrap();
+    let gate = LogicGate::from_truth_table(constant).unwrap();
+    assert_eq!(outputs(&gate), vec![false, false]);
+}
+
+#[test]
+fn custom_gates_round_trip_through_the_registry() {
+    let table = TruthTable::from_expression("a & !b").unwrap();
+    let gate_type = GateType::CUSTOM(table);
+    assert_eq!(gate_type.to_string(), "CUSTOM(0010)");
+    assert_eq!(GateType::parse("CUSTOM(0010)").unwrap(), gate_type);
+    assert!(GateType::parse("CUSTOM(001)").is_err());
+
+    let registry = GateRegistry::new();
+    let trained = outputs(&registry.get(gate_type.clone(), 2));
+    let restored = GateRegistry::parse(&registry.to_text()).unwrap();
+    assert_eq!(outputs(&restored.get(gate_type, 2)), trained);
+}
@@ -0,0 +1,284 @@
+//! Self-Attention
+//!
+//! A recurrent layer squeezes everything it has read into one hidden state. Attention instead
+//! lets every position look directly at every other position of the sequence and decide how
+//! much of each to take.
+//!
+//! # Scaled Dot-Product Attention
+//!
+//! Every position has a query `q`, a key `k` and a value `v`. The output at position `i` is a
+//! weighted average of the values, where the weights are the softmax of how well `qᵢ`
+//! matches each key:
+//!
+//! ```text
+//! Attention(Q, K, V) = softmax(Q·Kᵀ / √d) · V
+//! ```
+//!
+//! Dividing by `√d` (the key size) keeps the dot products from growing with the dimension and
+//! saturating the softmax. With a **causal mask** position `i` may only attend to positions
+//! `j ≤ i`, so a language model cannot peek at the token it has to predict.
+//!
+//! # Multi-Head Attention
+//!
+//! [`MultiHeadAttention`] projects its input into queries, keys and values, splits them into
+//! `heads` slices of `model_size / heads` columns, runs causal attention on each slice
+//! separately and projects the concatenated results back. Different heads can learn to look
+//! for different things, e.g. the previous character or the start of the word.
+//!
+//! Sequences are `[length, model_size]` matrices, one row per position.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_language::attention::{scaled_dot_product_attention, MultiHeadAttention};
+//! # use aspirina_core::matrix::Matrix;
+//! let x = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]]);
+//!
+//! let (_, weights) = scaled_dot_product_attention(&x, &x, &x, true);
+//! assert_eq!(weights.data[0], vec![1.0, 0.0, 0.0]); // the first position only sees itself
+//!
+//! let attention = MultiHeadAttention::new(2, 2, 7);
+//! assert_eq!(attention.forward(&x).data.len(), 3);
+//! ```
+
+use crate::ops::{columns, concat_columns, softmax, Linear};
+use aspirina_core::matrix::Matrix;
+use aspirina_core::random::Rng;
+
+/// Computes `softmax(Q·Kᵀ / √d)·V` and returns the output and the attention weights.
+///
+/// `query` and `key` are `[length, d]`, `value` is `[length, d_v]`. Row `i` of the weights
+/// holds how much position `i` attends to each position; with `causal` the weights above the
+/// diagonal are zero.
+///
+/// # Panics
+///
+/// Panics if the shapes do not match.
+pub fn scaled_dot_product_attention(
+    query: &Matrix,
+    key: &Matrix,
+    value: &Matrix,
+    causal: bool,
+) -> (Matrix, Matrix) {
+    if query.data[0].len() != key.data[0].len() || key.data.len() != value.data.len() {
+        panic!("Incompatible dimensions");
+    }
+
+    let scale = 1.0 / (query.data[0].len() as f64).sqrt();
+    let scores = query.clone() * key.transpose();
+    let weights = Matrix::new(
+        scores
+            .data
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let masked: Vec<f64> = row
+                    .iter()
+                    .enumerate()
+                    .map(|(j, score)| {
+                        if causal && j > i {
+                            f64::NEG_INFINITY
+                        } else {
+                            score * scale
+                        }
+                    })
+                    .collect();
+                softmax(&masked)
+            })
+            .collect(),
+    );
+
+    (weights.clone() * value.clone(), weights)
+}
+
+/// Gradients of the query, key and value given the forward pass's `weights`.
+fn attention_backward(
+    query: &Matrix,
+    key: &Matrix,
+    value: &Matrix,
+    weights: &Matrix,
+    output_gradient: &Matrix,
+) -> (Matrix, Matrix, Matrix) {
+    let scale = 1.0 / (query.data[0].len() as f64).sqrt();
+    let weights_gradient = output_gradient.clone() * value.transpose();
+    let value_gradient = weights.transpose() * output_gradient.clone();
+
+    // softmax backward, row by row: dS = A ⊙ (dA - Σ dA⊙A)
+    let scores_gradient = Matrix::new(
+        weights
+            .data
+            .iter()
+            .zip(weights_gradient.data.iter())
+            .map(|(a, da)| {
+                let dot: f64 = a.iter().zip(da.iter()).map(|(a, da)| a * da).sum();
+                a.iter()
+                    .zip(da.iter())
+                    .map(|(a, da)| a * (da - dot) * scale)
+                    .collect()
+            })
+            .collect(),
+    );
+
+    let query_gradient = scores_gradient.clone() * key.clone();
+    let key_gradient = scores_gradient.transpose() * query.clone();
+
+    (query_gradient, key_gradient, value_gradient)
+}
+
+/// Causal multi-head self-attention with an output projection.
+#[derive(Debug, Clone)]
+pub struct MultiHeadAttention {
+    heads: usize,
+    query: Linear,
+    key: Linear,
+    value: Linear,
+    output: Linear,
+}
+
+/// Values saved by [`MultiHeadAttention::forward_cached`] for the backward pass.
+#[derive(Debug, Clone)]
+pub(crate) struct AttentionCache {
+    input: Matrix,
+    queries: Matrix,
+    keys: Matrix,
+    values: Matrix,
+    weights: Vec<Matrix>,
+    concatenated: Matrix,
+}
+
+impl MultiHeadAttention {
+    /// Creates attention over `model_size` features split into `heads` heads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `model_size` is not divisible by `heads`.
+    pub fn new(model_size: usize, heads: usize, seed: u64) -> Self {
+        if heads == 0 || !model_size.is_multiple_of(heads) {
+            panic!("Model size must be divisible by the number of heads");
+        }
+
+        let mut rng = Rng::new(seed);
+        Self {
+            heads,
+            query: Linear::new(model_size, model_size, &mut rng),
+            key: Linear::new(model_size, model_size, &mut rng),
+            value: Linear::new(model_size, model_size, &mut rng),
+            output: Linear::new(model_size, model_size, &mut rng),
+        }
+    }
+
+    /// Number of attention heads.
+    pub fn heads(&self) -> usize {
+        self.heads
+    }
+
+    /// Attends over the `[length, model_size]` sequence `input` with a causal mask.
+    pub fn forward(&self, input: &Matrix) -> Matrix {
+        self.forward_cached(input).0
+    }
+
+    /// The attention weights of every head for `input`, each `[length, length]`.
+    pub fn attention_weights(&self, input: &Matrix) -> Vec<Matrix> {
+        self.forward_cached(input).1.weights
+    }
+
+    pub(crate) fn forward_cached(&self, input: &Matrix) -> (Matrix, AttentionCache) {
+        let queries = self.query.forward(input);
+        let keys = self.key.forward(input);
+        let values = self.value.forward(input);
+
+        let mut outputs = Vec::with_capacity(self.heads);
+        let mut weights = Vec::with_capacity(self.heads);
+        for (start, end) in self.head_ranges() {
+            let (output, head_weights) = scaled_dot_product_attention(
+                &columns(&queries, start, end),
+                &columns(&keys, start, end),
+                &columns(&values, start, end),
+                true,
+            );
+            outputs.push(output);
+            weights.push(head_weights);
+        }
+
+        let concatenated = concat_columns(&outputs);
+        let output = self.output.forward(&concatenated);
+
+        (
+            output,
+            AttentionCache {
+                input: input.clone(),
+                queries,
+                keys,
+                values,
+                weights,
+                concatenated,
+            },
+        )
+    }
+
+    /// Returns the input gradient and the parameter gradients.
+    pub(crate) fn backward(
+        &self,
+        cache: &AttentionCache,
+        output_gradient: &Matrix,
+    ) -> (Matrix, Vec<Matrix>) {
+        let (concatenated_gradient, output_parameters) =
+            self.output.backward(&cache.concatenated, output_gradient);
+
+        let mut query_gradients = Vec::with_capacity(self.heads);
+        let mut key_gradients = Vec::with_capacity(self.heads);
+        let mut value_gradients = Vec::with_capacity(self.heads);
+        for ((start, end), weights) in self.head_ranges().into_iter().zip(cache.weights.iter()) {
+            let (query, key, value) = attention_backward(
+                &columns(&cache.queries, start, end),
+                &columns(&cache.keys, start, end),
+                &columns(&cache.values, start, end),
+                weights,
+                &columns(&concatenated_gradient, start, end),
+            );
+            query_gradients.push(query);
+            key_gradients.push(key);
+            value_gradients.push(value);
+        }
+
+        let (query_input, query_parameters) = self
+            .query
+            .backward(&cache.input, &concat_columns(&query_gradients));
+        let (key_input, key_parameters) = self
+            .key
+            .backward(&cache.input, &concat_columns(&key_gradients));
+        let (value_input, value_parameters) = self
+            .value
+            .backward(&cache.input, &concat_columns(&value_gradients));
+
+        let mut parameters = query_parameters;
+        parameters.extend(key_parameters);
+        parameters.extend(value_parameters);
+        parameters.extend(output_parameters);
+
+        (query_input + key_input + value_input, parameters)
+    }
+
+    /// Copies of the query, key, value and output projections, each as `[W, b]`.
+    pub fn parameters(&self) -> Vec<Matrix> {
+        let mut parameters = self.query.parameters();
+        parameters.extend(self.key.parameters());
+        parameters.extend(self.value.parameters());
+        parameters.extend(self.output.parameters());
+        parameters
+    }
+
+    pub(crate) fn set_parameters(&mut self, parameters: &mut impl Iterator<Item = Matrix>) {
+        self.query.set_parameters(parameters);
+        self.key.set_parameters(parameters);
+        self.value.set_parameters(parameters);
+        self.output.set_parameters(parameters);
+    }
+
+    fn head_ranges(&self) -> Vec<(usize, usize)> {
+        let head_size = self.query.weights.data.len() / self.heads;
+        (0..self.heads)
+            .map(|head| (head * head_size, (head + 1) * head_size))
+            .collect()
+    }
+}
@@ -0,0 +1,369 @@
+//! Character-Level Language Model
+//!
+//! A language model predicts the next token from the ones before it. This one works on single
+//! characters: every character is looked up in an [`Embedding`] table, the embeddings are fed
+//! through a recurrent cell, and a linear readout turns each hidden state into one score
+//! (logit) per character of the vocabulary. A softmax turns the scores into probabilities.
+//!
+//! # Training
+//!
+//! The model is trained to minimize the cross-entropy `-ln p(next character)`. The corpus is
+//! cut into `batch_size` parallel streams that are read side by side, `window` characters at a
+//! time, using truncated backpropagation through time (see [`crate::recurrent`]). Gradients
+//! are clipped to a norm of 5 before each update, since recurrent networks occasionally
+//! produce very large ones.
+//!
+//! # Sampling
+//!
+//! Text is generated one character at a time by feeding each sampled character back in.
+//! [`Sampling`] controls how a character is picked from the predicted distribution:
+//!
+//! - **Greedy**: always the most likely character
+//! - **Temperature**: divides the logits by `t` first; `t < 1` sharpens, `t > 1` flattens
+//! - **Top-k**: only the `k` most likely characters can be picked
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_language::char_model::{CharLanguageModel, Sampling, CORPUS};
+//! # use aspirina_language::recurrent::GruCell;
+//! # use aspirina_language::tokenizer::CharTokenizer;
+//! let tokenizer = CharTokenizer::from_corpus(CORPUS);
+//! let cell = GruCell::new(8, 16, 1);
+//! let mut model = CharLanguageModel::new(tokenizer, cell, 2);
+//!
+//! let loss = model.train_epoch(CORPUS, 4, 8, 0.5).unwrap();
+//! assert!(loss.is_finite());
+//!
+//! let text = model
+//!     .generate("the ", 10, &Sampling::temperature(0.8).with_top_k(5), 3)
+//!     .unwrap();
+//! assert_eq!(text.chars().count(), 14);
+//! ```
+
+use crate::embedding::Embedding;
+use crate::ops::{add_row, clip_factor, column_sums, softmax, squared_norm, uniform, zeros};
+use crate::recurrent::{Recurrent, RecurrentCell};
+use crate::tokenizer::{CharTokenizer, Tokenizer, Vocabulary};
+use aspirina_core::matrix::Matrix;
+use aspirina_core::random::Rng;
+
+/// A small bundled corpus that a model can learn in a few seconds.
+pub const CORPUS: &str = include_str!("../data/corpus.txt");
+
+const MAX_GRADIENT_NORM: f64 = 5.0;
+
+/// How the next token is chosen from the predicted logits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sampling {
+    temperature: f64,
+    top_k: Option<usize>,
+}
+
+impl Sampling {
+    /// Always picks the most likely token.
+    pub fn greedy() -> Self {
+        Self {
+            temperature: 0.0,
+            top_k: None,
+        }
+    }
+
+    /// Samples from the softmax of `logits / temperature`. A temperature of 0 is greedy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `temperature` is negative.
+    pub fn temperature(temperature: f64) -> Self {
+        if temperature < 0.0 {
+            panic!("Temperature must not be negative");
+        }
+
+        Self {
+            temperature,
+            top_k: None,
+        }
+    }
+
+    /// Restricts sampling to the `k` most likely tokens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero.
+    pub fn with_top_k(mut self, k: usize) -> Self {
+        if k == 0 {
+            panic!("Top-k must be greater than zero");
+        }
+
+        self.top_k = Some(k);
+        self
+    }
+
+    /// Picks a token id from `logits`.
+    pub fn sample(&self, logits: &[f64], rng: &mut Rng) -> usize {
+        let mut candidates: Vec<usize> = (0..logits.len()).collect();
+        candidates.sort_by(|a, b| logits[*b].total_cmp(&logits[*a]));
+
+        if self.temperature == 0.0 {
+            return candidates[0];
+        }
+
+        if let Some(k) = self.top_k {
+            candidates.truncate(k);
+        }
+
+        let scaled: Vec<f64> = candidates
+            .iter()
+            .map(|id| logits[*id] / self.temperature)
+            .collect();
+        let probabilities = softmax(&scaled);
+
+        let mut threshold = rng.next_f64();
+        for (id, probability) in candidates.iter().zip(probabilities.iter()) {
+            if threshold < *probability {
+                return *id;
+            }
+            threshold -= probability;
+        }
+
+        *candidates.last().unwrap()
+    }
+}
+
+/// Embedding, recurrent cell and linear readout over a character vocabulary.
+#[derive(Debug, Clone)]
+pub struct CharLanguageModel<C: RecurrentCell> {
+    tokenizer: CharTokenizer,
+    /// One vector of `cell.input_size()` values per character
+    pub embedding: Embedding,
+    /// The recurrent backbone
+    pub recurrent: Recurrent<C>,
+    /// Readout weights, shape `vocab_size × hidden_size`
+    pub readout: Matrix,
+    /// Readout bias, shape `1 × vocab_size`
+    pub readout_bias: Matrix,
+}
+
+impl<C: RecurrentCell> CharLanguageModel<C> {
+    /// Creates a model over `tokenizer`'s vocabulary.
+    ///
+    /// The embedding size is the cell's input size. The embedding and readout are initialized
+    /// from `seed`.
+    pub fn new(tokenizer: CharTokenizer, cell: C, seed: u64) -> Self {
+        let vocab_size = tokenizer.vocab_size();
+        let mut rng = Rng::new(seed);
+        let embedding =
+            Embedding::from_weights(uniform(vocab_size, cell.input_size(), 1.0, &mut rng));
+        let limit = 1.0 / (cell.hidden_size() as f64).sqrt();
+        let readout = uniform(vocab_size, cell.hidden_size(), limit, &mut rng);
+
+        Self {
+            tokenizer,
+            embedding,
+            recurrent: Recurrent::new(cell),
+            readout,
+            readout_bias: zeros(1, vocab_size),
+        }
+    }
+
+    /// The tokenizer that defines the vocabulary.
+    pub fn tokenizer(&self) -> &CharTokenizer {
+        &self.tokenizer
+    }
+
+    /// Makes one pass over `text` and returns the mean cross-entropy per character (in nats),
+    /// measured before each window's update.
+    ///
+    /// Returns an error if `batch_size` or `window` is zero, if `text` contains characters
+    /// outside the vocabulary, or if it is too short for the batch size.
+    pub fn train_epoch(
+        &mut self,
+        text: &str,
+        batch_size: usize,
+        window: usize,
+        learning_rate: f64,
+    ) -> Result<f64, String> {
+        if batch_size == 0 || window == 0 {
+            return Err("Batch size and window must be greater than zero".to_string());
+        }
+
+        let tokens = self.tokenizer.try_encode(text)?;
+        let stream_length = tokens.len().saturating_sub(1) / batch_size;
+        if stream_length == 0 {
+            return Err("Text is too short for the batch size".to_string());
+        }
+
+        self.recurrent.reset_state();
+        let mut total_loss = 0.0;
+
+        let mut start = 0;
+        while start < stream_length {
+            let end = (start + window).min(stream_length);
+            let mut inputs = Vec::with_capacity(end - start);
+            let mut targets = Vec::with_capacity(end - start);
+
+            for t in start..end {
+                let positions: Vec<usize> =
+                    (0..batch_size).map(|b| b * stream_length + t).collect();
+                inputs.push(positions.iter().map(|p| tokens[*p]).collect::<Vec<_>>());
+                targets.push(positions.iter().map(|p| tokens[p + 1]).collect::<Vec<_>>());
+            }
+
+            total_loss += self.train_window(&inputs, &targets, learning_rate);
+            start = end;
+        }
+
+        Ok(total_loss / (stream_length * batch_size) as f64)
+    }
+
+    /// Mean cross-entropy per character of predicting each character of `text` from the ones
+    /// before it, starting from a fresh state.
+    pub fn loss(&mut self, text: &str) -> Result<f64, String> {
+        let tokens = self.tokenizer.try_encode(text)?;
+        if tokens.len() < 2 {
+            return Err("Text must contain at least two characters".to_string());
+        }
+
+        self.recurrent.reset_state();
+        let mut total = 0.0;
+
+        for pair in tokens.windows(2) {
+            let logits = self.step(pair[0]);
+            total -= softmax(&logits)[pair[1]].ln();
+        }
+
+        Ok(total / (tokens.len() - 1) as f64)
+    }
+
+    /// Perplexity of `text`: the exponential of [`CharLanguageModel::loss`]. A model that
+    /// guesses uniformly among `n` characters has perplexity `n`.
+    pub fn perplexity(&mut self, text: &str) -> Result<f64, String> {
+        Ok(self.loss(text)?.exp())
+    }
+
+    /// Continues `prompt` with `length` sampled characters and returns the prompt followed by
+    /// the generated text. Special tokens are never sampled.
+    pub fn generate(
+        &mut self,
+        prompt: &str,
+        length: usize,
+        sampling: &Sampling,
+        seed: u64,
+    ) -> Result<String, String> {
+        let prompt_tokens = self.tokenizer.try_encode(prompt)?;
+        let Some((&last, context)) = prompt_tokens.split_last() else {
+            return Err("Prompt must not be empty".to_string());
+        };
+
+        self.recurrent.reset_state();
+        for token in context {
+            self.step(*token);
+        }
+
+        let mut rng = Rng::new(seed);
+        let mut generated = prompt_tokens.clone();
+        let mut token = last;
+
+        for _ in 0..length {
+            let mut logits = self.step(token);
+            for logit in &mut logits[..Vocabulary::SPECIAL_TOKENS.len()] {
+                *logit = f64::NEG_INFINITY;
+            }
+            token = sampling.sample(&logits, &mut rng);
+            generated.push(token);
+        }
+
+        Ok(self.tokenizer.decode(&generated))
+    }
+
+    /// Feeds one token with batch size 1, continuing the carried state, and returns the logits
+    /// for the next token.
+    fn step(&mut self, token: usize) -> Vec<f64> {
+        let input = self.embedding.forward(&[token]);
+        let hidden = self.recurrent.step(&input);
+        self.read_out(&hidden).data.remove(0)
+    }
+
+    fn read_out(&self, hidden: &Matrix) -> Matrix {
+        add_row(
+            &(hidden.clone() * self.readout.transpose()),
+            &self.readout_bias,
+        )
+    }
+
+    /// Trains on one window of `[timestep][stream]` token ids and returns the summed loss.
+    fn train_window(
+        &mut self,
+        inputs: &[Vec<usize>],
+        targets: &[Vec<usize>],
+        learning_rate: f64,
+    ) -> f64 {
+        let embedded: Vec<Matrix> = inputs
+            .iter()
+            .map(|ids| self.embedding.forward(ids))
+            .collect();
+        let hidden = self.recurrent.forward(&embedded);
+        let count = (inputs.len() * inputs[0].len()) as f64;
+
+        let mut loss = 0.0;
+        let mut readout = zeros(self.readout.data.len(), self.readout.data[0].len());
+        let mut readout_bias = zeros(1, self.readout_bias.data[0].len());
+        let mut hidden_gradients = Vec::with_capacity(hidden.len());
+
+        for (hidden, targets) in hidden.iter().zip(targets.iter()) {
+            let logits = self.read_out(hidden);
+            let mut logits_gradient = Vec::with_capacity(targets.len());
+
+            for (row, target) in logits.data.iter().zip(targets.iter()) {
+                let mut probabilities = softmax(row);
+                loss -= probabilities[*target].ln();
+                probabilities[*target] -= 1.0;
+                logits_gradient.push(probabilities.iter().map(|p| p / count).collect());
+            }
+
+            let logits_gradient = Matrix::new(logits_gradient);
+            readout = readout + logits_gradient.transpose() * hidden.clone();
+            readout_bias = readout_bias + column_sums(&logits_gradient);
+            hidden_gradients.push(logits_gradient * self.readout.clone());
+        }
+
+        let gradients = self.recurrent.backward(&hidden_gradients);
+
+        let ids: Vec<usize> = inputs.concat();
+        let input_gradients = Matrix::new(
+            gradients
+                .inputs
+                .into_iter()
+                .flat_map(|gradient| gradient.data)
+                .collect(),
+        );
+        let embedding = self.embedding.gradient(&ids, &input_gradients);
+
+        let mut dense = gradients.parameters;
+        dense.push(readout);
+        dense.push(readout_bias);
+        let factor = clip_factor(
+            squared_norm(&dense) + embedding.squared_norm(),
+            MAX_GRADIENT_NORM,
+        );
+        let step = learning_rate * factor;
+
+        let readout_bias = dense.pop().unwrap();
+        let readout = dense.pop().unwrap();
+
+        let cell_parameters = self
+            .recurrent
+            .cell
+            .parameters()
+            .into_iter()
+            .zip(dense)
+            .map(|(parameter, gradient)| parameter - gradient.scale(step))
+            .collect();
+        self.recurrent.cell.set_parameters(cell_parameters);
+        self.readout = self.readout.clone() - readout.scale(step);
+        self.readout_bias = self.readout_bias.clone() - readout_bias.scale(step);
+        self.embedding.apply(&embedding, step);
+
+        loss
+    }
+}
@@ -0,0 +1,191 @@
+//! Embedding Layer
+//!
+//! An embedding is a lookup table with one learned vector per token. Looking up a sequence
+//! of token ids gives one row per token, which is what recurrent and attention models take
+//! as input.
+//!
+//! A batch only touches a few rows of the table, so the gradient is sparse: a
+//! [`SparseGradient`] holds one row per distinct token id seen, and an update only changes
+//! those rows. Gradients for a token that appears several times are summed.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_language::embedding::Embedding;
+//! # use aspirina_core::matrix::Matrix;
+//! let mut embedding = Embedding::new(10, 4, 1);
+//! let before = embedding.weights.data[7].clone();
+//!
+//! let vectors = embedding.forward(&[2, 5, 2]);
+//! assert_eq!(vectors.data.len(), 3);
+//!
+//! let output_gradient = Matrix::new(vec![vec![0.1; 4]; 3]);
+//! let gradient = embedding.gradient(&[2, 5, 2], &output_gradient);
+//! assert_eq!(gradient.len(), 2); // ids 2 and 5
+//!
+//! embedding.apply(&gradient, 0.5);
+//! assert_eq!(embedding.weights.data[7], before);
+//! ```
+
+use crate::ops::uniform;
+use aspirina_core::matrix::Matrix;
+use aspirina_core::random::Rng;
+use std::collections::BTreeMap;
+use std::ops::Add;
+
+/// A `vocab_size × dimension` lookup table of token vectors.
+#[derive(Debug, Clone)]
+pub struct Embedding {
+    /// One row per token id
+    pub weights: Matrix,
+}
+
+/// Gradient rows for the token ids touched by a batch.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SparseGradient {
+    rows: BTreeMap<usize, Vec<f64>>,
+}
+
+impl Embedding {
+    /// Creates a table with weights drawn uniformly from `±1`.
+    pub fn new(vocab_size: usize, dimension: usize, seed: u64) -> Self {
+        Self::from_weights(uniform(vocab_size, dimension, 1.0, &mut Rng::new(seed)))
+    }
+
+    /// Wraps an existing `vocab_size × dimension` table.
+    pub fn from_weights(weights: Matrix) -> Self {
+        Self { weights }
+    }
+
+    /// Number of rows in the table.
+    pub fn vocab_size(&self) -> usize {
+        self.weights.data.len()
+    }
+
+    /// Length of each token vector.
+    pub fn dimension(&self) -> usize {
+        self.weights.data.first().map_or(0, Vec::len)
+    }
+
+    /// Looks up `ids`, one row per id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an id is outside the table.
+    pub fn forward(&self, ids: &[usize]) -> Matrix {
+        Matrix::new(ids.iter().map(|id| self.row(*id).clone()).collect())
+    }
+
+    /// Sums the rows of `output_gradient` per token id.
+    ///
+    /// `output_gradient` is the gradient of the loss with respect to [`Embedding::forward`]'s
+    /// output, one row per id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output_gradient` does not have one row of `dimension` values per id.
+    pub fn gradient(&self, ids: &[usize], output_gradient: &Matrix) -> SparseGradient {
+        if output_gradient.data.len() != ids.len()
+            || output_gradient
+                .data
+                .iter()
+                .any(|row| row.len() != self.dimension())
+        {
+            panic!("Incompatible dimensions");
+        }
+
+        let mut gradient = SparseGradient::default();
+        for (id, row) in ids.iter().zip(output_gradient.data.iter()) {
+            self.row(*id);
+            let sum = gradient
+                .rows
+                .entry(*id)
+                .or_insert_with(|| vec![0.0; row.len()]);
+            for (s, g) in sum.iter_mut().zip(row.iter()) {
+                *s += g;
+            }
+        }
+
+        gradient
+    }
+
+    /// Takes a gradient descent step on the rows in `gradient` only.
+    pub fn apply(&mut self, gradient: &SparseGradient, learning_rate: f64) {
+        for (id, row) in &gradient.rows {
+            for (w, g) in self.weights.data[*id].iter_mut().zip(row.iter()) {
+                *w -= learning_rate * g;
+            }
+        }
+    }
+
+    /// Computes the gradient for `ids` and applies it in one go.
+    pub fn update(&mut self, ids: &[usize], output_gradient: &Matrix, learning_rate: f64) {
+        let gradient = self.gradient(ids, output_gradient);
+        self.apply(&gradient, learning_rate);
+    }
+
+    fn row(&self, id: usize) -> &Vec<f64> {
+        self.weights
+            .data
+            .get(id)
+            .expect("Token id outside the vocabulary")
+    }
+}
+
+impl SparseGradient {
+    /// Number of distinct token ids with a gradient.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether no token id has a gradient.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// The gradient row for `id`, if the batch touched it.
+    pub fn row(&self, id: usize) -> Option<&[f64]> {
+        self.rows.get(&id).map(Vec::as_slice)
+    }
+
+    /// The gradient rows in ascending id order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &[f64])> {
+        self.rows.iter().map(|(id, row)| (*id, row.as_slice()))
+    }
+
+    /// Sum of the squared gradient values.
+    pub fn squared_norm(&self) -> f64 {
+        self.rows.values().flatten().map(|g| g * g).sum()
+    }
+
+    /// Multiplies every gradient value by `factor`.
+    pub fn scale(mut self, factor: f64) -> Self {
+        for g in self.rows.values_mut().flatten() {
+            *g *= factor;
+        }
+
+        self
+    }
+}
+
+impl Add for SparseGradient {
+    type Output = Self;
+
+    /// Sums two gradients row by row.
+    fn add(mut self, other: Self) -> Self {
+        for (id, row) in other.rows {
+            match self.rows.get_mut(&id) {
+                Some(sum) => {
+                    for (s, g) in sum.iter_mut().zip(row.iter()) {
+                        *s += g;
+                    }
+                }
+                None => {
+                    self.rows.insert(id, row);
+                }
+            }
+        }
+
+        self
+    }
+}
@@ -1 +1,10 @@
 pub use aspirina_core;
+
+pub mod attention;
+pub mod char_model;
+pub mod embedding;
+pub mod ngram;
+mod ops;
+pub mod recurrent;
+pub mod tokenizer;
+pub mod transformer;
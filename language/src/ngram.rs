@@ -0,0 +1,231 @@
+//! N-Gram Language Model
+//!
+//! A count-based baseline: the probability of a token depends only on the `n - 1` tokens
+//! before it, estimated from how often each n-gram occurs in the training text. Every
+//! sequence is padded at the start with `n - 1` `<bos>` tokens so the first tokens have a
+//! context as well.
+//!
+//! Raw counts give zero probability to anything unseen, so the counts are smoothed:
+//!
+//! - **Add-k**: `P(w | h) = (c(h w) + k) / (c(h) + k·V)`, as if every token of the
+//!   vocabulary had been seen `k` extra times after every context
+//! - **Kneser-Ney** (interpolated): subtracts a fixed discount `D` from every seen count and
+//!   gives the freed probability mass to a lower-order model. The lower orders count in how
+//!   many distinct contexts a token appears rather than how often, so a token that only ever
+//!   follows one word ("Francisco") is not predicted everywhere else. The lowest order falls
+//!   back to the uniform distribution `1 / V`.
+//!
+//! # Perplexity
+//!
+//! Models are compared by the perplexity of held-out text, `exp(-1/N Σ ln P(wᵢ | hᵢ))`.
+//! Lower is better; guessing uniformly among `V` tokens gives `V`.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_language::ngram::{NGramModel, Smoothing};
+//! # use aspirina_language::tokenizer::{CharTokenizer, Tokenizer};
+//! let tokenizer = CharTokenizer::from_corpus("abcabcabd");
+//! let mut model = NGramModel::new(3, Smoothing::KneserNey(0.75), tokenizer.vocab_size());
+//! model.train(&tokenizer.encode("abcabcabd"));
+//!
+//! let ids = tokenizer.encode("abc");
+//! let likely = model.probability(&ids[..2], ids[2]);
+//! assert!(likely > 0.5);
+//! assert!(model.perplexity(&tokenizer.encode("abcab")) < tokenizer.vocab_size() as f64);
+//! ```
+
+use crate::tokenizer::Vocabulary;
+use std::collections::HashMap;
+
+/// How unseen n-grams get probability mass.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Smoothing {
+    /// Adds `k` to every count
+    AddK(f64),
+    /// Interpolated Kneser-Ney with the given absolute discount
+    KneserNey(f64),
+}
+
+/// Counts of the tokens that follow each context.
+type Counts = HashMap<Vec<usize>, HashMap<usize, f64>>;
+
+/// Count-based model of order `n`.
+#[derive(Debug, Clone)]
+pub struct NGramModel {
+    order: usize,
+    smoothing: Smoothing,
+    vocab_size: usize,
+    /// Raw counts, indexed by n-gram length minus one
+    counts: Vec<Counts>,
+    /// Kneser-Ney continuation counts for the lower orders, indexed like `counts`
+    continuations: Vec<Counts>,
+}
+
+impl NGramModel {
+    /// Creates an empty model over `vocab_size` token ids.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `order` is zero, the add-k constant is not positive, or the Kneser-Ney
+    /// discount is not between 0 and 1.
+    pub fn new(order: usize, smoothing: Smoothing, vocab_size: usize) -> Self {
+        if order == 0 {
+            panic!("Order must be at least one");
+        }
+        match smoothing {
+            Smoothing::AddK(k) if k <= 0.0 => panic!("Add-k constant must be positive"),
+            Smoothing::KneserNey(d) if d <= 0.0 || d >= 1.0 => {
+                panic!("Discount must be between 0 and 1")
+            }
+            _ => {}
+        }
+
+        Self {
+            order,
+            smoothing,
+            vocab_size,
+            counts: vec![Counts::new(); order],
+            continuations: vec![Counts::new(); order],
+        }
+    }
+
+    /// The n in n-gram.
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// The smoothing method.
+    pub fn smoothing(&self) -> Smoothing {
+        self.smoothing
+    }
+
+    /// Adds the n-grams of one token sequence to the counts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a token id is outside the vocabulary.
+    pub fn train(&mut self, tokens: &[usize]) {
+        let padded = self.pad(tokens);
+
+        for end in self.order - 1..padded.len() {
+            for length in 1..=self.order {
+                let context = padded[end + 1 - length..end].to_vec();
+                *self.counts[length - 1]
+                    .entry(context)
+                    .or_default()
+                    .entry(padded[end])
+                    .or_default() += 1.0;
+            }
+        }
+
+        self.update_continuations();
+    }
+
+    /// Probability of `token` after `context`. Only the last `n - 1` tokens of the context
+    /// are used; a shorter context is padded with `<bos>`.
+    pub fn probability(&self, context: &[usize], token: usize) -> f64 {
+        let mut history = vec![Vocabulary::BOS; (self.order - 1).saturating_sub(context.len())];
+        history.extend_from_slice(&context[context.len().saturating_sub(self.order - 1)..]);
+
+        match self.smoothing {
+            Smoothing::AddK(k) => self.add_k(k, &history, token),
+            Smoothing::KneserNey(discount) => {
+                self.kneser_ney(discount, self.order, &history, token)
+            }
+        }
+    }
+
+    /// Mean negative log-probability per token of `tokens`, in nats.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tokens` is empty or a token id is outside the vocabulary.
+    pub fn cross_entropy(&self, tokens: &[usize]) -> f64 {
+        if tokens.is_empty() {
+            panic!("Cannot score an empty sequence");
+        }
+
+        let padded = self.pad(tokens);
+        let total: f64 = (self.order - 1..padded.len())
+            .map(|end| {
+                -self
+                    .probability(&padded[end + 1 - self.order..end], padded[end])
+                    .ln()
+            })
+            .sum();
+
+        total / tokens.len() as f64
+    }
+
+    /// Perplexity of `tokens`: the exponential of [`NGramModel::cross_entropy`].
+    pub fn perplexity(&self, tokens: &[usize]) -> f64 {
+        self.cross_entropy(tokens).exp()
+    }
+
+    fn pad(&self, tokens: &[usize]) -> Vec<usize> {
+        if tokens.iter().any(|id| *id >= self.vocab_size) {
+            panic!("Token id outside the vocabulary");
+        }
+
+        let mut padded = vec![Vocabulary::BOS; self.order - 1];
+        padded.extend_from_slice(tokens);
+        padded
+    }
+
+    fn add_k(&self, k: f64, history: &[usize], token: usize) -> f64 {
+        let followers = self.counts[self.order - 1].get(history);
+        let count = followers
+            .and_then(|f| f.get(&token))
+            .copied()
+            .unwrap_or(0.0);
+        let total: f64 = followers.map_or(0.0, |f| f.values().sum());
+
+        (count + k) / (total + k * self.vocab_size as f64)
+    }
+
+    /// Interpolated Kneser-Ney at n-gram `length`, where `history` holds `length - 1` tokens.
+    fn kneser_ney(&self, discount: f64, length: usize, history: &[usize], token: usize) -> f64 {
+        if length == 0 {
+            return 1.0 / self.vocab_size as f64;
+        }
+
+        let lower = self.kneser_ney(
+            discount,
+            length - 1,
+            &history[1.min(history.len())..],
+            token,
+        );
+        let table = if length == self.order {
+            &self.counts[length - 1]
+        } else {
+            &self.continuations[length - 1]
+        };
+
+        let Some(followers) = table.get(history) else {
+            return lower;
+        };
+        let total: f64 = followers.values().sum();
+        let count = followers.get(&token).copied().unwrap_or(0.0);
+        let types = followers.len() as f64;
+
+        ((count - discount).max(0.0) + discount * types * lower) / total
+    }
+
+    /// Recounts, for every lower order, in how many distinct contexts each n-gram follows.
+    fn update_continuations(&mut self) {
+        for length in 1..self.order {
+            let mut continuations = Counts::new();
+            for (context, followers) in &self.counts[length] {
+                for token in followers.keys() {
+                    *continuations
+                        .entry(context[1..].to_vec())
+                        .or_default()
+                        .entry(*token)
+                        .or_default() += 1.0;
+                }
+            }
+            self.continuations[length - 1] = continuations;
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Small matrix helpers shared by the language models.
+
+use aspirina_core::matrix::Matrix;
+use aspirina_core::random::Rng;
+
+/// A `rows × cols` matrix of zeros.
+pub(crate) fn zeros(rows: usize, cols: usize) -> Matrix {
+    Matrix::new(vec![vec![0.0; cols]; rows])
+}
+
+/// Applies `f` to every element.
+pub(crate) fn map(matrix: &Matrix, f: impl Fn(f64) -> f64) -> Matrix {
+    Matrix::new(
+        matrix
+            .data
+            .iter()
+            .map(|row| row.iter().map(|x| f(*x)).collect())
+            .collect(),
+    )
+}
+
+/// Combines two matrices of the same shape element by element.
+pub(crate) fn zip_with(a: &Matrix, b: &Matrix, f: impl Fn(f64, f64) -> f64) -> Matrix {
+    Matrix::new(
+        a.data
+            .iter()
+            .zip(b.data.iter())
+            .map(|(a, b)| a.iter().zip(b.iter()).map(|(a, b)| f(*a, *b)).collect())
+            .collect(),
+    )
+}
+
+/// Adds the `1 × cols` row vector `bias` to every row.
+pub(crate) fn add_row(matrix: &Matrix, bias: &Matrix) -> Matrix {
+    Matrix::new(
+        matrix
+            .data
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(bias.data[0].iter())
+                    .map(|(x, b)| x + b)
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+/// Sums every column into a `1 × cols` row vector.
+pub(crate) fn column_sums(matrix: &Matrix) -> Matrix {
+    let mut sums = vec![0.0; matrix.data.first().map_or(0, Vec::len)];
+    for row in &matrix.data {
+        for (sum, x) in sums.iter_mut().zip(row.iter()) {
+            *sum += x;
+        }
+    }
+
+    Matrix::new(vec![sums])
+}
+
+/// Uniform random matrix in `±limit`.
+pub(crate) fn uniform(rows: usize, cols: usize, limit: f64, rng: &mut Rng) -> Matrix {
+    Matrix::new(
+        (0..rows)
+            .map(|_| (0..cols).map(|_| rng.range(-limit, limit)).collect())
+            .collect(),
+    )
+}
+
+/// Numerically stable softmax of one row of logits.
+pub(crate) fn softmax(logits: &[f64]) -> Vec<f64> {
+    let max = logits.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let exps: Vec<f64> = logits.iter().map(|x| (x - max).exp()).collect();
+    let sum: f64 = exps.iter().sum();
+
+    exps.iter().map(|e| e / sum).collect()
+}
+
+/// Sum of the squared values of every matrix.
+pub(crate) fn squared_norm(matrices: &[Matrix]) -> f64 {
+    matrices
+        .iter()
+        .flat_map(|m| m.data.iter().flatten())
+        .map(|x| x * x)
+        .sum()
+}
+
+/// The factor that scales gradients with the given squared norm down to at most `max_norm`.
+pub(crate) fn clip_factor(squared_norm: f64, max_norm: f64) -> f64 {
+    let norm = squared_norm.sqrt();
+    if norm > max_norm {
+        max_norm / norm
+    } else {
+        1.0
+    }
+}
+
+/// Columns `start..end` as a new matrix.
+pub(crate) fn columns(matrix: &Matrix, start: usize, end: usize) -> Matrix {
+    Matrix::new(
+        matrix
+            .data
+            .iter()
+            .map(|row| row[start..end].to_vec())
+            .collect(),
+    )
+}
+
+/// Places matrices with the same number of rows side by side.
+pub(crate) fn concat_columns(parts: &[Matrix]) -> Matrix {
+    Matrix::new(
+        (0..parts[0].data.len())
+            .map(|i| {
+                parts
+                    .iter()
+                    .flat_map(|part| part.data[i].iter().copied())
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+/// Affine map `y = x·Wᵀ + b` with `W` stored as `outputs × inputs` and `b` as `1 × outputs`.
+#[derive(Debug, Clone)]
+pub(crate) struct Linear {
+    pub(crate) weights: Matrix,
+    pub(crate) bias: Matrix,
+}
+
+impl Linear {
+    /// Weights uniform in `±1/√inputs`, bias zero.
+    pub(crate) fn new(inputs: usize, outputs: usize, rng: &mut Rng) -> Self {
+        Self {
+            weights: uniform(outputs, inputs, 1.0 / (inputs as f64).sqrt(), rng),
+            bias: zeros(1, outputs),
+        }
+    }
+
+    pub(crate) fn forward(&self, input: &Matrix) -> Matrix {
+        add_row(&(input.clone() * self.weights.transpose()), &self.bias)
+    }
+
+    /// Returns the input gradient and the `[weights, bias]` gradients.
+    pub(crate) fn backward(
+        &self,
+        input: &Matrix,
+        output_gradient: &Matrix,
+    ) -> (Matrix, Vec<Matrix>) {
+        let weights = output_gradient.transpose() * input.clone();
+        let bias = column_sums(output_gradient);
+        let input_gradient = output_gradient.clone() * self.weights.clone();
+
+        (input_gradient, vec![weights, bias])
+    }
+
+    pub(crate) fn parameters(&self) -> Vec<Matrix> {
+        vec![self.weights.clone(), self.bias.clone()]
+    }
+
+    pub(crate) fn set_parameters(&mut self, parameters: &mut impl Iterator<Item = Matrix>) {
+        self.weights = parameters.next().unwrap();
+        self.bias = parameters.next().unwrap();
+    }
+}
@@ -0,0 +1,883 @@
+//! Recurrent Layers
+//!
+//! Feed-forward networks see each input in isolation. A recurrent layer keeps a hidden state
+//! that is updated at every timestep, so its output can depend on everything it has read so
+//! far, which is what a language model needs.
+//!
+//! # Cells
+//!
+//! A [`RecurrentCell`] computes one timestep: it takes the input of the step and the previous
+//! state and returns the new state. Three cells are available:
+//!
+//! - [`RnnCell`] (Elman): `h' = tanh(x·Wᵀ + h·Uᵀ + b)`
+//! - [`GruCell`]: update gate `z`, reset gate `r` and candidate `n`;
+//!   `h' = (1 - z)⊙n + z⊙h`
+//! - [`LstmCell`]: input, forget and output gates plus a separate cell state `c`;
+//!   `c' = f⊙c + i⊙g`, `h' = o⊙tanh(c')`
+//!
+//! Every weight matrix is stored as `outputs × inputs`, like the dense layers of
+//! `aspirina-gates`, and every timestep is a `[batch_size, features]` matrix.
+//!
+//! # Backpropagation Through Time
+//!
+//! [`Recurrent::forward`] runs a cell over a sequence and remembers what each step needs for
+//! the backward pass; [`Recurrent::backward`] walks the sequence in reverse, accumulating the
+//! gradient of every parameter. Long sequences are trained in windows of a few steps
+//! (*truncated* BPTT): the hidden state flows from one window into the next, but gradients
+//! stop at the window boundary. [`SequenceModel::train_truncated`] does this for a recurrent
+//! layer followed by a linear readout.
+//!
+//! # Streaming
+//!
+//! The hidden state is carried over between calls until [`Recurrent::reset_state`], so a
+//! sequence can be fed one timestep at a time with [`Recurrent::step`] and gives the same
+//! outputs as processing it in one go.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_language::recurrent::{GruCell, Recurrent};
+//! # use aspirina_core::matrix::Matrix;
+//! let mut layer = Recurrent::new(GruCell::new(2, 4, 42));
+//! let sequence = vec![
+//!     Matrix::new(vec![vec![1.0, 0.0]]),
+//!     Matrix::new(vec![vec![0.0, 1.0]]),
+//! ];
+//!
+//! let outputs = layer.forward(&sequence);
+//! assert_eq!(outputs.len(), 2);
+//! assert_eq!(outputs[1].data[0].len(), 4);
+//!
+//! // The state carries over: the next step continues the same sequence.
+//! let next = layer.step(&Matrix::new(vec![vec![1.0, 1.0]]));
+//! assert_eq!(next.data[0].len(), 4);
+//! ```
+
+use crate::ops::{add_row, column_sums, map, uniform, zeros, zip_with};
+use aspirina_core::calc::Calc;
+use aspirina_core::matrix::Matrix;
+use aspirina_core::random::Rng;
+use std::fmt::Debug;
+
+/// The state a cell passes from one timestep to the next.
+#[derive(Debug, Clone)]
+pub struct CellState {
+    /// Hidden state (the cell's output), shape `[batch_size, hidden_size]`
+    pub hidden: Matrix,
+    /// LSTM cell state, shape `[batch_size, hidden_size]`; `None` for other cells
+    pub cell: Option<Matrix>,
+}
+
+/// One timestep of a recurrent network.
+pub trait RecurrentCell {
+    /// Values saved by [`forward`](Self::forward) for use in [`backward`](Self::backward).
+    type Cache: Clone + Debug;
+
+    /// Number of input features per timestep.
+    fn input_size(&self) -> usize;
+
+    /// Number of hidden units.
+    fn hidden_size(&self) -> usize;
+
+    /// The all-zero state for a batch of `batch_size` sequences.
+    fn initial_state(&self, batch_size: usize) -> CellState;
+
+    /// Computes the state after reading `input` in `state`.
+    fn forward(&self, input: &Matrix, state: &CellState) -> (CellState, Self::Cache);
+
+    /// Given the loss gradient with respect to the new state, returns the gradients with
+    /// respect to the input, the previous state and every parameter (in the order of
+    /// [`parameters`](Self::parameters)).
+    fn backward(
+        &self,
+        cache: &Self::Cache,
+        state_gradient: &CellState,
+    ) -> (Matrix, CellState, Vec<Matrix>);
+
+    /// Copies of the trainable parameters.
+    fn parameters(&self) -> Vec<Matrix>;
+
+    /// Replaces the trainable parameters, in the order of [`parameters`](Self::parameters).
+    fn set_parameters(&mut self, parameters: Vec<Matrix>);
+}
+
+/// Input weights, recurrent weights and bias feeding one activation.
+#[derive(Debug, Clone)]
+struct Gate {
+    input: Matrix,
+    hidden: Matrix,
+    bias: Matrix,
+}
+
+impl Gate {
+    fn new(input_size: usize, hidden_size: usize, bias: f64, rng: &mut Rng) -> Self {
+        let limit = 1.0 / (hidden_size as f64).sqrt();
+
+        Self {
+            input: uniform(hidden_size, input_size, limit, rng),
+            hidden: uniform(hidden_size, hidden_size, limit, rng),
+            bias: Matrix::new(vec![vec![bias; hidden_size]]),
+        }
+    }
+
+    /// `x·Wᵀ + h·Uᵀ + b`
+    fn pre_activation(&self, input: &Matrix, hidden: &Matrix) -> Matrix {
+        add_row(
+            &(input.clone() * self.input.transpose() + hidden.clone() * self.hidden.transpose()),
+            &self.bias,
+        )
+    }
+
+    /// Returns the gradients with respect to the input, the hidden state and `[W, U, b]`.
+    fn backward(
+        &self,
+        input: &Matrix,
+        hidden: &Matrix,
+        gradient: &Matrix,
+    ) -> (Matrix, Matrix, Vec<Matrix>) {
+        let input_gradient = gradient.clone() * self.input.clone();
+        let hidden_gradient = gradient.clone() * self.hidden.clone();
+        let parameters = vec![
+            gradient.transpose() * input.clone(),
+            gradient.transpose() * hidden.clone(),
+            column_sums(gradient),
+        ];
+
+        (input_gradient, hidden_gradient, parameters)
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        vec![self.input.clone(), self.hidden.clone(), self.bias.clone()]
+    }
+
+    fn set_parameters(&mut self, parameters: &mut impl Iterator<Item = Matrix>) {
+        self.input = parameters.next().unwrap();
+        self.hidden = parameters.next().unwrap();
+        self.bias = parameters.next().unwrap();
+    }
+}
+
+fn sigmoid(matrix: &Matrix) -> Matrix {
+    map(matrix, |x| Calc::sigmoid(&x))
+}
+
+fn tanh(matrix: &Matrix) -> Matrix {
+    map(matrix, |x| Calc::tanh(&x))
+}
+
+/// `gradient ⊙ σ'`, given the sigmoid output.
+fn through_sigmoid(gradient: &Matrix, output: &Matrix) -> Matrix {
+    zip_with(gradient, output, |g, y| g * Calc::sigmoid_derivative(&y))
+}
+
+/// `gradient ⊙ tanh'`, given the tanh output.
+fn through_tanh(gradient: &Matrix, output: &Matrix) -> Matrix {
+    zip_with(gradient, output, |g, y| g * (1.0 - y * y))
+}
+
+fn product(a: &Matrix, b: &Matrix) -> Matrix {
+    zip_with(a, b, |a, b| a * b)
+}
+
+fn check_parameter_count(parameters: &[Matrix], expected: usize) {
+    if parameters.len() != expected {
+        panic!("Parameter count does not match cell");
+    }
+}
+
+/// Elman recurrent cell: `h' = tanh(x·Wᵀ + h·Uᵀ + b)`.
+#[derive(Debug, Clone)]
+pub struct RnnCell {
+    gate: Gate,
+}
+
+/// Values saved by [`RnnCell::forward`].
+#[derive(Debug, Clone)]
+pub struct RnnCache {
+    input: Matrix,
+    previous: Matrix,
+    hidden: Matrix,
+}
+
+impl RnnCell {
+    /// Creates a cell with weights drawn uniformly from `±1/√hidden_size` using `seed`.
+    pub fn new(input_size: usize, hidden_size: usize, seed: u64) -> Self {
+        Self {
+            gate: Gate::new(input_size, hidden_size, 0.0, &mut Rng::new(seed)),
+        }
+    }
+}
+
+impl RecurrentCell for RnnCell {
+    type Cache = RnnCache;
+
+    fn input_size(&self) -> usize {
+        self.gate.input.data[0].len()
+    }
+
+    fn hidden_size(&self) -> usize {
+        self.gate.hidden.data.len()
+    }
+
+    fn initial_state(&self, batch_size: usize) -> CellState {
+        CellState {
+            hidden: zeros(batch_size, self.hidden_size()),
+            cell: None,
+        }
+    }
+
+    fn forward(&self, input: &Matrix, state: &CellState) -> (CellState, RnnCache) {
+        let hidden = tanh(&self.gate.pre_activation(input, &state.hidden));
+        let cache = RnnCache {
+            input: input.clone(),
+            previous: state.hidden.clone(),
+            hidden: hidden.clone(),
+        };
+
+        (CellState { hidden, cell: None }, cache)
+    }
+
+    fn backward(
+        &self,
+        cache: &RnnCache,
+        state_gradient: &CellState,
+    ) -> (Matrix, CellState, Vec<Matrix>) {
+        let gradient = through_tanh(&state_gradient.hidden, &cache.hidden);
+        let (input_gradient, hidden_gradient, parameters) =
+            self.gate.backward(&cache.input, &cache.previous, &gradient);
+
+        let previous = CellState {
+            hidden: hidden_gradient,
+            cell: None,
+        };
+
+        (input_gradient, previous, parameters)
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        self.gate.parameters()
+    }
+
+    fn set_parameters(&mut self, parameters: Vec<Matrix>) {
+        check_parameter_count(&parameters, 3);
+        self.gate.set_parameters(&mut parameters.into_iter());
+    }
+}
+
+/// Gated recurrent unit.
+///
+/// ```text
+/// z  = σ(x·Wzᵀ + h·Uzᵀ + bz)          update gate
+/// r  = σ(x·Wrᵀ + h·Urᵀ + br)          reset gate
+/// n  = tanh(x·Wnᵀ + (r⊙h)·Unᵀ + bn)   candidate
+/// h' = (1 - z)⊙n + z⊙h
+/// ```
+///
+/// Parameters are ordered update, reset, candidate, each as `[W, U, b]`.
+#[derive(Debug, Clone)]
+pub struct GruCell {
+    update: Gate,
+    reset: Gate,
+    candidate: Gate,
+}
+
+/// Values saved by [`GruCell::forward`].
+#[derive(Debug, Clone)]
+pub struct GruCache {
+    input: Matrix,
+    previous: Matrix,
+    update: Matrix,
+    reset: Matrix,
+    reset_hidden: Matrix,
+    candidate: Matrix,
+}
+
+impl GruCell {
+    /// Creates a cell with weights drawn uniformly from `±1/√hidden_size` using `seed`.
+    pub fn new(input_size: usize, hidden_size: usize, seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+
+        Self {
+            update: Gate::new(input_size, hidden_size, 0.0, &mut rng),
+            reset: Gate::new(input_size, hidden_size, 0.0, &mut rng),
+            candidate: Gate::new(input_size, hidden_size, 0.0, &mut rng),
+        }
+    }
+}
+
+impl RecurrentCell for GruCell {
+    type Cache = GruCache;
+
+    fn input_size(&self) -> usize {
+        self.update.input.data[0].len()
+    }
+
+    fn hidden_size(&self) -> usize {
+        self.update.hidden.data.len()
+    }
+
+    fn initial_state(&self, batch_size: usize) -> CellState {
+        CellState {
+            hidden: zeros(batch_size, self.hidden_size()),
+            cell: None,
+        }
+    }
+
+    fn forward(&self, input: &Matrix, state: &CellState) -> (CellState, GruCache) {
+        let previous = &state.hidden;
+        let update = sigmoid(&self.update.pre_activation(input, previous));
+        let reset = sigmoid(&self.reset.pre_activation(input, previous));
+        let reset_hidden = product(&reset, previous);
+        let candidate = tanh(&self.candidate.pre_activation(input, &reset_hidden));
+
+        let hidden = Matrix::new(
+            update
+                .data
+                .iter()
+                .zip(candidate.data.iter())
+                .zip(previous.data.iter())
+                .map(|((z, n), h)| {
+                    z.iter()
+                        .zip(n.iter())
+                        .zip(h.iter())
+                        .map(|((z, n), h)| (1.0 - z) * n + z * h)
+                        .collect()
+                })
+                .collect(),
+        );
+
+        let cache = GruCache {
+            input: input.clone(),
+            previous: previous.clone(),
+            update,
+            reset,
+            reset_hidden,
+            candidate,
+        };
+
+        (CellState { hidden, cell: None }, cache)
+    }
+
+    fn backward(
+        &self,
+        cache: &GruCache,
+        state_gradient: &CellState,
+    ) -> (Matrix, CellState, Vec<Matrix>) {
+        let dh = &state_gradient.hidden;
+
+        // h' = (1 - z)⊙n + z⊙h
+        let d_candidate = zip_with(dh, &cache.update, |g, z| g * (1.0 - z));
+        let d_update = zip_with(
+            &product(
+                dh,
+                &zip_with(&cache.previous, &cache.candidate, |h, n| h - n),
+            ),
+            &cache.update,
+            |g, z| g * Calc::sigmoid_derivative(&z),
+        );
+        let mut d_previous = product(dh, &cache.update);
+
+        let (dx_n, d_reset_hidden, candidate_parameters) = self.candidate.backward(
+            &cache.input,
+            &cache.reset_hidden,
+            &through_tanh(&d_candidate, &cache.candidate),
+        );
+
+        // r⊙h feeds the candidate
+        let d_reset = through_sigmoid(&product(&d_reset_hidden, &cache.previous), &cache.reset);
+        d_previous = d_previous + product(&d_reset_hidden, &cache.reset);
+
+        let (dx_z, dh_z, update_parameters) =
+            self.update
+                .backward(&cache.input, &cache.previous, &d_update);
+        let (dx_r, dh_r, reset_parameters) =
+            self.reset.backward(&cache.input, &cache.previous, &d_reset);
+
+        let input_gradient = dx_n + dx_z + dx_r;
+        let previous = CellState {
+            hidden: d_previous + dh_z + dh_r,
+            cell: None,
+        };
+
+        let mut parameters = update_parameters;
+        parameters.extend(reset_parameters);
+        parameters.extend(candidate_parameters);
+
+        (input_gradient, previous, parameters)
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        let mut parameters = self.update.parameters();
+        parameters.extend(self.reset.parameters());
+        parameters.extend(self.candidate.parameters());
+        parameters
+    }
+
+    fn set_parameters(&mut self, parameters: Vec<Matrix>) {
+        check_parameter_count(&parameters, 9);
+        let mut parameters = parameters.into_iter();
+        self.update.set_parameters(&mut parameters);
+        self.reset.set_parameters(&mut parameters);
+        self.candidate.set_parameters(&mut parameters);
+    }
+}
+
+/// Long short-term memory cell.
+///
+/// ```text
+/// i  = σ(x·Wiᵀ + h·Uiᵀ + bi)      input gate
+/// f  = σ(x·Wfᵀ + h·Ufᵀ + bf)      forget gate
+/// g  = tanh(x·Wgᵀ + h·Ugᵀ + bg)   candidate
+/// o  = σ(x·Woᵀ + h·Uoᵀ + bo)      output gate
+/// c' = f⊙c + i⊙g
+/// h' = o⊙tanh(c')
+/// ```
+///
+/// The forget gate bias starts at 1 so the cell remembers by default. Parameters are ordered
+/// input, forget, candidate, output, each as `[W, U, b]`.
+#[derive(Debug, Clone)]
+pub struct LstmCell {
+    input_gate: Gate,
+    forget: Gate,
+    candidate: Gate,
+    output: Gate,
+}
+
+/// Values saved by [`LstmCell::forward`].
+#[derive(Debug, Clone)]
+pub struct LstmCache {
+    input: Matrix,
+    previous_hidden: Matrix,
+    previous_cell: Matrix,
+    input_gate: Matrix,
+    forget: Matrix,
+    candidate: Matrix,
+    output: Matrix,
+    cell_activation: Matrix,
+}
+
+impl LstmCell {
+    /// Creates a cell with weights drawn uniformly from `±1/√hidden_size` using `seed`.
+    pub fn new(input_size: usize, hidden_size: usize, seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+
+        Self {
+            input_gate: Gate::new(input_size, hidden_size, 0.0, &mut rng),
+            forget: Gate::new(input_size, hidden_size, 1.0, &mut rng),
+            candidate: Gate::new(input_size, hidden_size, 0.0, &mut rng),
+            output: Gate::new(input_size, hidden_size, 0.0, &mut rng),
+        }
+    }
+}
+
+impl RecurrentCell for LstmCell {
+    type Cache = LstmCache;
+
+    fn input_size(&self) -> usize {
+        self.input_gate.input.data[0].len()
+    }
+
+    fn hidden_size(&self) -> usize {
+        self.input_gate.hidden.data.len()
+    }
+
+    fn initial_state(&self, batch_size: usize) -> CellState {
+        CellState {
+            hidden: zeros(batch_size, self.hidden_size()),
+            cell: Some(zeros(batch_size, self.hidden_size())),
+        }
+    }
+
+    fn forward(&self, input: &Matrix, state: &CellState) -> (CellState, LstmCache) {
+        let previous_hidden = &state.hidden;
+        let previous_cell = state.cell.as_ref().unwrap();
+
+        let input_gate = sigmoid(&self.input_gate.pre_activation(input, previous_hidden));
+        let forget = sigmoid(&self.forget.pre_activation(input, previous_hidden));
+        let candidate = tanh(&self.candidate.pre_activation(input, previous_hidden));
+        let output = sigmoid(&self.output.pre_activation(input, previous_hidden));
+
+        let cell = product(&forget, previous_cell) + product(&input_gate, &candidate);
+        let cell_activation = tanh(&cell);
+        let hidden = product(&output, &cell_activation);
+
+        let cache = LstmCache {
+            input: input.clone(),
+            previous_hidden: previous_hidden.clone(),
+            previous_cell: previous_cell.clone(),
+            input_gate,
+            forget,
+            candidate,
+            output,
+            cell_activation,
+        };
+
+        (
+            CellState {
+                hidden,
+                cell: Some(cell),
+            },
+            cache,
+        )
+    }
+
+    fn backward(
+        &self,
+        cache: &LstmCache,
+        state_gradient: &CellState,
+    ) -> (Matrix, CellState, Vec<Matrix>) {
+        let dh = &state_gradient.hidden;
+
+        // h' = o⊙tanh(c'), and c' also receives the gradient flowing back from the next step
+        let d_output = through_sigmoid(&product(dh, &cache.cell_activation), &cache.output);
+        let mut d_cell = through_tanh(&product(dh, &cache.output), &cache.cell_activation);
+        if let Some(next) = &state_gradient.cell {
+            d_cell = d_cell + next.clone();
+        }
+
+        // c' = f⊙c + i⊙g
+        let d_input_gate = through_sigmoid(&product(&d_cell, &cache.candidate), &cache.input_gate);
+        let d_forget = through_sigmoid(&product(&d_cell, &cache.previous_cell), &cache.forget);
+        let d_candidate = through_tanh(&product(&d_cell, &cache.input_gate), &cache.candidate);
+        let d_previous_cell = product(&d_cell, &cache.forget);
+
+        let mut input_gradient = zeros(cache.input.data.len(), self.input_size());
+        let mut hidden_gradient = zeros(cache.input.data.len(), self.hidden_size());
+        let mut parameters = Vec::with_capacity(12);
+
+        for (gate, gradient) in [
+            (&self.input_gate, d_input_gate),
+            (&self.forget, d_forget),
+            (&self.candidate, d_candidate),
+            (&self.output, d_output),
+        ] {
+            let (dx, dh, gate_parameters) =
+                gate.backward(&cache.input, &cache.previous_hidden, &gradient);
+            input_gradient = input_gradient + dx;
+            hidden_gradient = hidden_gradient + dh;
+            parameters.extend(gate_parameters);
+        }
+
+        let previous = CellState {
+            hidden: hidden_gradient,
+            cell: Some(d_previous_cell),
+        };
+
+        (input_gradient, previous, parameters)
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        let mut parameters = self.input_gate.parameters();
+        parameters.extend(self.forget.parameters());
+        parameters.extend(self.candidate.parameters());
+        parameters.extend(self.output.parameters());
+        parameters
+    }
+
+    fn set_parameters(&mut self, parameters: Vec<Matrix>) {
+        check_parameter_count(&parameters, 12);
+        let mut parameters = parameters.into_iter();
+        self.input_gate.set_parameters(&mut parameters);
+        self.forget.set_parameters(&mut parameters);
+        self.candidate.set_parameters(&mut parameters);
+        self.output.set_parameters(&mut parameters);
+    }
+}
+
+/// Gradients produced by [`Recurrent::backward`].
+#[derive(Debug, Clone)]
+pub struct RecurrentGradients {
+    /// Loss gradient with respect to each input timestep
+    pub inputs: Vec<Matrix>,
+    /// Loss gradient with respect to each cell parameter, summed over the timesteps
+    pub parameters: Vec<Matrix>,
+}
+
+/// A recurrent layer: a cell applied over a sequence, with the hidden state carried between
+/// calls.
+#[derive(Debug, Clone)]
+pub struct Recurrent<C: RecurrentCell> {
+    /// The cell applied at every timestep
+    pub cell: C,
+    state: Option<CellState>,
+    caches: Vec<C::Cache>,
+}
+
+impl<C: RecurrentCell> Recurrent<C> {
+    /// Wraps `cell` in a layer whose state starts at zero.
+    pub fn new(cell: C) -> Self {
+        Self {
+            cell,
+            state: None,
+            caches: Vec::new(),
+        }
+    }
+
+    /// The state after the last processed timestep, or `None` after a reset.
+    pub fn state(&self) -> Option<&CellState> {
+        self.state.as_ref()
+    }
+
+    /// Forgets the carried state so the next input starts a new sequence.
+    pub fn reset_state(&mut self) {
+        self.state = None;
+        self.caches.clear();
+    }
+
+    /// Processes a single timestep for streaming inference and returns the hidden state.
+    ///
+    /// Nothing is saved for the backward pass.
+    pub fn step(&mut self, input: &Matrix) -> Matrix {
+        let state = self.current_state(input);
+        let (state, _) = self.cell.forward(input, &state);
+        let hidden = state.hidden.clone();
+        self.state = Some(state);
+        hidden
+    }
+
+    /// Processes `sequence`, continuing from the carried state, and returns the hidden state
+    /// after every timestep.
+    ///
+    /// The steps are remembered for [`backward`](Self::backward), replacing those of any
+    /// earlier call.
+    pub fn forward(&mut self, sequence: &[Matrix]) -> Vec<Matrix> {
+        self.caches.clear();
+        let mut outputs = Vec::with_capacity(sequence.len());
+
+        for input in sequence {
+            let state = self.current_state(input);
+            let (state, cache) = self.cell.forward(input, &state);
+            outputs.push(state.hidden.clone());
+            self.caches.push(cache);
+            self.state = Some(state);
+        }
+
+        outputs
+    }
+
+    /// Backpropagates through the timesteps of the last [`forward`](Self::forward) call.
+    ///
+    /// `output_gradients[t]` is the loss gradient with respect to the hidden state returned
+    /// for timestep `t`. The gradient reaching the state the sequence started from is
+    /// discarded, which is what truncates backpropagation at the window boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of gradients does not match the number of timesteps.
+    pub fn backward(&self, output_gradients: &[Matrix]) -> RecurrentGradients {
+        if output_gradients.len() != self.caches.len() {
+            panic!("Expected one output gradient per timestep");
+        }
+
+        let mut parameters: Option<Vec<Matrix>> = None;
+        let mut inputs = Vec::with_capacity(self.caches.len());
+        let mut state_gradient: Option<CellState> = None;
+
+        for (cache, output_gradient) in self.caches.iter().zip(output_gradients.iter()).rev() {
+            let gradient = match state_gradient {
+                Some(next) => CellState {
+                    hidden: next.hidden + output_gradient.clone(),
+                    cell: next.cell,
+                },
+                None => CellState {
+                    hidden: output_gradient.clone(),
+                    cell: None,
+                },
+            };
+
+            let (input_gradient, previous, step_parameters) = self.cell.backward(cache, &gradient);
+
+            parameters = Some(match parameters {
+                Some(total) => total
+                    .into_iter()
+                    .zip(step_parameters)
+                    .map(|(total, step)| total + step)
+                    .collect(),
+                None => step_parameters,
+            });
+            inputs.push(input_gradient);
+            state_gradient = Some(previous);
+        }
+
+        inputs.reverse();
+
+        RecurrentGradients {
+            inputs,
+            parameters: parameters.unwrap_or_else(|| {
+                self.cell
+                    .parameters()
+                    .iter()
+                    .map(|p| zeros(p.data.len(), p.data[0].len()))
+                    .collect()
+            }),
+        }
+    }
+
+    /// The carried state, or a zero state sized for `input`'s batch.
+    fn current_state(&self, input: &Matrix) -> CellState {
+        match &self.state {
+            Some(state) if state.hidden.data.len() == input.data.len() => state.clone(),
+            Some(_) => panic!("Input batch size does not match the carried state"),
+            None => self.cell.initial_state(input.data.len()),
+        }
+    }
+}
+
+/// A recurrent layer followed by a linear readout, trained with squared error.
+///
+/// At every timestep the output is `h·Vᵀ + c`, where `h` is the hidden state and `V`, `c` are
+/// the readout weights and bias. The loss over a sequence is `½ Σ (target - output)²`.
+#[derive(Debug, Clone)]
+pub struct SequenceModel<C: RecurrentCell> {
+    /// The recurrent layer
+    pub recurrent: Recurrent<C>,
+    /// Readout weights, shape `output_size × hidden_size`
+    pub readout: Matrix,
+    /// Readout bias, shape `1 × output_size`
+    pub readout_bias: Matrix,
+}
+
+impl<C: RecurrentCell> SequenceModel<C> {
+    /// Puts a readout with `output_size` outputs, initialized from `seed`, on top of `cell`.
+    pub fn new(cell: C, output_size: usize, seed: u64) -> Self {
+        let hidden_size = cell.hidden_size();
+        let limit = 1.0 / (hidden_size as f64).sqrt();
+
+        Self {
+            recurrent: Recurrent::new(cell),
+            readout: uniform(output_size, hidden_size, limit, &mut Rng::new(seed)),
+            readout_bias: zeros(1, output_size),
+        }
+    }
+
+    /// Processes one timestep, continuing from the carried state, and returns the output.
+    pub fn predict_step(&mut self, input: &Matrix) -> Matrix {
+        let hidden = self.recurrent.step(input);
+        self.read_out(&hidden)
+    }
+
+    /// Outputs for every timestep of `inputs`, starting from a fresh state.
+    pub fn predict(&mut self, inputs: &[Matrix]) -> Vec<Matrix> {
+        self.recurrent.reset_state();
+        self.recurrent
+            .forward(inputs)
+            .iter()
+            .map(|hidden| self.read_out(hidden))
+            .collect()
+    }
+
+    /// Loss over the whole sequence, starting from a fresh state.
+    pub fn loss(&mut self, inputs: &[Matrix], targets: &[Matrix]) -> f64 {
+        self.predict(inputs)
+            .iter()
+            .zip(targets.iter())
+            .map(|(output, target)| squared_error(output, target))
+            .sum()
+    }
+
+    /// Copies of every trainable parameter: the cell's, then the readout weights and bias.
+    pub fn parameters(&self) -> Vec<Matrix> {
+        let mut parameters = self.recurrent.cell.parameters();
+        parameters.push(self.readout.clone());
+        parameters.push(self.readout_bias.clone());
+        parameters
+    }
+
+    /// Replaces every trainable parameter, in the order of [`parameters`](Self::parameters).
+    pub fn set_parameters(&mut self, mut parameters: Vec<Matrix>) {
+        self.readout_bias = parameters.pop().unwrap();
+        self.readout = parameters.pop().unwrap();
+        self.recurrent.cell.set_parameters(parameters);
+    }
+
+    /// Loss gradient of every parameter with full backpropagation through the sequence,
+    /// starting from a fresh state. Parameters are left unchanged.
+    pub fn gradients(&mut self, inputs: &[Matrix], targets: &[Matrix]) -> Vec<Matrix> {
+        self.recurrent.reset_state();
+        self.window_gradients(inputs, targets).1
+    }
+
+    /// Trains on one long sequence with truncated backpropagation through time.
+    ///
+    /// The sequence is cut into windows of `window` timesteps. The state starts at zero and
+    /// flows through every window, while gradients only flow within a window; the parameters
+    /// are updated after each window by `learning_rate × gradient`. Returns the total loss of
+    /// the sequence, measured before each window's update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is zero or the numbers of inputs and targets differ.
+    pub fn train_truncated(
+        &mut self,
+        inputs: &[Matrix],
+        targets: &[Matrix],
+        window: usize,
+        learning_rate: f64,
+    ) -> f64 {
+        if window == 0 {
+            panic!("Window must be greater than zero");
+        }
+        if inputs.len() != targets.len() {
+            panic!("Expected one target per timestep");
+        }
+
+        self.recurrent.reset_state();
+        let mut total_loss = 0.0;
+
+        for (inputs, targets) in inputs.chunks(window).zip(targets.chunks(window)) {
+            let (loss, gradients) = self.window_gradients(inputs, targets);
+            total_loss += loss;
+
+            let parameters = self
+                .parameters()
+                .into_iter()
+                .zip(gradients)
+                .map(|(parameter, gradient)| parameter - gradient.scale(learning_rate))
+                .collect();
+            self.set_parameters(parameters);
+        }
+
+        total_loss
+    }
+
+    /// Forward and backward pass over one window, continuing from the carried state.
+    fn window_gradients(&mut self, inputs: &[Matrix], targets: &[Matrix]) -> (f64, Vec<Matrix>) {
+        let hidden = self.recurrent.forward(inputs);
+        let mut loss = 0.0;
+        let mut readout = zeros(self.readout.data.len(), self.readout.data[0].len());
+        let mut readout_bias = zeros(1, self.readout_bias.data[0].len());
+        let mut hidden_gradients = Vec::with_capacity(hidden.len());
+
+        for (hidden, target) in hidden.iter().zip(targets.iter()) {
+            let output = self.read_out(hidden);
+            loss += squared_error(&output, target);
+
+            let output_gradient = output - target.clone();
+            readout = readout + output_gradient.transpose() * hidden.clone();
+            readout_bias = readout_bias + column_sums(&output_gradient);
+            hidden_gradients.push(output_gradient * self.readout.clone());
+        }
+
+        let mut gradients = self.recurrent.backward(&hidden_gradients).parameters;
+        gradients.push(readout);
+        gradients.push(readout_bias);
+
+        (loss, gradients)
+    }
+
+    fn read_out(&self, hidden: &Matrix) -> Matrix {
+        add_row(
+            &(hidden.clone() * self.readout.transpose()),
+            &self.readout_bias,
+        )
+    }
+}
+
+fn squared_error(output: &Matrix, target: &Matrix) -> f64 {
+    0.5 * output
+        .data
+        .iter()
+        .zip(target.data.iter())
+        .flat_map(|(o, t)| o.iter().zip(t.iter()).map(|(o, t)| (t - o).powi(2)))
+        .sum::<f64>()
+}
@@ -0,0 +1,685 @@
+//! Tokenizers
+//!
+//! A language model works on integer token ids, not on text. A tokenizer fixes the vocabulary
+//! and converts between the two. Three tokenizers are provided:
+//!
+//! - [`CharTokenizer`]: every distinct character is a token
+//! - [`WordTokenizer`]: every whitespace-separated word is a token
+//! - [`BpeTokenizer`]: byte-pair encoding, which starts from characters and learns to merge
+//!   the most frequent adjacent pairs into longer tokens
+//!
+//! # Special Tokens
+//!
+//! Every [`Vocabulary`] starts with four special tokens at fixed ids:
+//!
+//! | Id | Token   | Meaning                                  |
+//! |----|---------|------------------------------------------|
+//! | 0  | `<pad>` | Fills up shorter sequences in a batch    |
+//! | 1  | `<unk>` | Replaces anything outside the vocabulary |
+//! | 2  | `<bos>` | Beginning of sequence                    |
+//! | 3  | `<eos>` | End of sequence                          |
+//!
+//! Decoding drops `<pad>`, `<bos>` and `<eos>`, and writes `<unk>` as is.
+//!
+//! # Vocabulary Files
+//!
+//! Vocabularies are saved as plain text, one token per line in id order. Backslash, space,
+//! tab, carriage return and newline are escaped as `\\`, `\s`, `\t`, `\r` and `\n`. A BPE
+//! merge is written as its two halves separated by a space, so the merges can be replayed
+//! after loading.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_language::tokenizer::{BpeTokenizer, CharTokenizer, Tokenizer, Vocabulary};
+//! let tokenizer = CharTokenizer::from_corpus("hello world");
+//!
+//! let ids = tokenizer.encode("hello");
+//! assert_eq!(tokenizer.decode(&ids), "hello");
+//! assert_eq!(tokenizer.vocab_size(), 4 + 8); // specials + " dehlorw"
+//! assert_eq!(tokenizer.encode("hex")[2], Vocabulary::UNK);
+//!
+//! let bpe = BpeTokenizer::train("low lower lowest", 16);
+//! assert_eq!(bpe.decode(&bpe.encode("lowest low")), "lowest low");
+//! ```
+
+use aspirina_core::matrix::Matrix;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// An ordered set of token strings, starting with the special tokens.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vocabulary {
+    tokens: Vec<String>,
+    ids: HashMap<String, usize>,
+}
+
+impl Vocabulary {
+    /// Id of the padding token.
+    pub const PAD: usize = 0;
+    /// Id of the unknown token.
+    pub const UNK: usize = 1;
+    /// Id of the beginning-of-sequence token.
+    pub const BOS: usize = 2;
+    /// Id of the end-of-sequence token.
+    pub const EOS: usize = 3;
+    /// The special tokens, indexed by id.
+    pub const SPECIAL_TOKENS: [&'static str; 4] = ["<pad>", "<unk>", "<bos>", "<eos>"];
+
+    /// A vocabulary holding only the special tokens.
+    pub fn new() -> Self {
+        let mut vocabulary = Self {
+            tokens: Vec::new(),
+            ids: HashMap::new(),
+        };
+        for token in Self::SPECIAL_TOKENS {
+            vocabulary.insert(token);
+        }
+
+        vocabulary
+    }
+
+    /// Adds `token` if it is new and returns its id.
+    pub fn insert(&mut self, token: &str) -> usize {
+        if let Some(id) = self.ids.get(token) {
+            return *id;
+        }
+
+        let id = self.tokens.len();
+        self.tokens.push(token.to_string());
+        self.ids.insert(token.to_string(), id);
+        id
+    }
+
+    /// Id of `token`, if it is in the vocabulary.
+    pub fn id(&self, token: &str) -> Option<usize> {
+        self.ids.get(token).copied()
+    }
+
+    /// The token with `id`, if there is one.
+    pub fn token(&self, id: usize) -> Option<&str> {
+        self.tokens.get(id).map(String::as_str)
+    }
+
+    /// All tokens, indexed by id.
+    pub fn tokens(&self) -> &[String] {
+        &self.tokens
+    }
+
+    /// Number of tokens, including the special tokens.
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// A vocabulary always holds the special tokens, so it is never empty.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Whether `id` is `<pad>`, `<unk>`, `<bos>` or `<eos>`.
+    pub fn is_special(id: usize) -> bool {
+        id < Self::SPECIAL_TOKENS.len()
+    }
+
+    /// The vocabulary file contents: one escaped token per line.
+    pub fn to_text(&self) -> String {
+        self.tokens
+            .iter()
+            .map(|token| escape(token) + "\n")
+            .collect()
+    }
+
+    /// Parses a vocabulary file with one token per line.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut vocabulary = Self::new();
+
+        for (line_idx, parts) in parse_lines(text)? {
+            match parts.as_slice() {
+                [token] => check_insert(&mut vocabulary, line_idx, token)?,
+                _ => return Err(format!("Line {}: expected a single token", line_idx + 1)),
+            }
+        }
+
+        Ok(vocabulary)
+    }
+}
+
+impl Default for Vocabulary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Converts between text and token ids over a [`Vocabulary`].
+pub trait Tokenizer {
+    /// The vocabulary that defines the token ids.
+    fn vocabulary(&self) -> &Vocabulary;
+
+    /// Splits `text` into token strings, which may fall outside the vocabulary.
+    fn tokenize(&self, text: &str) -> Vec<String>;
+
+    /// Joins token strings back into text.
+    fn join(&self, tokens: &[&str]) -> String;
+
+    /// Number of tokens, including the special tokens.
+    fn vocab_size(&self) -> usize {
+        self.vocabulary().len()
+    }
+
+    /// Converts `text` into token ids, mapping unknown pieces to [`Vocabulary::UNK`].
+    fn encode(&self, text: &str) -> Vec<usize> {
+        let vocabulary = self.vocabulary();
+        self.tokenize(text)
+            .iter()
+            .map(|token| vocabulary.id(token).unwrap_or(Vocabulary::UNK))
+            .collect()
+    }
+
+    /// Like [`Tokenizer::encode`], but fails on the first piece outside the vocabulary.
+    fn try_encode(&self, text: &str) -> Result<Vec<usize>, String> {
+        let vocabulary = self.vocabulary();
+        self.tokenize(text)
+            .iter()
+            .map(|token| {
+                vocabulary
+                    .id(token)
+                    .filter(|id| !Vocabulary::is_special(*id))
+                    .ok_or_else(|| format!("Unknown token {:?}", token))
+            })
+            .collect()
+    }
+
+    /// Encodes `text` between `<bos>` and `<eos>`.
+    fn encode_with_bounds(&self, text: &str) -> Vec<usize> {
+        let mut ids = vec![Vocabulary::BOS];
+        ids.extend(self.encode(text));
+        ids.push(Vocabulary::EOS);
+        ids
+    }
+
+    /// Converts token ids back into text, dropping `<pad>`, `<bos>` and `<eos>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an id is outside the vocabulary.
+    fn decode(&self, ids: &[usize]) -> String {
+        let vocabulary = self.vocabulary();
+        let tokens: Vec<&str> = ids
+            .iter()
+            .filter(|id| !Vocabulary::is_special(**id) || **id == Vocabulary::UNK)
+            .map(|id| {
+                vocabulary
+                    .token(*id)
+                    .expect("Token id outside the vocabulary")
+            })
+            .collect();
+
+        self.join(&tokens)
+    }
+
+    /// One-hot encodes a sequence into a `ids.len() × vocab_size` matrix.
+    fn one_hot(&self, ids: &[usize]) -> Matrix {
+        let vocab_size = self.vocab_size();
+        Matrix::new(ids.iter().map(|id| one_hot_row(*id, vocab_size)).collect())
+    }
+
+    /// One-hot encodes a batch of sequences as one `batch × vocab_size` matrix per timestep,
+    /// the layout used by [`crate::recurrent::Recurrent`]. Shorter sequences are padded with
+    /// `<pad>`.
+    fn one_hot_batch(&self, sequences: &[Vec<usize>]) -> Vec<Matrix> {
+        let vocab_size = self.vocab_size();
+        let length = sequences.iter().map(Vec::len).max().unwrap_or(0);
+
+        (0..length)
+            .map(|t| {
+                Matrix::new(
+                    sequences
+                        .iter()
+                        .map(|ids| {
+                            one_hot_row(ids.get(t).copied().unwrap_or(Vocabulary::PAD), vocab_size)
+                        })
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// The vocabulary file contents.
+    fn to_text(&self) -> String {
+        self.vocabulary().to_text()
+    }
+
+    /// Writes the vocabulary file to `path`.
+    fn save(&self, path: impl AsRef<Path>) -> Result<(), String>
+    where
+        Self: Sized,
+    {
+        let path = path.as_ref();
+        fs::write(path, self.to_text())
+            .map_err(|error| format!("Cannot write {}: {}", path.display(), error))
+    }
+}
+
+/// Maps each distinct character of a corpus to a token id.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CharTokenizer {
+    vocabulary: Vocabulary,
+}
+
+impl CharTokenizer {
+    /// Builds the vocabulary from the characters of `corpus`, in sorted order.
+    pub fn from_corpus(corpus: &str) -> Self {
+        let mut chars: Vec<char> = corpus.chars().collect();
+        chars.sort_unstable();
+        chars.dedup();
+
+        let mut vocabulary = Vocabulary::new();
+        for c in chars {
+            vocabulary.insert(&c.to_string());
+        }
+
+        Self { vocabulary }
+    }
+
+    /// Reads a vocabulary file saved by [`Tokenizer::save`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        Self::parse(&read(path.as_ref())?)
+    }
+
+    /// Parses vocabulary file contents. Every regular token must be a single character.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let vocabulary = Vocabulary::parse(text)?;
+        if let Some(token) = vocabulary.tokens()[Vocabulary::SPECIAL_TOKENS.len()..]
+            .iter()
+            .find(|token| token.chars().count() != 1)
+        {
+            return Err(format!("Expected single characters, found {:?}", token));
+        }
+
+        Ok(Self { vocabulary })
+    }
+}
+
+impl Tokenizer for CharTokenizer {
+    fn vocabulary(&self) -> &Vocabulary {
+        &self.vocabulary
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        text.chars().map(|c| c.to_string()).collect()
+    }
+
+    fn join(&self, tokens: &[&str]) -> String {
+        tokens.concat()
+    }
+}
+
+/// Maps each whitespace-separated word of a corpus to a token id.
+///
+/// Decoding joins words with single spaces, so text only round-trips when its words are
+/// separated by exactly one space.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordTokenizer {
+    vocabulary: Vocabulary,
+}
+
+impl WordTokenizer {
+    /// Builds the vocabulary from the words of `corpus` that occur at least `min_count` times,
+    /// most frequent first.
+    pub fn from_corpus(corpus: &str, min_count: usize) -> Self {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for word in corpus.split_whitespace() {
+            *counts.entry(word).or_default() += 1;
+        }
+
+        let mut words: Vec<(&str, usize)> = counts
+            .into_iter()
+            .filter(|(_, count)| *count >= min_count)
+            .collect();
+        words.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+        let mut vocabulary = Vocabulary::new();
+        for (word, _) in words {
+            vocabulary.insert(word);
+        }
+
+        Self { vocabulary }
+    }
+
+    /// Reads a vocabulary file saved by [`Tokenizer::save`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        Self::parse(&read(path.as_ref())?)
+    }
+
+    /// Parses vocabulary file contents. Tokens must not contain whitespace.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let vocabulary = Vocabulary::parse(text)?;
+        if let Some(token) = vocabulary
+            .tokens()
+            .iter()
+            .find(|token| token.chars().any(char::is_whitespace))
+        {
+            return Err(format!(
+                "Expected words without whitespace, found {:?}",
+                token
+            ));
+        }
+
+        Ok(Self { vocabulary })
+    }
+}
+
+impl Tokenizer for WordTokenizer {
+    fn vocabulary(&self) -> &Vocabulary {
+        &self.vocabulary
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        text.split_whitespace().map(str::to_string).collect()
+    }
+
+    fn join(&self, tokens: &[&str]) -> String {
+        tokens.join(" ")
+    }
+}
+
+/// Byte-pair encoding over characters.
+///
+/// Text is first split into chunks that start at each whitespace character, so `"the cat"`
+/// becomes `"the"` and `" cat"` and merges never cross a word boundary. Training starts with
+/// one token per character and repeatedly merges the most frequent adjacent pair of tokens
+/// (ties broken alphabetically) into a new token. Encoding replays the merges in the order
+/// they were learned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BpeTokenizer {
+    vocabulary: Vocabulary,
+    merges: Vec<(String, String)>,
+    ranks: HashMap<(String, String), usize>,
+}
+
+impl BpeTokenizer {
+    /// Learns merges from `corpus` until the vocabulary holds `vocab_size` tokens or no pair
+    /// occurs more than once.
+    pub fn train(corpus: &str, vocab_size: usize) -> Self {
+        let mut chars: Vec<char> = corpus.chars().collect();
+        chars.sort_unstable();
+        chars.dedup();
+
+        let mut vocabulary = Vocabulary::new();
+        for c in chars {
+            vocabulary.insert(&c.to_string());
+        }
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for chunk in chunks(corpus) {
+            *counts.entry(chunk).or_default() += 1;
+        }
+        let mut words: Vec<(Vec<String>, usize)> = counts
+            .into_iter()
+            .map(|(chunk, count)| (chunk.chars().map(|c| c.to_string()).collect(), count))
+            .collect();
+
+        let mut merges = Vec::new();
+        while vocabulary.len() < vocab_size {
+            let mut pairs: HashMap<(&str, &str), usize> = HashMap::new();
+            for (word, count) in &words {
+                for pair in word.windows(2) {
+                    *pairs.entry((&pair[0], &pair[1])).or_default() += count;
+                }
+            }
+
+            let Some(((left, right), count)) = pairs
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+            else {
+                break;
+            };
+            if count < 2 {
+                break;
+            }
+
+            let pair = (left.to_string(), right.to_string());
+            for (word, _) in &mut words {
+                *word = merge(word, &pair);
+            }
+            vocabulary.insert(&format!("{}{}", pair.0, pair.1));
+            merges.push(pair);
+        }
+
+        Self::from_parts(vocabulary, merges)
+    }
+
+    /// The learned merges, in the order they are applied.
+    pub fn merges(&self) -> &[(String, String)] {
+        &self.merges
+    }
+
+    /// Reads a vocabulary file saved by [`Tokenizer::save`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        Self::parse(&read(path.as_ref())?)
+    }
+
+    /// Parses vocabulary file contents: single characters, then one merge per line.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut vocabulary = Vocabulary::new();
+        let mut merges = Vec::new();
+
+        for (line_idx, parts) in parse_lines(text)? {
+            match parts.as_slice() {
+                [token]
+                    if token.chars().count() == 1
+                        || line_idx < Vocabulary::SPECIAL_TOKENS.len() =>
+                {
+                    check_insert(&mut vocabulary, line_idx, token)?
+                }
+                [left, right] => {
+                    if vocabulary.id(left).is_none() || vocabulary.id(right).is_none() {
+                        return Err(format!("Line {}: merge of unknown tokens", line_idx + 1));
+                    }
+                    vocabulary.insert(&format!("{}{}", left, right));
+                    merges.push((left.clone(), right.clone()));
+                }
+                _ => {
+                    return Err(format!(
+                        "Line {}: expected a character or a merge",
+                        line_idx + 1
+                    ))
+                }
+            }
+        }
+
+        Ok(Self::from_parts(vocabulary, merges))
+    }
+
+    fn from_parts(vocabulary: Vocabulary, merges: Vec<(String, String)>) -> Self {
+        let ranks = merges
+            .iter()
+            .enumerate()
+            .map(|(rank, pair)| (pair.clone(), rank))
+            .collect();
+
+        Self {
+            vocabulary,
+            merges,
+            ranks,
+        }
+    }
+
+    /// Applies the merges to one chunk, lowest rank first.
+    fn encode_chunk(&self, chunk: &str) -> Vec<String> {
+        let mut tokens: Vec<String> = chunk.chars().map(|c| c.to_string()).collect();
+
+        loop {
+            let best = tokens
+                .windows(2)
+                .filter_map(|pair| {
+                    let pair = (pair[0].clone(), pair[1].clone());
+                    self.ranks.get(&pair).map(|rank| (*rank, pair))
+                })
+                .min_by_key(|(rank, _)| *rank);
+
+            match best {
+                Some((_, pair)) => tokens = merge(&tokens, &pair),
+                None => return tokens,
+            }
+        }
+    }
+}
+
+impl Tokenizer for BpeTokenizer {
+    fn vocabulary(&self) -> &Vocabulary {
+        &self.vocabulary
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        chunks(text)
+            .into_iter()
+            .flat_map(|chunk| self.encode_chunk(chunk))
+            .collect()
+    }
+
+    fn join(&self, tokens: &[&str]) -> String {
+        tokens.concat()
+    }
+
+    fn to_text(&self) -> String {
+        let mut text: String = self
+            .vocabulary
+            .tokens()
+            .iter()
+            .enumerate()
+            .filter(|(id, token)| Vocabulary::is_special(*id) || token.chars().count() == 1)
+            .map(|(_, token)| escape(token) + "\n")
+            .collect();
+        for (left, right) in &self.merges {
+            text += &format!("{} {}\n", escape(left), escape(right));
+        }
+
+        text
+    }
+}
+
+/// Splits `text` before every whitespace character.
+fn chunks(text: &str) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    for (index, c) in text.char_indices() {
+        if c.is_whitespace() && index > start {
+            chunks.push(&text[start..index]);
+            start = index;
+        }
+    }
+    if start < text.len() {
+        chunks.push(&text[start..]);
+    }
+
+    chunks
+}
+
+/// Replaces every adjacent occurrence of `pair` in `tokens` with the merged token.
+fn merge(tokens: &[String], pair: &(String, String)) -> Vec<String> {
+    let mut merged = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+
+    while i < tokens.len() {
+        if i + 1 < tokens.len() && tokens[i] == pair.0 && tokens[i + 1] == pair.1 {
+            merged.push(format!("{}{}", pair.0, pair.1));
+            i += 2;
+        } else {
+            merged.push(tokens[i].clone());
+            i += 1;
+        }
+    }
+
+    merged
+}
+
+fn one_hot_row(id: usize, vocab_size: usize) -> Vec<f64> {
+    if id >= vocab_size {
+        panic!("Token id outside the vocabulary");
+    }
+
+    let mut row = vec![0.0; vocab_size];
+    row[id] = 1.0;
+    row
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|error| format!("Cannot read {}: {}", path.display(), error))
+}
+
+fn escape(token: &str) -> String {
+    let mut escaped = String::with_capacity(token.len());
+    for c in token.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ' ' => escaped.push_str("\\s"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn unescape(text: &str, line_idx: usize) -> Result<String, String> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('\\') => unescaped.push('\\'),
+            Some('s') => unescaped.push(' '),
+            Some('t') => unescaped.push('\t'),
+            Some('r') => unescaped.push('\r'),
+            Some('n') => unescaped.push('\n'),
+            _ => return Err(format!("Line {}: invalid escape", line_idx + 1)),
+        }
+    }
+
+    Ok(unescaped)
+}
+
+/// Splits every non-empty line into its unescaped, space-separated parts.
+fn parse_lines(text: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let mut lines = Vec::new();
+
+    for (line_idx, line) in text.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+
+        let parts = line
+            .split(' ')
+            .map(|part| unescape(part, line_idx))
+            .collect::<Result<Vec<_>, _>>()?;
+        lines.push((line_idx, parts));
+    }
+
+    Ok(lines)
+}
+
+/// Inserts `token`, which must be new unless it is a special token at its own id.
+fn check_insert(vocabulary: &mut Vocabulary, line_idx: usize, token: &str) -> Result<(), String> {
+    match vocabulary.id(token) {
+        Some(id) if Vocabulary::is_special(id) && id == line_idx => Ok(()),
+        Some(_) => Err(format!(
+            "Line {}: duplicate token {:?}",
+            line_idx + 1,
+            token
+        )),
+        None => {
+            vocabulary.insert(token);
+            Ok(())
+        }
+    }
+}
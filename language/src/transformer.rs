@@ -0,0 +1,753 @@
+//! Decoder-Only Transformer
+//!
+//! A tiny GPT-style language model built from [`MultiHeadAttention`]. Each token is looked up
+//! in an [`Embedding`] table, a fixed sinusoidal [`positional_encoding`] is added so the
+//! model knows where each token sits, and the sequence goes through a stack of blocks:
+//!
+//! ```text
+//! x = x + Attention(LayerNorm(x))     tokens exchange information
+//! x = x + FeedForward(LayerNorm(x))   each position is transformed on its own
+//! ```
+//!
+//! The residual connections (`x + ...`) let gradients flow straight through the stack, and
+//! [`LayerNorm`] keeps the activations of every position at zero mean and unit variance. A
+//! final LayerNorm and a linear readout produce one logit per token of the vocabulary at
+//! every position. Thanks to the causal mask, position `i` predicts token `i + 1` from tokens
+//! `0..=i` only, so one forward pass scores every prefix of a sequence at once.
+//!
+//! # Training
+//!
+//! The loss is the mean cross-entropy of predicting every token from the ones before it.
+//! [`Transformer::train_step`] averages the gradients over a batch of sequences, clips them
+//! to a norm of 5 and takes a gradient descent step; the embedding gets a sparse update.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! # use aspirina_language::transformer::{Transformer, TransformerConfig};
+//! let config = TransformerConfig::new(10, 8, 2).with_context_length(6);
+//! let mut model = Transformer::new(config, 42);
+//!
+//! let sequence = vec![4, 5, 6, 7, 4, 5];
+//! let before = model.loss(&sequence);
+//! for _ in 0..50 {
+//!     model.train_step(&[sequence.clone()], 0.1);
+//! }
+//! assert!(model.loss(&sequence) < before);
+//!
+//! let logits = model.forward(&[4, 5]);
+//! assert_eq!(logits.data.len(), 2);
+//! assert_eq!(logits.data[0].len(), 10);
+//! ```
+
+use crate::attention::{AttentionCache, MultiHeadAttention};
+use crate::char_model::Sampling;
+use crate::embedding::{Embedding, SparseGradient};
+use crate::ops::{clip_factor, map, softmax, squared_norm, uniform, zeros, zip_with, Linear};
+use crate::tokenizer::Vocabulary;
+use aspirina_core::matrix::Matrix;
+use aspirina_core::random::Rng;
+
+const LAYER_NORM_EPSILON: f64 = 1e-5;
+const MAX_GRADIENT_NORM: f64 = 5.0;
+
+/// Sinusoidal position encodings as a `[length, model_size]` matrix.
+///
+/// Column pairs `2i, 2i + 1` hold `sin(p / 10000^(2i/d))` and `cos(p / 10000^(2i/d))` for
+/// position `p`, so every position gets a distinct pattern and nearby positions get similar
+/// ones.
+pub fn positional_encoding(length: usize, model_size: usize) -> Matrix {
+    Matrix::new(
+        (0..length)
+            .map(|position| {
+                (0..model_size)
+                    .map(|column| {
+                        let frequency =
+                            10000f64.powf(-((column / 2 * 2) as f64) / model_size as f64);
+                        let angle = position as f64 * frequency;
+                        if column % 2 == 0 {
+                            angle.sin()
+                        } else {
+                            angle.cos()
+                        }
+                    })
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+/// Normalizes every row to zero mean and unit variance, then scales and shifts it.
+#[derive(Debug, Clone)]
+pub struct LayerNorm {
+    /// Scale, shape `1 × size`, starts at one
+    pub gain: Matrix,
+    /// Shift, shape `1 × size`, starts at zero
+    pub bias: Matrix,
+}
+
+#[derive(Debug, Clone)]
+struct LayerNormCache {
+    normalized: Matrix,
+    inverse_deviations: Vec<f64>,
+}
+
+impl LayerNorm {
+    /// Creates the identity normalization over `size` features.
+    pub fn new(size: usize) -> Self {
+        Self {
+            gain: Matrix::new(vec![vec![1.0; size]]),
+            bias: zeros(1, size),
+        }
+    }
+
+    /// Normalizes every row of `input`.
+    pub fn forward(&self, input: &Matrix) -> Matrix {
+        self.forward_cached(input).0
+    }
+
+    fn forward_cached(&self, input: &Matrix) -> (Matrix, LayerNormCache) {
+        let mut normalized = Vec::with_capacity(input.data.len());
+        let mut inverse_deviations = Vec::with_capacity(input.data.len());
+
+        for row in &input.data {
+            let n = row.len() as f64;
+            let mean = row.iter().sum::<f64>() / n;
+            let variance = row.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+            let inverse = 1.0 / (variance + LAYER_NORM_EPSILON).sqrt();
+
+            normalized.push(row.iter().map(|x| (x - mean) * inverse).collect::<Vec<_>>());
+            inverse_deviations.push(inverse);
+        }
+
+        let normalized = Matrix::new(normalized);
+        let output = Matrix::new(
+            normalized
+                .data
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .zip(self.gain.data[0].iter().zip(self.bias.data[0].iter()))
+                        .map(|(x, (g, b))| x * g + b)
+                        .collect()
+                })
+                .collect(),
+        );
+
+        (
+            output,
+            LayerNormCache {
+                normalized,
+                inverse_deviations,
+            },
+        )
+    }
+
+    fn backward(&self, cache: &LayerNormCache, output_gradient: &Matrix) -> (Matrix, Vec<Matrix>) {
+        let size = self.gain.data[0].len();
+        let mut gain = vec![0.0; size];
+        let mut bias = vec![0.0; size];
+        let mut input_gradient = Vec::with_capacity(output_gradient.data.len());
+
+        for ((dy, x), inverse) in output_gradient
+            .data
+            .iter()
+            .zip(cache.normalized.data.iter())
+            .zip(cache.inverse_deviations.iter())
+        {
+            for j in 0..size {
+                gain[j] += dy[j] * x[j];
+                bias[j] += dy[j];
+            }
+
+            let dx: Vec<f64> = (0..size).map(|j| dy[j] * self.gain.data[0][j]).collect();
+            let n = size as f64;
+            let sum: f64 = dx.iter().sum();
+            let dot: f64 = dx.iter().zip(x.iter()).map(|(d, x)| d * x).sum();
+            input_gradient.push(
+                dx.iter()
+                    .zip(x.iter())
+                    .map(|(d, x)| inverse / n * (n * d - sum - x * dot))
+                    .collect(),
+            );
+        }
+
+        (
+            Matrix::new(input_gradient),
+            vec![Matrix::new(vec![gain]), Matrix::new(vec![bias])],
+        )
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        vec![self.gain.clone(), self.bias.clone()]
+    }
+
+    fn set_parameters(&mut self, parameters: &mut impl Iterator<Item = Matrix>) {
+        self.gain = parameters.next().unwrap();
+        self.bias = parameters.next().unwrap();
+    }
+}
+
+/// Two linear layers with a ReLU in between, applied to every position separately.
+#[derive(Debug, Clone)]
+struct FeedForward {
+    hidden: Linear,
+    output: Linear,
+}
+
+#[derive(Debug, Clone)]
+struct FeedForwardCache {
+    input: Matrix,
+    pre_activation: Matrix,
+    activation: Matrix,
+}
+
+impl FeedForward {
+    fn new(model_size: usize, hidden_size: usize, rng: &mut Rng) -> Self {
+        Self {
+            hidden: Linear::new(model_size, hidden_size, rng),
+            output: Linear::new(hidden_size, model_size, rng),
+        }
+    }
+
+    fn forward_cached(&self, input: &Matrix) -> (Matrix, FeedForwardCache) {
+        let pre_activation = self.hidden.forward(input);
+        let activation = map(&pre_activation, |x| x.max(0.0));
+        let output = self.output.forward(&activation);
+
+        (
+            output,
+            FeedForwardCache {
+                input: input.clone(),
+                pre_activation,
+                activation,
+            },
+        )
+    }
+
+    fn backward(
+        &self,
+        cache: &FeedForwardCache,
+        output_gradient: &Matrix,
+    ) -> (Matrix, Vec<Matrix>) {
+        let (activation_gradient, mut parameters) =
+            self.output.backward(&cache.activation, output_gradient);
+        let pre_activation_gradient =
+            zip_with(&activation_gradient, &cache.pre_activation, |d, x| {
+                if x > 0.0 {
+                    d
+                } else {
+                    0.0
+                }
+            });
+        let (input_gradient, hidden_parameters) =
+            self.hidden.backward(&cache.input, &pre_activation_gradient);
+
+        parameters.splice(0..0, hidden_parameters);
+        (input_gradient, parameters)
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        let mut parameters = self.hidden.parameters();
+        parameters.extend(self.output.parameters());
+        parameters
+    }
+
+    fn set_parameters(&mut self, parameters: &mut impl Iterator<Item = Matrix>) {
+        self.hidden.set_parameters(parameters);
+        self.output.set_parameters(parameters);
+    }
+}
+
+/// One pre-norm Transformer block: attention and feed-forward sublayers with residuals.
+#[derive(Debug, Clone)]
+pub struct TransformerBlock {
+    attention_norm: LayerNorm,
+    attention: MultiHeadAttention,
+    feed_forward_norm: LayerNorm,
+    feed_forward: FeedForward,
+}
+
+#[derive(Debug, Clone)]
+struct BlockCache {
+    attention_norm: LayerNormCache,
+    attention: AttentionCache,
+    feed_forward_norm: LayerNormCache,
+    feed_forward: FeedForwardCache,
+}
+
+impl TransformerBlock {
+    /// Creates a block over `model_size` features.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `model_size` is not divisible by `heads`.
+    pub fn new(model_size: usize, heads: usize, hidden_size: usize, seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        Self {
+            attention_norm: LayerNorm::new(model_size),
+            attention: MultiHeadAttention::new(model_size, heads, rng.next_u64()),
+            feed_forward_norm: LayerNorm::new(model_size),
+            feed_forward: FeedForward::new(model_size, hidden_size, &mut rng),
+        }
+    }
+
+    /// The block's attention sublayer.
+    pub fn attention(&self) -> &MultiHeadAttention {
+        &self.attention
+    }
+
+    /// Transforms the `[length, model_size]` sequence `input`.
+    pub fn forward(&self, input: &Matrix) -> Matrix {
+        self.forward_cached(input).0
+    }
+
+    fn forward_cached(&self, input: &Matrix) -> (Matrix, BlockCache) {
+        let (normalized, attention_norm) = self.attention_norm.forward_cached(input);
+        let (attended, attention) = self.attention.forward_cached(&normalized);
+        let middle = input.clone() + attended;
+
+        let (normalized, feed_forward_norm) = self.feed_forward_norm.forward_cached(&middle);
+        let (transformed, feed_forward) = self.feed_forward.forward_cached(&normalized);
+
+        (
+            middle + transformed,
+            BlockCache {
+                attention_norm,
+                attention,
+                feed_forward_norm,
+                feed_forward,
+            },
+        )
+    }
+
+    fn backward(&self, cache: &BlockCache, output_gradient: &Matrix) -> (Matrix, Vec<Matrix>) {
+        let (normalized_gradient, feed_forward) = self
+            .feed_forward
+            .backward(&cache.feed_forward, output_gradient);
+        let (middle_gradient, feed_forward_norm) = self
+            .feed_forward_norm
+            .backward(&cache.feed_forward_norm, &normalized_gradient);
+        let middle_gradient = output_gradient.clone() + middle_gradient;
+
+        let (normalized_gradient, attention) =
+            self.attention.backward(&cache.attention, &middle_gradient);
+        let (input_gradient, attention_norm) = self
+            .attention_norm
+            .backward(&cache.attention_norm, &normalized_gradient);
+
+        let mut parameters = attention_norm;
+        parameters.extend(attention);
+        parameters.extend(feed_forward_norm);
+        parameters.extend(feed_forward);
+
+        (middle_gradient + input_gradient, parameters)
+    }
+
+    /// Copies of every parameter: attention norm, attention, feed-forward norm, feed-forward.
+    pub fn parameters(&self) -> Vec<Matrix> {
+        let mut parameters = self.attention_norm.parameters();
+        parameters.extend(self.attention.parameters());
+        parameters.extend(self.feed_forward_norm.parameters());
+        parameters.extend(self.feed_forward.parameters());
+        parameters
+    }
+
+    fn set_parameters(&mut self, parameters: &mut impl Iterator<Item = Matrix>) {
+        self.attention_norm.set_parameters(parameters);
+        self.attention.set_parameters(parameters);
+        self.feed_forward_norm.set_parameters(parameters);
+        self.feed_forward.set_parameters(parameters);
+    }
+}
+
+/// Sizes of a [`Transformer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransformerConfig {
+    /// Number of token ids
+    pub vocab_size: usize,
+    /// Features per position
+    pub model_size: usize,
+    /// Attention heads per block
+    pub heads: usize,
+    /// Number of blocks
+    pub layers: usize,
+    /// Hidden units of the feed-forward sublayers
+    pub hidden_size: usize,
+    /// Longest sequence the model accepts
+    pub context_length: usize,
+}
+
+impl TransformerConfig {
+    /// One block, a feed-forward size of `4 × model_size` and a context of 32 tokens.
+    pub fn new(vocab_size: usize, model_size: usize, heads: usize) -> Self {
+        Self {
+            vocab_size,
+            model_size,
+            heads,
+            layers: 1,
+            hidden_size: 4 * model_size,
+            context_length: 32,
+        }
+    }
+
+    /// Sets the number of blocks.
+    pub fn with_layers(mut self, layers: usize) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Sets the feed-forward hidden size.
+    pub fn with_hidden_size(mut self, hidden_size: usize) -> Self {
+        self.hidden_size = hidden_size;
+        self
+    }
+
+    /// Sets the longest sequence the model accepts.
+    pub fn with_context_length(mut self, context_length: usize) -> Self {
+        self.context_length = context_length;
+        self
+    }
+}
+
+/// Decoder-only Transformer language model.
+#[derive(Debug, Clone)]
+pub struct Transformer {
+    config: TransformerConfig,
+    /// Token vectors, `vocab_size × model_size`
+    pub embedding: Embedding,
+    /// The stack of blocks
+    pub blocks: Vec<TransformerBlock>,
+    /// Normalization before the readout
+    pub final_norm: LayerNorm,
+    readout: Linear,
+    positions: Matrix,
+}
+
+#[derive(Debug, Clone)]
+struct ModelCache {
+    tokens: Vec<usize>,
+    blocks: Vec<BlockCache>,
+    final_norm: LayerNormCache,
+    normalized: Matrix,
+}
+
+impl Transformer {
+    /// Creates a model with weights initialized from `seed`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `model_size` is not divisible by `heads`.
+    pub fn new(config: TransformerConfig, seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        let embedding = Embedding::from_weights(uniform(
+            config.vocab_size,
+            config.model_size,
+            1.0 / (config.model_size as f64).sqrt(),
+            &mut rng,
+        ));
+        let blocks = (0..config.layers)
+            .map(|_| {
+                TransformerBlock::new(
+                    config.model_size,
+                    config.heads,
+                    config.hidden_size,
+                    rng.next_u64(),
+                )
+            })
+            .collect();
+
+        Self {
+            config,
+            embedding,
+            blocks,
+            final_norm: LayerNorm::new(config.model_size),
+            readout: Linear::new(config.model_size, config.vocab_size, &mut rng),
+            positions: positional_encoding(config.context_length, config.model_size),
+        }
+    }
+
+    /// The model's sizes.
+    pub fn config(&self) -> &TransformerConfig {
+        &self.config
+    }
+
+    /// Logits for the token after every prefix of `tokens`, shape `[length, vocab_size]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tokens` is empty, longer than the context length, or holds an id outside
+    /// the vocabulary.
+    pub fn forward(&self, tokens: &[usize]) -> Matrix {
+        self.forward_cached(tokens).0
+    }
+
+    /// Mean cross-entropy of predicting `tokens[1..]`, each from the tokens before it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tokens` has fewer than two or more than `context_length + 1` tokens.
+    pub fn loss(&self, tokens: &[usize]) -> f64 {
+        let (inputs, targets) = split_targets(tokens);
+        let logits = self.forward(inputs);
+
+        logits
+            .data
+            .iter()
+            .zip(targets.iter())
+            .map(|(row, target)| -softmax(row)[*target].ln())
+            .sum::<f64>()
+            / targets.len() as f64
+    }
+
+    /// Perplexity of `tokens`: the exponential of [`Transformer::loss`].
+    pub fn perplexity(&self, tokens: &[usize]) -> f64 {
+        self.loss(tokens).exp()
+    }
+
+    /// Copies of every parameter: the embedding, each block, the final norm, then the readout
+    /// weights and bias.
+    pub fn parameters(&self) -> Vec<Matrix> {
+        let mut parameters = vec![self.embedding.weights.clone()];
+        for block in &self.blocks {
+            parameters.extend(block.parameters());
+        }
+        parameters.extend(self.final_norm.parameters());
+        parameters.extend(self.readout.parameters());
+        parameters
+    }
+
+    /// Replaces every parameter, in the order of [`parameters`](Self::parameters).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of parameters does not match the model.
+    pub fn set_parameters(&mut self, parameters: Vec<Matrix>) {
+        if parameters.len() != self.parameters().len() {
+            panic!("Parameter count does not match model");
+        }
+
+        let mut parameters = parameters.into_iter();
+        self.embedding.weights = parameters.next().unwrap();
+        for block in &mut self.blocks {
+            block.set_parameters(&mut parameters);
+        }
+        self.final_norm.set_parameters(&mut parameters);
+        self.readout.set_parameters(&mut parameters);
+    }
+
+    /// Gradient of [`Transformer::loss`] for every parameter, in the order of
+    /// [`parameters`](Self::parameters). The embedding gradient is returned dense.
+    pub fn gradients(&self, tokens: &[usize]) -> Vec<Matrix> {
+        let (inputs, targets) = split_targets(tokens);
+        let (_, embedding, dense) = self.backward(inputs, targets, 1.0 / targets.len() as f64);
+
+        let mut embedding_dense = zeros(self.config.vocab_size, self.config.model_size);
+        for (id, row) in embedding.iter() {
+            embedding_dense.data[id] = row.to_vec();
+        }
+
+        let mut gradients = vec![embedding_dense];
+        gradients.extend(dense);
+        gradients
+    }
+
+    /// Takes one gradient descent step on the mean loss over `sequences` and returns that
+    /// loss, measured before the update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sequences` is empty or a sequence is too short or too long.
+    pub fn train_step(&mut self, sequences: &[Vec<usize>], learning_rate: f64) -> f64 {
+        if sequences.is_empty() {
+            panic!("Expected at least one sequence");
+        }
+
+        let count: usize = sequences.iter().map(|s| s.len().saturating_sub(1)).sum();
+        let mut total_loss = 0.0;
+        let mut embedding: Option<SparseGradient> = None;
+        let mut dense: Option<Vec<Matrix>> = None;
+
+        for sequence in sequences {
+            let (inputs, targets) = split_targets(sequence);
+            let (loss, sequence_embedding, sequence_dense) =
+                self.backward(inputs, targets, 1.0 / count as f64);
+            total_loss += loss;
+
+            embedding = Some(match embedding {
+                Some(sum) => sum + sequence_embedding,
+                None => sequence_embedding,
+            });
+            dense = Some(match dense {
+                Some(sum) => sum
+                    .into_iter()
+                    .zip(sequence_dense)
+                    .map(|(a, b)| a + b)
+                    .collect(),
+                None => sequence_dense,
+            });
+        }
+
+        let embedding = embedding.unwrap();
+        let dense = dense.unwrap();
+        let factor = clip_factor(
+            squared_norm(&dense) + embedding.squared_norm(),
+            MAX_GRADIENT_NORM,
+        );
+        let step = learning_rate * factor;
+
+        self.embedding.apply(&embedding, step);
+        let mut parameters = self.parameters();
+        let updated: Vec<Matrix> = parameters
+            .drain(1..)
+            .zip(dense)
+            .map(|(parameter, gradient)| parameter - gradient.scale(step))
+            .collect();
+        parameters.extend(updated);
+        self.set_parameters(parameters);
+
+        total_loss / count as f64
+    }
+
+    /// Makes one pass over a long token sequence, cut into consecutive chunks of
+    /// `context_length + 1` tokens and trained `batch_size` chunks at a time. Returns the
+    /// mean loss per chunk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero or `tokens` has fewer than two tokens.
+    pub fn train_epoch(&mut self, tokens: &[usize], batch_size: usize, learning_rate: f64) -> f64 {
+        if batch_size == 0 {
+            panic!("Batch size must be greater than zero");
+        }
+
+        if tokens.len() < 2 {
+            panic!("Expected at least two tokens");
+        }
+
+        let step = self.config.context_length;
+        let chunks: Vec<Vec<usize>> = (0..tokens.len() - 1)
+            .step_by(step)
+            .map(|start| tokens[start..(start + step + 1).min(tokens.len())].to_vec())
+            .collect();
+
+        let mut total = 0.0;
+        for batch in chunks.chunks(batch_size) {
+            total += self.train_step(batch, learning_rate) * batch.len() as f64;
+        }
+
+        total / chunks.len() as f64
+    }
+
+    /// Continues `prompt` with `length` sampled tokens and returns the prompt followed by the
+    /// generated ids. Only the last `context_length` tokens are used as context, and special
+    /// tokens are never sampled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prompt` is empty.
+    pub fn generate(
+        &self,
+        prompt: &[usize],
+        length: usize,
+        sampling: &Sampling,
+        seed: u64,
+    ) -> Vec<usize> {
+        if prompt.is_empty() {
+            panic!("Prompt must not be empty");
+        }
+
+        let mut rng = Rng::new(seed);
+        let mut tokens = prompt.to_vec();
+
+        for _ in 0..length {
+            let start = tokens.len().saturating_sub(self.config.context_length);
+            let logits = self.forward(&tokens[start..]);
+            let mut last = logits.data.last().unwrap().clone();
+            for logit in last.iter_mut().take(Vocabulary::SPECIAL_TOKENS.len()) {
+                *logit = f64::NEG_INFINITY;
+            }
+            tokens.push(sampling.sample(&last, &mut rng));
+        }
+
+        tokens
+    }
+
+    fn forward_cached(&self, tokens: &[usize]) -> (Matrix, ModelCache) {
+        if tokens.is_empty() || tokens.len() > self.config.context_length {
+            panic!("Sequence length must be between 1 and the context length");
+        }
+
+        let mut hidden = self.embedding.forward(tokens)
+            + Matrix::new(self.positions.data[..tokens.len()].to_vec());
+        let mut blocks = Vec::with_capacity(self.blocks.len());
+        for block in &self.blocks {
+            let (output, cache) = block.forward_cached(&hidden);
+            hidden = output;
+            blocks.push(cache);
+        }
+
+        let (normalized, final_norm) = self.final_norm.forward_cached(&hidden);
+        let logits = self.readout.forward(&normalized);
+
+        (
+            logits,
+            ModelCache {
+                tokens: tokens.to_vec(),
+                blocks,
+                final_norm,
+                normalized,
+            },
+        )
+    }
+
+    /// Forward and backward pass over one sequence, with the loss gradient scaled by
+    /// `scale`. Returns the summed loss, the embedding gradient and the other gradients.
+    fn backward(
+        &self,
+        inputs: &[usize],
+        targets: &[usize],
+        scale: f64,
+    ) -> (f64, SparseGradient, Vec<Matrix>) {
+        let (logits, cache) = self.forward_cached(inputs);
+        let mut loss = 0.0;
+        let mut logits_gradient = Vec::with_capacity(targets.len());
+
+        for (row, target) in logits.data.iter().zip(targets.iter()) {
+            let mut probabilities = softmax(row);
+            loss -= probabilities[*target].ln();
+            probabilities[*target] -= 1.0;
+            logits_gradient.push(probabilities.iter().map(|p| p * scale).collect());
+        }
+
+        let (normalized_gradient, readout) = self
+            .readout
+            .backward(&cache.normalized, &Matrix::new(logits_gradient));
+        let (mut hidden_gradient, final_norm) = self
+            .final_norm
+            .backward(&cache.final_norm, &normalized_gradient);
+
+        let mut block_gradients = Vec::with_capacity(self.blocks.len());
+        for (block, block_cache) in self.blocks.iter().zip(cache.blocks.iter()).rev() {
+            let (input_gradient, parameters) = block.backward(block_cache, &hidden_gradient);
+            hidden_gradient = input_gradient;
+            block_gradients.push(parameters);
+        }
+
+        let embedding = self.embedding.gradient(&cache.tokens, &hidden_gradient);
+        let mut dense: Vec<Matrix> = block_gradients.into_iter().rev().flatten().collect();
+        dense.extend(final_norm);
+        dense.extend(readout);
+
+        (loss, embedding, dense)
+    }
+}
+
+/// Splits a sequence into the model inputs and the next-token targets.
+fn split_targets(tokens: &[usize]) -> (&[usize], &[usize]) {
+    if tokens.len() < 2 {
+        panic!("Expected at least two tokens");
+    }
+
+    (&tokens[..tokens.len() - 1], &tokens[1..])
+}
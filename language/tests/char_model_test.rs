@@ -0,0 +1,161 @@
+use aspirina_core::random::Rng;
+use aspirina_language::char_model::{CharLanguageModel, Sampling, CORPUS};
+use aspirina_language::recurrent::{GruCell, RnnCell};
+use aspirina_language::tokenizer::{CharTokenizer, Tokenizer};
+
+fn model() -> CharLanguageModel<GruCell> {
+    CharLanguageModel::new(
+        CharTokenizer::from_corpus(CORPUS),
+        GruCell::new(12, 32, 1),
+        2,
+    )
+}
+
+#[test]
+fn untrained_loss_is_close_to_uniform() {
+    let mut model = model();
+    let uniform = (model.tokenizer().vocab_size() as f64).ln();
+
+    let loss = model.loss(CORPUS).unwrap();
+
+    assert!((loss - uniform).abs() < 0.5, "loss {}", loss);
+}
+
+#[test]
+fn training_on_bundled_corpus_lowers_loss() {
+    let mut model = model();
+    let initial = model.loss(CORPUS).unwrap();
+
+    let mut epoch_loss = f64::INFINITY;
+    for _ in 0..20 {
+        epoch_loss = model.train_epoch(CORPUS, 8, 12, 1.0).unwrap();
+    }
+    let trained = model.loss(CORPUS).unwrap();
+
+    assert!(epoch_loss < 1.6, "epoch loss {}", epoch_loss);
+    assert!(trained < initial / 2.0, "{} -> {}", initial, trained);
+}
+
+#[test]
+fn training_works_with_a_plain_rnn() {
+    let tokenizer = CharTokenizer::from_corpus(CORPUS);
+    let mut model = CharLanguageModel::new(tokenizer, RnnCell::new(8, 24, 3), 4);
+    let initial = model.loss(CORPUS).unwrap();
+
+    for _ in 0..10 {
+        model.train_epoch(CORPUS, 8, 12, 0.5).unwrap();
+    }
+
+    assert!(model.loss(CORPUS).unwrap() < initial);
+}
+
+#[test]
+fn train_epoch_rejects_short_text() {
+    let mut model = model();
+
+    assert!(model.train_epoch("the", 8, 4, 0.1).is_err());
+}
+
+#[test]
+fn train_epoch_rejects_zero_batch_size_or_window() {
+    let mut model = model();
+
+    for (batch_size, window) in [(0, 4), (4, 0)] {
+        assert_eq!(
+            model.train_epoch(CORPUS, batch_size, window, 0.1),
+            Err("Batch size and window must be greater than zero".to_string())
+        );
+    }
+}
+
+#[test]
+fn generate_starts_with_prompt_and_has_requested_length() {
+    let mut model = model();
+
+    let text = model
+        .generate("the cat", 25, &Sampling::temperature(1.0), 7)
+        .unwrap();
+
+    assert!(text.starts_with("the cat"));
+    assert_eq!(text.chars().count(), 32);
+}
+
+#[test]
+fn generate_is_reproducible_with_seed() {
+    let mut model = model();
+    let sampling = Sampling::temperature(1.2).with_top_k(6);
+
+    let first = model.generate("a ", 40, &sampling, 11).unwrap();
+    let second = model.generate("a ", 40, &sampling, 11).unwrap();
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn generate_rejects_empty_or_unknown_prompt() {
+    let mut model = model();
+
+    assert!(model.generate("", 5, &Sampling::greedy(), 1).is_err());
+    assert!(model.generate("XYZ", 5, &Sampling::greedy(), 1).is_err());
+}
+
+#[test]
+fn greedy_and_top_one_pick_the_largest_logit() {
+    let logits = [0.1, 2.0, -1.0, 1.9];
+    let mut rng = Rng::new(5);
+
+    for _ in 0..20 {
+        assert_eq!(Sampling::greedy().sample(&logits, &mut rng), 1);
+        assert_eq!(
+            Sampling::temperature(5.0)
+                .with_top_k(1)
+                .sample(&logits, &mut rng),
+            1
+        );
+    }
+}
+
+#[test]
+fn top_k_never_samples_outside_the_k_best() {
+    let logits = [3.0, 0.0, 2.5, 0.1, -4.0];
+    let sampling = Sampling::temperature(10.0).with_top_k(2);
+    let mut rng = Rng::new(9);
+
+    for _ in 0..200 {
+        let id = sampling.sample(&logits, &mut rng);
+        assert!(id == 0 || id == 2);
+    }
+}
+
+#[test]
+fn low_temperature_concentrates_samples() {
+    let logits = [1.0, 0.5, 0.0];
+    let count = |temperature: f64| {
+        let mut rng = Rng::new(21);
+        (0..500)
+            .filter(|_| Sampling::temperature(temperature).sample(&logits, &mut rng) == 0)
+            .count()
+    };
+
+    let cold = count(0.1);
+    let hot = count(3.0);
+
+    assert!(cold > 480, "cold {}", cold);
+    assert!(hot < 300, "hot {}", hot);
+}
+
+#[test]
+#[should_panic(expected = "Top-k must be greater than zero")]
+fn top_k_of_zero_panics() {
+    Sampling::temperature(1.0).with_top_k(0);
+}
+
+#[test]
+fn perplexity_is_exponential_of_loss() {
+    let mut model = model();
+
+    let loss = model.loss("the cat sat").unwrap();
+    let perplexity = model.perplexity("the cat sat").unwrap();
+
+    assert!((perplexity - loss.exp()).abs() < 1e-9);
+}
@@ -0,0 +1,127 @@
+use aspirina_core::matrix::Matrix;
+use aspirina_language::embedding::Embedding;
+
+#[test]
+fn forward_looks_up_rows() {
+    let embedding = Embedding::from_weights(Matrix::new(vec![
+        vec![0.0, 0.1],
+        vec![1.0, 1.1],
+        vec![2.0, 2.1],
+    ]));
+
+    let output = embedding.forward(&[2, 0, 2]);
+
+    assert_eq!(embedding.vocab_size(), 3);
+    assert_eq!(embedding.dimension(), 2);
+    assert_eq!(
+        output.data,
+        vec![vec![2.0, 2.1], vec![0.0, 0.1], vec![2.0, 2.1]]
+    );
+}
+
+#[test]
+fn gradient_sums_repeated_ids() {
+    let embedding = Embedding::new(5, 2, 1);
+    let output_gradient = Matrix::new(vec![vec![1.0, 2.0], vec![0.5, 0.5], vec![3.0, -1.0]]);
+
+    let gradient = embedding.gradient(&[4, 1, 4], &output_gradient);
+
+    assert_eq!(gradient.len(), 2);
+    assert_eq!(gradient.row(4), Some(&[4.0, 1.0][..]));
+    assert_eq!(gradient.row(1), Some(&[0.5, 0.5][..]));
+    assert_eq!(gradient.row(0), None);
+    assert_eq!(gradient.squared_norm(), 16.0 + 1.0 + 0.25 + 0.25);
+}
+
+#[test]
+fn update_only_touches_looked_up_rows() {
+    let mut embedding = Embedding::new(6, 3, 2);
+    let before = embedding.weights.clone();
+
+    embedding.update(
+        &[1, 3],
+        &Matrix::new(vec![vec![1.0; 3], vec![-2.0; 3]]),
+        0.1,
+    );
+
+    for id in [0, 2, 4, 5] {
+        assert_eq!(embedding.weights.data[id], before.data[id]);
+    }
+    for (after, before) in embedding.weights.data[1].iter().zip(&before.data[1]) {
+        assert!((after - (before - 0.1)).abs() < 1e-12);
+    }
+    for (after, before) in embedding.weights.data[3].iter().zip(&before.data[3]) {
+        assert!((after - (before + 0.2)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn gradient_matches_numerical_gradient() {
+    // loss = (Σ forward(ids) ⊙ target)²
+    let ids = [2, 0, 2, 1];
+    let target = Matrix::new(vec![
+        vec![0.5, -1.0, 0.2],
+        vec![1.0, 0.3, -0.4],
+        vec![-0.7, 0.1, 0.9],
+        vec![0.2, 0.2, 0.2],
+    ]);
+    let dot = |embedding: &Embedding| -> f64 {
+        let output = embedding.forward(&ids);
+        output
+            .data
+            .iter()
+            .flatten()
+            .zip(target.data.iter().flatten())
+            .map(|(o, t)| o * t)
+            .sum()
+    };
+    let loss = |embedding: &Embedding| dot(embedding).powi(2);
+
+    let embedding = Embedding::new(4, 3, 3);
+    let output_gradient = target.clone().scale(2.0 * dot(&embedding));
+    let gradient = embedding.gradient(&ids, &output_gradient);
+
+    let epsilon = 1e-6;
+    for id in 0..4 {
+        for j in 0..3 {
+            let mut plus = embedding.clone();
+            plus.weights.data[id][j] += epsilon;
+            let mut minus = embedding.clone();
+            minus.weights.data[id][j] -= epsilon;
+            let numerical = (loss(&plus) - loss(&minus)) / (2.0 * epsilon);
+            let analytical = gradient.row(id).map_or(0.0, |row| row[j]);
+
+            assert!(
+                (numerical - analytical).abs() < 1e-5,
+                "row {} col {}: {} vs {}",
+                id,
+                j,
+                numerical,
+                analytical
+            );
+        }
+    }
+}
+
+#[test]
+fn sparse_gradients_can_be_scaled() {
+    let embedding = Embedding::new(3, 2, 4);
+    let gradient = embedding
+        .gradient(&[0], &Matrix::new(vec![vec![2.0, -4.0]]))
+        .scale(0.5);
+
+    assert_eq!(gradient.row(0), Some(&[1.0, -2.0][..]));
+}
+
+#[test]
+#[should_panic(expected = "Token id outside the vocabulary")]
+fn forward_rejects_unknown_ids() {
+    Embedding::new(3, 2, 5).forward(&[3]);
+}
+
+#[test]
+#[should_panic(expected = "Incompatible dimensions")]
+fn gradient_rejects_mismatched_rows() {
+    let embedding = Embedding::new(3, 2, 6);
+    embedding.gradient(&[0, 1], &Matrix::new(vec![vec![1.0, 1.0]]));
+}
@@ -0,0 +1,123 @@
+use aspirina_language::char_model::CORPUS;
+use aspirina_language::ngram::{NGramModel, Smoothing};
+use aspirina_language::tokenizer::{CharTokenizer, Tokenizer};
+
+fn split_corpus() -> (CharTokenizer, Vec<usize>, Vec<usize>) {
+    let tokenizer = CharTokenizer::from_corpus(CORPUS);
+    let (train, held_out) = CORPUS.split_at(CORPUS.rfind("the net").unwrap());
+
+    (
+        tokenizer.clone(),
+        tokenizer.encode(train),
+        tokenizer.encode(held_out),
+    )
+}
+
+fn assert_distribution(model: &NGramModel, context: &[usize], vocab_size: usize) {
+    let total: f64 = (0..vocab_size)
+        .map(|token| model.probability(context, token))
+        .sum();
+    assert!((total - 1.0).abs() < 1e-9, "sums to {}", total);
+}
+
+#[test]
+fn add_one_unigram_matches_hand_computation() {
+    let mut model = NGramModel::new(1, Smoothing::AddK(1.0), 5);
+    model.train(&[4, 4, 3]);
+
+    assert!((model.probability(&[], 4) - 3.0 / 8.0).abs() < 1e-12);
+    assert!((model.probability(&[], 0) - 1.0 / 8.0).abs() < 1e-12);
+}
+
+#[test]
+fn add_k_bigram_uses_context() {
+    let mut model = NGramModel::new(2, Smoothing::AddK(0.5), 6);
+    model.train(&[4, 5, 4, 5, 4]);
+
+    // c(4 5) = 2, c(4) as context = 2
+    assert!((model.probability(&[4], 5) - 2.5 / 5.0).abs() < 1e-12);
+    // unseen context falls back to uniform
+    assert!((model.probability(&[0], 5) - 1.0 / 6.0).abs() < 1e-12);
+}
+
+#[test]
+fn probabilities_sum_to_one() {
+    let (tokenizer, train, _) = split_corpus();
+    let vocab_size = tokenizer.vocab_size();
+
+    for smoothing in [Smoothing::AddK(0.1), Smoothing::KneserNey(0.75)] {
+        let mut model = NGramModel::new(3, smoothing, vocab_size);
+        model.train(&train);
+
+        for context in ["th", "e ", "xq", "a"] {
+            let context = tokenizer.encode(context);
+            assert_distribution(&model, &context, vocab_size);
+        }
+        assert_distribution(&model, &[], vocab_size);
+    }
+}
+
+#[test]
+fn kneser_ney_uses_continuation_counts() {
+    // 5 follows three different tokens, 9 is more frequent but only ever follows 8
+    let mut model = NGramModel::new(2, Smoothing::KneserNey(0.5), 10);
+    model.train(&[4, 5, 6, 5, 7, 5, 8, 9, 8, 9, 8, 9, 8, 9]);
+
+    // after an unseen context, the widely used token wins despite being rarer
+    assert!(model.probability(&[0], 5) > model.probability(&[0], 9));
+}
+
+#[test]
+fn higher_order_lowers_training_perplexity() {
+    let (tokenizer, train, _) = split_corpus();
+    let perplexity = |order| {
+        let mut model = NGramModel::new(order, Smoothing::KneserNey(0.75), tokenizer.vocab_size());
+        model.train(&train);
+        model.perplexity(&train)
+    };
+
+    let unigram = perplexity(1);
+    let trigram = perplexity(3);
+
+    assert!(trigram < unigram / 2.0, "{} vs {}", trigram, unigram);
+}
+
+#[test]
+fn held_out_perplexity_beats_uniform() {
+    let (tokenizer, train, held_out) = split_corpus();
+    let vocab_size = tokenizer.vocab_size() as f64;
+
+    let mut kneser_ney = NGramModel::new(3, Smoothing::KneserNey(0.75), tokenizer.vocab_size());
+    kneser_ney.train(&train);
+    let mut add_one = NGramModel::new(3, Smoothing::AddK(1.0), tokenizer.vocab_size());
+    add_one.train(&train);
+
+    let kneser_ney = kneser_ney.perplexity(&held_out);
+    let add_one = add_one.perplexity(&held_out);
+
+    assert!(kneser_ney < vocab_size / 3.0, "{}", kneser_ney);
+    assert!(kneser_ney < add_one, "{} vs {}", kneser_ney, add_one);
+}
+
+#[test]
+fn training_accumulates_counts() {
+    let mut once = NGramModel::new(2, Smoothing::AddK(1.0), 6);
+    once.train(&[4, 5, 4, 5]);
+    let mut twice = NGramModel::new(2, Smoothing::AddK(1.0), 6);
+    twice.train(&[4, 5]);
+    twice.train(&[4, 5]);
+
+    assert!(twice.probability(&[4], 5) > once.probability(&[4], 5) - 1e-12);
+}
+
+#[test]
+#[should_panic(expected = "Discount must be between 0 and 1")]
+fn invalid_discount_panics() {
+    NGramModel::new(2, Smoothing::KneserNey(1.5), 10);
+}
+
+#[test]
+#[should_panic(expected = "Token id outside the vocabulary")]
+fn unknown_token_panics() {
+    NGramModel::new(2, Smoothing::AddK(1.0), 4).train(&[1, 4]);
+}
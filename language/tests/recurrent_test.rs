@@ -0,0 +1,194 @@
+use aspirina_core::matrix::Matrix;
+use aspirina_core::random::Rng;
+use aspirina_language::recurrent::{
+    GruCell, LstmCell, Recurrent, RecurrentCell, RnnCell, SequenceModel,
+};
+
+fn random_sequence(steps: usize, batch: usize, features: usize, seed: u64) -> Vec<Matrix> {
+    let mut rng = Rng::new(seed);
+    (0..steps)
+        .map(|_| {
+            Matrix::new(
+                (0..batch)
+                    .map(|_| (0..features).map(|_| rng.range(-1.0, 1.0)).collect())
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+/// Largest relative error between the analytic gradients and central differences.
+fn max_gradient_error<C: RecurrentCell>(model: &mut SequenceModel<C>) -> f64 {
+    let inputs = random_sequence(4, 2, 3, 1);
+    let targets = random_sequence(4, 2, 2, 2);
+    let epsilon = 1e-5;
+
+    let analytic = model.gradients(&inputs, &targets);
+    let mut parameters = model.parameters();
+    let mut max_error: f64 = 0.0;
+
+    for p in 0..parameters.len() {
+        for i in 0..parameters[p].data.len() {
+            for j in 0..parameters[p].data[i].len() {
+                let original = parameters[p].data[i][j];
+
+                parameters[p].data[i][j] = original + epsilon;
+                model.set_parameters(parameters.clone());
+                let plus = model.loss(&inputs, &targets);
+
+                parameters[p].data[i][j] = original - epsilon;
+                model.set_parameters(parameters.clone());
+                let minus = model.loss(&inputs, &targets);
+
+                parameters[p].data[i][j] = original;
+                model.set_parameters(parameters.clone());
+
+                let numerical = (plus - minus) / (2.0 * epsilon);
+                let value = analytic[p].data[i][j];
+                let error = (value - numerical).abs() / (value.abs() + numerical.abs()).max(1e-8);
+                max_error = max_error.max(error);
+            }
+        }
+    }
+
+    max_error
+}
+
+/// Target at step `t` is the input from step `t - 1`.
+fn delayed_echo(steps: usize, seed: u64) -> (Vec<Matrix>, Vec<Matrix>) {
+    let mut rng = Rng::new(seed);
+    let bits: Vec<f64> = (0..steps)
+        .map(|_| if rng.next_f64() < 0.5 { 0.0 } else { 1.0 })
+        .collect();
+
+    let inputs = bits.iter().map(|b| Matrix::new(vec![vec![*b]])).collect();
+    let targets = (0..steps)
+        .map(|t| Matrix::new(vec![vec![if t == 0 { 0.0 } else { bits[t - 1] }]]))
+        .collect();
+
+    (inputs, targets)
+}
+
+fn learns_delayed_echo<C: RecurrentCell>(cell: C, learning_rate: f64) {
+    let mut model = SequenceModel::new(cell, 1, 9);
+    let (inputs, targets) = delayed_echo(200, 3);
+
+    let first = model.train_truncated(&inputs, &targets, 8, learning_rate);
+    let mut last = first;
+    for _ in 0..60 {
+        last = model.train_truncated(&inputs, &targets, 8, learning_rate);
+    }
+
+    assert!(last < first * 0.1, "loss went from {} to {}", first, last);
+
+    let outputs = model.predict(&inputs);
+    for t in 1..inputs.len() {
+        let predicted = if outputs[t].data[0][0] > 0.5 {
+            1.0
+        } else {
+            0.0
+        };
+        assert_eq!(predicted, targets[t].data[0][0], "step {}", t);
+    }
+}
+
+#[test]
+fn rnn_gradients_match_finite_differences() {
+    let mut model = SequenceModel::new(RnnCell::new(3, 4, 1), 2, 2);
+    let error = max_gradient_error(&mut model);
+    assert!(error < 1e-5, "{}", error);
+}
+
+#[test]
+fn gru_gradients_match_finite_differences() {
+    let mut model = SequenceModel::new(GruCell::new(3, 4, 3), 2, 4);
+    let error = max_gradient_error(&mut model);
+    assert!(error < 1e-5, "{}", error);
+}
+
+#[test]
+fn lstm_gradients_match_finite_differences() {
+    let mut model = SequenceModel::new(LstmCell::new(3, 4, 5), 2, 6);
+    let error = max_gradient_error(&mut model);
+    assert!(error < 1e-5, "{}", error);
+}
+
+#[test]
+fn streaming_matches_whole_sequence() {
+    let sequence = random_sequence(6, 2, 3, 7);
+    let mut whole = Recurrent::new(LstmCell::new(3, 5, 8));
+    let mut streaming = whole.clone();
+
+    let expected = whole.forward(&sequence);
+
+    for (input, expected) in sequence.iter().zip(expected.iter()) {
+        let output = streaming.step(input);
+        assert_eq!(output.data, expected.data);
+    }
+}
+
+#[test]
+fn state_carries_over_until_reset() {
+    let input = Matrix::new(vec![vec![1.0, -1.0]]);
+    let mut layer = Recurrent::new(RnnCell::new(2, 3, 4));
+    assert!(layer.state().is_none());
+
+    let first = layer.step(&input);
+    let second = layer.step(&input);
+    assert_ne!(first.data, second.data);
+
+    layer.reset_state();
+    assert!(layer.state().is_none());
+    assert_eq!(layer.step(&input).data, first.data);
+}
+
+#[test]
+fn lstm_state_includes_cell() {
+    let mut layer = Recurrent::new(LstmCell::new(1, 2, 0));
+    layer.step(&Matrix::new(vec![vec![1.0]]));
+
+    let state = layer.state().unwrap();
+    assert_eq!(state.cell.as_ref().unwrap().data[0].len(), 2);
+    assert!(Recurrent::new(GruCell::new(1, 2, 0))
+        .cell
+        .initial_state(1)
+        .cell
+        .is_none());
+}
+
+#[test]
+fn backward_returns_one_input_gradient_per_step() {
+    let sequence = random_sequence(5, 3, 2, 11);
+    let mut layer = Recurrent::new(GruCell::new(2, 4, 12));
+    let outputs = layer.forward(&sequence);
+
+    let gradients = layer.backward(&outputs);
+
+    assert_eq!(gradients.inputs.len(), 5);
+    assert_eq!(gradients.inputs[0].data.len(), 3);
+    assert_eq!(gradients.inputs[0].data[0].len(), 2);
+    assert_eq!(gradients.parameters.len(), 9);
+}
+
+#[test]
+#[should_panic(expected = "Input batch size does not match the carried state")]
+fn carried_state_rejects_different_batch_size() {
+    let mut layer = Recurrent::new(RnnCell::new(1, 2, 0));
+    layer.step(&Matrix::new(vec![vec![1.0]]));
+    layer.step(&Matrix::new(vec![vec![1.0], vec![0.0]]));
+}
+
+#[test]
+fn rnn_learns_delayed_echo_with_truncated_bptt() {
+    learns_delayed_echo(RnnCell::new(1, 6, 1), 0.05);
+}
+
+#[test]
+fn gru_learns_delayed_echo_with_truncated_bptt() {
+    learns_delayed_echo(GruCell::new(1, 6, 1), 0.05);
+}
+
+#[test]
+fn lstm_learns_delayed_echo_with_truncated_bptt() {
+    learns_delayed_echo(LstmCell::new(1, 6, 1), 0.05);
+}
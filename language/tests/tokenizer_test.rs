@@ -0,0 +1,162 @@
+use aspirina_language::char_model::CORPUS;
+use aspirina_language::tokenizer::{
+    BpeTokenizer, CharTokenizer, Tokenizer, Vocabulary, WordTokenizer,
+};
+
+fn round_trip(tokenizer: &impl Tokenizer, text: &str) {
+    assert_eq!(tokenizer.decode(&tokenizer.encode(text)), text);
+}
+
+#[test]
+fn tokenizer_round_trip() {
+    let tokenizer = CharTokenizer::from_corpus(CORPUS);
+    let ids = tokenizer.encode(CORPUS);
+
+    assert_eq!(tokenizer.decode(&ids), CORPUS);
+    assert!(ids.iter().all(|id| *id < tokenizer.vocab_size()));
+}
+
+#[test]
+fn tokenizer_rejects_unknown_characters() {
+    let tokenizer = CharTokenizer::from_corpus("abc");
+
+    assert_eq!(
+        tokenizer.try_encode("abz"),
+        Err("Unknown token \"z\"".to_string())
+    );
+}
+
+#[test]
+fn vocabulary_starts_with_special_tokens() {
+    let tokenizer = CharTokenizer::from_corpus("ba");
+    let vocabulary = tokenizer.vocabulary();
+
+    assert_eq!(vocabulary.token(Vocabulary::PAD), Some("<pad>"));
+    assert_eq!(vocabulary.token(Vocabulary::UNK), Some("<unk>"));
+    assert_eq!(vocabulary.token(Vocabulary::BOS), Some("<bos>"));
+    assert_eq!(vocabulary.token(Vocabulary::EOS), Some("<eos>"));
+    assert_eq!(vocabulary.id("a"), Some(4));
+    assert_eq!(vocabulary.id("b"), Some(5));
+}
+
+#[test]
+fn unknown_pieces_encode_as_unk() {
+    let tokenizer = CharTokenizer::from_corpus("abc");
+
+    assert_eq!(tokenizer.encode("azb"), vec![4, Vocabulary::UNK, 5]);
+    assert_eq!(tokenizer.decode(&tokenizer.encode("azb")), "a<unk>b");
+}
+
+#[test]
+fn decode_drops_padding_and_bounds() {
+    let tokenizer = WordTokenizer::from_corpus("the cat sat", 1);
+    let mut ids = tokenizer.encode_with_bounds("the cat");
+
+    assert_eq!(ids.first(), Some(&Vocabulary::BOS));
+    assert_eq!(ids.last(), Some(&Vocabulary::EOS));
+
+    ids.push(Vocabulary::PAD);
+    assert_eq!(tokenizer.decode(&ids), "the cat");
+}
+
+#[test]
+fn word_tokenizer_orders_by_frequency_and_drops_rare_words() {
+    let tokenizer = WordTokenizer::from_corpus("b a b c b a", 2);
+
+    assert_eq!(tokenizer.vocab_size(), 6);
+    assert_eq!(tokenizer.vocabulary().id("b"), Some(4));
+    assert_eq!(tokenizer.vocabulary().id("a"), Some(5));
+    assert_eq!(tokenizer.encode("c a"), vec![Vocabulary::UNK, 5]);
+    round_trip(&tokenizer, "a b b a");
+}
+
+#[test]
+fn bpe_learns_frequent_merges() {
+    let tokenizer = BpeTokenizer::train("low low low lower lowest", 20);
+
+    assert_eq!(tokenizer.merges()[0], ("l".to_string(), "o".to_string()));
+    assert!(tokenizer.vocabulary().id(" low").is_some());
+    assert!(tokenizer.encode(" low").len() == 1);
+    assert!(tokenizer.encode("lowest").len() < 6);
+}
+
+#[test]
+fn bpe_round_trips_the_corpus_and_new_text() {
+    let tokenizer = BpeTokenizer::train(CORPUS, 80);
+
+    round_trip(&tokenizer, CORPUS);
+    round_trip(&tokenizer, "the  dog\nsat. then the cat ran.");
+    assert!(tokenizer.encode(CORPUS).len() < CORPUS.chars().count() / 2);
+}
+
+#[test]
+fn bpe_stops_when_no_pair_repeats() {
+    let tokenizer = BpeTokenizer::train("abcd", 100);
+
+    assert!(tokenizer.merges().is_empty());
+    assert_eq!(tokenizer.vocab_size(), 8);
+}
+
+#[test]
+fn vocabulary_files_round_trip() {
+    let path = std::env::temp_dir().join("aspirina_tokenizer_test.vocab");
+    let text = "tab\there, back\\slash\nand the new line";
+
+    let chars = CharTokenizer::from_corpus(text);
+    chars.save(&path).unwrap();
+    assert_eq!(CharTokenizer::load(&path).unwrap(), chars);
+
+    let words = WordTokenizer::from_corpus(CORPUS, 1);
+    words.save(path.to_str().unwrap()).unwrap();
+    assert_eq!(WordTokenizer::load(&path).unwrap(), words);
+
+    let bpe = BpeTokenizer::train(CORPUS, 60);
+    bpe.save(&path).unwrap();
+    let loaded = BpeTokenizer::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, bpe);
+    assert_eq!(loaded.encode(CORPUS), bpe.encode(CORPUS));
+    assert!(CharTokenizer::load(&path).is_err());
+}
+
+#[test]
+fn vocabulary_parse_errors() {
+    assert_eq!(
+        CharTokenizer::parse("<pad>\n<unk>\n<bos>\n<eos>\na\na\n"),
+        Err("Line 6: duplicate token \"a\"".to_string())
+    );
+    assert_eq!(
+        Vocabulary::parse("a b\n"),
+        Err("Line 1: expected a single token".to_string())
+    );
+    assert_eq!(
+        Vocabulary::parse("a\\q\n"),
+        Err("Line 1: invalid escape".to_string())
+    );
+    assert!(CharTokenizer::parse("ab\n").is_err());
+    assert!(BpeTokenizer::parse("a\nx y\n").is_err());
+}
+
+#[test]
+fn one_hot_batch_pads_shorter_sequences() {
+    let tokenizer = CharTokenizer::from_corpus("ab");
+    let batch = tokenizer.one_hot_batch(&[vec![4, 5], vec![5]]);
+
+    assert_eq!(batch.len(), 2);
+    assert_eq!(
+        batch[0].data,
+        vec![
+            vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 0.0, 0.0, 0.0, 1.0]
+        ]
+    );
+    assert_eq!(batch[1].data[1], vec![1.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+    assert_eq!(tokenizer.one_hot(&[5, 4]).data[0][5], 1.0);
+}
+
+#[test]
+#[should_panic(expected = "Token id outside the vocabulary")]
+fn one_hot_rejects_unknown_ids() {
+    CharTokenizer::from_corpus("ab").one_hot(&[6]);
+}
@@ -0,0 +1,165 @@
+use aspirina_core::matrix::Matrix;
+use aspirina_language::attention::{scaled_dot_product_attention, MultiHeadAttention};
+use aspirina_language::char_model::{Sampling, CORPUS};
+use aspirina_language::tokenizer::{CharTokenizer, Tokenizer};
+use aspirina_language::transformer::{
+    positional_encoding, LayerNorm, Transformer, TransformerConfig,
+};
+
+fn small_model() -> Transformer {
+    let config = TransformerConfig::new(9, 6, 2)
+        .with_layers(2)
+        .with_hidden_size(8)
+        .with_context_length(5);
+    Transformer::new(config, 3)
+}
+
+#[test]
+fn gradients_match_numerical_gradients() {
+    let mut model = small_model();
+    let sequence = [4, 7, 5, 8, 4, 6];
+    let analytical = model.gradients(&sequence);
+    let parameters = model.parameters();
+    assert_eq!(analytical.len(), parameters.len());
+
+    let epsilon = 1e-5;
+    for (index, parameter) in parameters.iter().enumerate() {
+        for i in 0..parameter.data.len() {
+            for j in 0..parameter.data[i].len() {
+                let mut shifted = parameters.clone();
+                shifted[index].data[i][j] += epsilon;
+                model.set_parameters(shifted.clone());
+                let plus = model.loss(&sequence);
+
+                shifted[index].data[i][j] -= 2.0 * epsilon;
+                model.set_parameters(shifted);
+                let minus = model.loss(&sequence);
+
+                let numerical = (plus - minus) / (2.0 * epsilon);
+                let expected = analytical[index].data[i][j];
+                assert!(
+                    (numerical - expected).abs() < 1e-6,
+                    "parameter {} [{}][{}]: numerical {} analytical {}",
+                    index,
+                    i,
+                    j,
+                    numerical,
+                    expected
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn causal_attention_weights() {
+    let x = Matrix::new(vec![vec![0.5, -1.0], vec![2.0, 0.1], vec![-0.3, 0.7]]);
+
+    let (output, weights) = scaled_dot_product_attention(&x, &x, &x, true);
+
+    assert_eq!(output.data[0], x.data[0]);
+    for (i, row) in weights.data.iter().enumerate() {
+        assert!((row.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(row[i + 1..].iter().all(|w| *w == 0.0));
+    }
+
+    let (_, unmasked) = scaled_dot_product_attention(&x, &x, &x, false);
+    assert!(unmasked.data[0][2] > 0.0);
+}
+
+#[test]
+fn attention_scales_by_key_size() {
+    let query = Matrix::new(vec![vec![2.0, 2.0, 2.0, 2.0]]);
+    let key = Matrix::new(vec![vec![1.0, 1.0, 1.0, 1.0], vec![0.0, 0.0, 0.0, 0.0]]);
+    let value = Matrix::new(vec![vec![1.0], vec![0.0]]);
+
+    let (output, _) = scaled_dot_product_attention(&query, &key, &value, false);
+
+    // scores 8 / √4 = 4 and 0
+    let expected = 4f64.exp() / (4f64.exp() + 1.0);
+    assert!((output.data[0][0] - expected).abs() < 1e-12);
+}
+
+#[test]
+fn multi_head_attention_is_causal() {
+    let attention = MultiHeadAttention::new(4, 2, 5);
+    let a = Matrix::new(vec![vec![0.1, 0.2, 0.3, 0.4], vec![1.0, 0.0, -1.0, 0.5]]);
+    let b = Matrix::new(vec![vec![0.1, 0.2, 0.3, 0.4], vec![-2.0, 3.0, 0.0, 0.1]]);
+
+    assert_eq!(attention.forward(&a).data[0], attention.forward(&b).data[0]);
+    assert_ne!(attention.forward(&a).data[1], attention.forward(&b).data[1]);
+    assert_eq!(attention.attention_weights(&a).len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Model size must be divisible by the number of heads")]
+fn heads_must_divide_model_size() {
+    MultiHeadAttention::new(6, 4, 1);
+}
+
+#[test]
+fn model_predictions_only_depend_on_the_past() {
+    let model = small_model();
+
+    let a = model.forward(&[4, 5, 6, 7]);
+    let b = model.forward(&[4, 5, 8, 8]);
+
+    assert_eq!(a.data[0], b.data[0]);
+    assert_eq!(a.data[1], b.data[1]);
+    assert_ne!(a.data[2], b.data[2]);
+}
+
+#[test]
+fn layer_norm_normalizes_rows() {
+    let norm = LayerNorm::new(4);
+    let output = norm.forward(&Matrix::new(vec![
+        vec![1.0, 2.0, 3.0, 4.0],
+        vec![-5.0, 0.0, 5.0, 10.0],
+    ]));
+
+    for row in &output.data {
+        let mean = row.iter().sum::<f64>() / 4.0;
+        let variance = row.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 4.0;
+        assert!(mean.abs() < 1e-12);
+        assert!((variance - 1.0).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn positional_encoding_is_distinct_per_position() {
+    let encoding = positional_encoding(8, 6);
+
+    assert_eq!(encoding.data[0], vec![0.0, 1.0, 0.0, 1.0, 0.0, 1.0]);
+    for i in 0..8 {
+        for j in i + 1..8 {
+            assert_ne!(encoding.data[i], encoding.data[j]);
+        }
+    }
+}
+
+#[test]
+fn trains_end_to_end_on_toy_corpus() {
+    let tokenizer = CharTokenizer::from_corpus(CORPUS);
+    let tokens = tokenizer.encode(CORPUS);
+    let config = TransformerConfig::new(tokenizer.vocab_size(), 16, 2).with_context_length(16);
+    let mut model = Transformer::new(config, 7);
+
+    let first = model.train_epoch(&tokens, 4, 0.5);
+    let mut last = first;
+    for _ in 0..30 {
+        last = model.train_epoch(&tokens, 4, 0.5);
+    }
+
+    assert!(last < first / 2.0, "{} -> {}", first, last);
+
+    let prompt = tokenizer.encode("the ");
+    let generated = model.generate(&prompt, 20, &Sampling::greedy(), 1);
+    assert_eq!(generated.len(), 24);
+    assert!(tokenizer.decode(&generated).starts_with("the "));
+}
+
+#[test]
+#[should_panic(expected = "Sequence length must be between 1 and the context length")]
+fn forward_rejects_sequences_longer_than_context() {
+    small_model().forward(&[4, 4, 4, 4, 4, 4]);
+}